@@ -6,7 +6,7 @@ use super::FeatureVariations;
 use super::LookupList;
 #[cfg(feature = "variable-fonts")]
 use crate::parser::Offset32;
-use crate::parser::{FromData, LazyArray16, Offset, Offset16, Stream};
+use crate::parser::{FromData, LazyArray16, Offset, Offset16, Stream, U24};
 use crate::Tag;
 
 /// A [Layout Table](https://docs.microsoft.com/en-us/typography/opentype/spec/chapter2#table-organization).
@@ -88,18 +88,27 @@ pub type VariationIndex = u32;
 pub trait RecordListItem<'a>: Sized {
     /// Parses raw data.
     fn parse(tag: Tag, data: &'a [u8]) -> Option<Self>;
+
+    /// Parses raw data stored in a [`RecordList`].
+    ///
+    /// `list_data` is the whole list data.
+    #[inline]
+    fn parse_in_list(tag: Tag, data: &'a [u8], list_data: &'a [u8]) -> Option<Self> {
+        let _ = list_data;
+        Self::parse(tag, data)
+    }
 }
 
 /// A data storage used by [`ScriptList`], [`LanguageSystemList`] and [`FeatureList`] data types.
 #[derive(Clone, Copy, Debug)]
 pub struct RecordList<'a, T: RecordListItem<'a>> {
-    data: &'a [u8],
+    pub(crate) data: &'a [u8],
     records: LazyArray16<'a, TagRecord>,
     data_type: core::marker::PhantomData<T>,
 }
 
 impl<'a, T: RecordListItem<'a>> RecordList<'a, T> {
-    fn parse(data: &'a [u8]) -> Option<Self> {
+    pub(crate) fn parse(data: &'a [u8]) -> Option<Self> {
         let mut s = Stream::new(data);
         let count = s.read::<u16>()?;
         let records = s.read_array16(count)?;
@@ -125,7 +134,7 @@ impl<'a, T: RecordListItem<'a>> RecordList<'a, T> {
         let record = self.records.get(index)?;
         self.data
             .get(record.offset.to_usize()..)
-            .and_then(|data| T::parse(record.tag, data))
+            .and_then(|data| T::parse_in_list(record.tag, data, self.data))
     }
 
     /// Returns RecordList value by [`Tag`].
@@ -136,7 +145,7 @@ impl<'a, T: RecordListItem<'a>> RecordList<'a, T> {
             .map(|p| p.1)?;
         self.data
             .get(record.offset.to_usize()..)
-            .and_then(|data| T::parse(record.tag, data))
+            .and_then(|data| T::parse_in_list(record.tag, data, self.data))
     }
 
     /// Returns RecordList value index by [`Tag`].
@@ -270,18 +279,202 @@ impl<'a> RecordListItem<'a> for LanguageSystem<'a> {
 #[derive(Clone, Copy, Debug)]
 pub struct Feature<'a> {
     pub tag: Tag,
+    /// Feature parameters.
+    ///
+    /// Set only for `size`, `ssXX` and `cvXX` features with valid parameters.
+    pub params: Option<FeatureParams<'a>>,
     pub lookup_indices: LazyArray16<'a, LookupIndex>,
 }
 
 impl<'a> RecordListItem<'a> for Feature<'a> {
     fn parse(tag: Tag, data: &'a [u8]) -> Option<Self> {
         let mut s = Stream::new(data);
-        let _params_offset = s.read::<Offset16>()?; // Unsupported.
+        let params = s
+            .read::<Option<Offset16>>()?
+            .and_then(|offset| data.get(offset.to_usize()..))
+            .and_then(|data| FeatureParams::parse(tag, data));
         let count = s.read::<u16>()?;
         let lookup_indices = s.read_array16(count)?;
         Some(Self {
             tag,
+            params,
             lookup_indices,
         })
     }
+
+    fn parse_in_list(tag: Tag, data: &'a [u8], list_data: &'a [u8]) -> Option<Self> {
+        let mut feature = Self::parse(tag, data)?;
+        if feature.params.is_none() && &tag.to_bytes() == b"size" {
+            // Some old Adobe fonts store the `size` parameters offset
+            // relative to the FeatureList and not to the Feature table.
+            feature.params = Stream::read_at::<Option<Offset16>>(data, 0)?
+                .and_then(|offset| list_data.get(offset.to_usize()..))
+                .and_then(SizeParams::parse)
+                .map(FeatureParams::Size);
+        }
+
+        Some(feature)
+    }
+}
+
+/// [Feature parameters](https://docs.microsoft.com/en-us/typography/opentype/spec/chapter2#featureparams-tables).
+#[derive(Clone, Copy, Debug)]
+pub enum FeatureParams<'a> {
+    /// Parameters of the `size` feature.
+    Size(SizeParams),
+    /// Parameters of the `ss01` - `ss20` features.
+    StylisticSet(StylisticSetParams),
+    /// Parameters of the `cv01` - `cv99` features.
+    CharacterVariant(CharacterVariantParams<'a>),
+}
+
+impl<'a> FeatureParams<'a> {
+    fn parse(tag: Tag, data: &'a [u8]) -> Option<Self> {
+        let tag = tag.to_bytes();
+        // Returns a feature number for tags like `ss01`.
+        let number = |a: u8, b: u8| {
+            if a.is_ascii_digit() && b.is_ascii_digit() {
+                Some((a - b'0') * 10 + (b - b'0'))
+            } else {
+                None
+            }
+        };
+
+        let n = number(tag[2], tag[3]).unwrap_or(0);
+        if &tag == b"size" {
+            SizeParams::parse(data).map(Self::Size)
+        } else if &tag[0..2] == b"ss" && (1..=20).contains(&n) {
+            StylisticSetParams::parse(data).map(Self::StylisticSet)
+        } else if &tag[0..2] == b"cv" && (1..=99).contains(&n) {
+            CharacterVariantParams::parse(data).map(Self::CharacterVariant)
+        } else {
+            None
+        }
+    }
+}
+
+/// Parameters of the [`size`](https://docs.microsoft.com/en-us/typography/opentype/spec/features_pt#tag-size)
+/// feature.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct SizeParams {
+    /// The design size in 720/inch units (decipoints).
+    pub design_size: u16,
+    /// Identifies a font subfamily, which differs only in the optical size.
+    ///
+    /// Zero, when the font has no recommended size range.
+    pub subfamily_id: u16,
+    /// A name ID of the subfamily name.
+    pub subfamily_name_id: Option<u16>,
+    /// The smallest size, in decipoints, the font is intended for. Exclusive.
+    pub range_start: u16,
+    /// The largest size, in decipoints, the font is intended for. Inclusive.
+    pub range_end: u16,
+}
+
+impl SizeParams {
+    fn parse(data: &[u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+        let design_size = s.read::<u16>()?;
+        let subfamily_id = s.read::<u16>()?;
+        let subfamily_name_id = s.read::<u16>()?;
+        let range_start = s.read::<u16>()?;
+        let range_end = s.read::<u16>()?;
+
+        if design_size == 0 {
+            return None;
+        }
+
+        if subfamily_id == 0 && subfamily_name_id == 0 {
+            // No size range.
+            if range_start != 0 || range_end != 0 {
+                return None;
+            }
+        } else if design_size < range_start
+            || design_size > range_end
+            || !(256..=32767).contains(&subfamily_name_id)
+        {
+            return None;
+        }
+
+        Some(SizeParams {
+            design_size,
+            subfamily_id,
+            subfamily_name_id: Some(subfamily_name_id).filter(|id| *id != 0),
+            range_start,
+            range_end,
+        })
+    }
+}
+
+/// Parameters of the [`ssXX`](https://docs.microsoft.com/en-us/typography/opentype/spec/features_pt#ssxx)
+/// features.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct StylisticSetParams {
+    /// A name ID of the user-interface string for this stylistic set.
+    pub ui_name_id: u16,
+}
+
+impl StylisticSetParams {
+    fn parse(data: &[u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+        s.skip::<u16>(); // version
+        let ui_name_id = s.read::<u16>()?;
+        Some(StylisticSetParams { ui_name_id })
+    }
+}
+
+/// Parameters of the [`cvXX`](https://docs.microsoft.com/en-us/typography/opentype/spec/features_ae#cv01-cv99)
+/// features.
+#[derive(Clone, Copy, Debug)]
+pub struct CharacterVariantParams<'a> {
+    /// A name ID of the user-interface label for this feature.
+    pub label_name_id: Option<u16>,
+    /// A name ID of the tooltip text for this feature.
+    pub tooltip_name_id: Option<u16>,
+    /// A name ID of the sample text that illustrates the effect of this feature.
+    pub sample_text_name_id: Option<u16>,
+    /// The number of named parameters.
+    pub named_parameters_count: u16,
+    /// A name ID of the first named parameter label.
+    ///
+    /// Other labels use consecutive name IDs.
+    pub first_param_label_name_id: Option<u16>,
+    /// Unicode code points for which this feature provides glyph variants.
+    pub characters: LazyArray16<'a, U24>,
+}
+
+impl<'a> CharacterVariantParams<'a> {
+    fn parse(data: &'a [u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+        let format = s.read::<u16>()?;
+        if format != 0 {
+            return None;
+        }
+
+        let name_id = |id: u16| Some(id).filter(|id| *id != 0);
+        let label_name_id = name_id(s.read::<u16>()?);
+        let tooltip_name_id = name_id(s.read::<u16>()?);
+        let sample_text_name_id = name_id(s.read::<u16>()?);
+        let named_parameters_count = s.read::<u16>()?;
+        let first_param_label_name_id = name_id(s.read::<u16>()?);
+        let count = s.read::<u16>()?;
+        let characters = s.read_array16::<U24>(count)?;
+        Some(CharacterVariantParams {
+            label_name_id,
+            tooltip_name_id,
+            sample_text_name_id,
+            named_parameters_count,
+            first_param_label_name_id,
+            characters,
+        })
+    }
+
+    /// Returns a name ID of a named parameter label at the specified index.
+    pub fn param_label_name_id(&self, index: u16) -> Option<u16> {
+        if index >= self.named_parameters_count {
+            return None;
+        }
+
+        self.first_param_label_name_id?.checked_add(index)
+    }
 }
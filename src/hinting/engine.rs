@@ -0,0 +1,1555 @@
+//! A TrueType bytecode interpreter.
+//!
+//! Follows the [TrueType Instruction Set](
+//! https://developer.apple.com/fonts/TrueType-Reference-Manual/RM05/Chap5.html)
+//! and the behavior of the version 35 FreeType interpreter for undocumented cases.
+
+use core::convert::TryFrom;
+use std::vec::Vec;
+
+use super::graphics::{GraphicsState, RoundState, Vector};
+use super::math;
+use super::zone::{Point, Zone, ON_CURVE, TOUCHED, TOUCHED_X, TOUCHED_Y};
+use crate::truetype_instructions::next_instruction;
+
+// Not defined in the spec, so we are using FreeType's values.
+const MAX_CALL_DEPTH: usize = 32;
+const MAX_INSTRUCTIONS: u32 = 1_000_000;
+// Some fonts underestimate the stack depth in `maxp`.
+const EXTRA_STACK_ELEMENTS: usize = 32;
+
+const GRID_PERIOD: i32 = 0x4000;
+const GRID_PERIOD_45: i32 = 0x2D41;
+
+/// A program type.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Program {
+    /// The `fpgm` table.
+    Font,
+    /// The `prep` table.
+    ControlValue,
+    /// Glyph instructions.
+    Glyph,
+}
+
+/// An execution error.
+///
+/// Any error aborts the current program.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Error {
+    StackOverflow,
+    StackUnderflow,
+    CallStackOverflow,
+    InvalidOpcode,
+    InvalidArgument,
+    InvalidReference,
+    InvalidDefinition,
+    InvalidJump,
+    DivisionByZero,
+    UnexpectedEnd,
+    ExecutionTooLong,
+}
+
+/// A function or an instruction definition.
+#[derive(Clone, Copy, Debug)]
+struct Definition {
+    program: Program,
+    start: usize,
+    // Offset of the ENDF instruction.
+    end: usize,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct CallRecord {
+    caller_program: Program,
+    return_pc: usize,
+    definition: Definition,
+    count: i32,
+}
+
+/// Engine limits.
+///
+/// Usually come from the `maxp` table.
+#[derive(Clone, Copy, Debug)]
+pub struct Limits {
+    pub stack_elements: u16,
+    pub storage: u16,
+    pub function_defs: u16,
+    pub instruction_defs: u16,
+    pub twilight_points: u16,
+}
+
+pub struct Engine<'a> {
+    font_program: &'a [u8],
+    control_value_program: &'a [u8],
+    pub glyph_program: &'a [u8],
+    program: Program,
+    stack: Vec<i32>,
+    stack_limit: usize,
+    call_stack: Vec<CallRecord>,
+    functions: Vec<Option<Definition>>,
+    instructions: Vec<Option<Definition>>,
+    max_instruction_defs: usize,
+    pub cvt: Vec<i32>,
+    pub storage: Vec<i32>,
+    pub gs: GraphicsState,
+    pub twilight: Zone,
+    pub glyph: Zone,
+    pub ppem: u16,
+    /// A 16.16 scale from font units to 26.6.
+    pub scale: i32,
+    /// A 16.16 scale applied to the unscaled points when measuring original distances.
+    ///
+    /// Equals to `scale` for simple glyphs and to 1.0 for composite ones,
+    /// since composite glyphs instructions refer to already hinted components.
+    pub unscaled_scale: i32,
+    /// Normalized variation coordinates.
+    ///
+    /// Empty for non-variable fonts.
+    pub coords: Vec<i32>,
+    pub is_variable: bool,
+}
+
+impl<'a> Engine<'a> {
+    pub fn new(font_program: &'a [u8], control_value_program: &'a [u8], limits: Limits) -> Self {
+        let mut twilight = Zone::default();
+        // Reserve space for phantom points, like FreeType does.
+        twilight.reset(usize::from(limits.twilight_points) + 4);
+
+        Engine {
+            font_program,
+            control_value_program,
+            glyph_program: &[],
+            program: Program::Font,
+            stack: Vec::new(),
+            stack_limit: usize::from(limits.stack_elements) + EXTRA_STACK_ELEMENTS,
+            call_stack: Vec::new(),
+            functions: vec![None; usize::from(limits.function_defs)],
+            instructions: vec![None; 256],
+            max_instruction_defs: usize::from(limits.instruction_defs),
+            cvt: Vec::new(),
+            storage: vec![0; usize::from(limits.storage)],
+            gs: GraphicsState::default(),
+            twilight,
+            glyph: Zone::default(),
+            ppem: 0,
+            scale: 0x10000,
+            unscaled_scale: 0x10000,
+            coords: Vec::new(),
+            is_variable: false,
+        }
+    }
+
+    #[inline]
+    fn code(&self, program: Program) -> &'a [u8] {
+        match program {
+            Program::Font => self.font_program,
+            Program::ControlValue => self.control_value_program,
+            Program::Glyph => self.glyph_program,
+        }
+    }
+
+    /// Executes a program.
+    ///
+    /// The stack, the vectors, the zone pointers, the loop counter
+    /// and the round state are reset before execution.
+    pub fn execute(&mut self, program: Program) -> Result<(), Error> {
+        self.gs.reset_program_state();
+        self.stack.clear();
+        self.call_stack.clear();
+        self.program = program;
+
+        let mut program = program;
+        let mut pc = 0;
+        let mut count = 0;
+        loop {
+            let code = self.code(program);
+            let opcode = match code.get(pc) {
+                Some(opcode) => *opcode,
+                None => {
+                    if self.call_stack.is_empty() {
+                        return Ok(());
+                    } else {
+                        // A function without ENDF.
+                        return Err(Error::UnexpectedEnd);
+                    }
+                }
+            };
+
+            count += 1;
+            if count > MAX_INSTRUCTIONS {
+                return Err(Error::ExecutionTooLong);
+            }
+
+            let next_pc = next_instruction(code, pc).ok_or(Error::UnexpectedEnd)?;
+            pc = match opcode {
+                // IF
+                0x58 => {
+                    if self.pop()? != 0 {
+                        next_pc
+                    } else {
+                        skip_branch(code, next_pc, true)?
+                    }
+                }
+                // ELSE
+                0x1B => skip_branch(code, next_pc, false)?,
+                // EIF
+                0x59 => next_pc,
+                // JMPR
+                0x1C => {
+                    let offset = self.pop()?;
+                    self.jump(pc, offset)?
+                }
+                // JROT, JROF
+                0x78 | 0x79 => {
+                    let condition = self.pop()?;
+                    let offset = self.pop()?;
+                    if (condition != 0) == (opcode == 0x78) {
+                        self.jump(pc, offset)?
+                    } else {
+                        next_pc
+                    }
+                }
+                // FDEF, IDEF
+                0x2C | 0x89 => {
+                    if self.program == Program::Glyph {
+                        return Err(Error::InvalidDefinition);
+                    }
+
+                    let index = self.pop()?;
+                    let end = find_definition_end(code, next_pc)?;
+                    let definition = Definition {
+                        program,
+                        start: next_pc,
+                        end,
+                    };
+
+                    if opcode == 0x2C {
+                        let slot = usize::try_from(index)
+                            .ok()
+                            .and_then(|i| self.functions.get_mut(i))
+                            .ok_or(Error::InvalidDefinition)?;
+                        *slot = Some(definition);
+                    } else {
+                        let defined = self.instructions.iter().filter(|d| d.is_some()).count();
+                        let max_defined = self.max_instruction_defs;
+                        let slot = usize::try_from(index)
+                            .ok()
+                            .and_then(|i| self.instructions.get_mut(i))
+                            .ok_or(Error::InvalidDefinition)?;
+                        if slot.is_none() && defined >= max_defined {
+                            return Err(Error::InvalidDefinition);
+                        }
+
+                        *slot = Some(definition);
+                    }
+
+                    end + 1
+                }
+                // ENDF
+                0x2D => {
+                    let record = self.call_stack.last_mut().ok_or(Error::InvalidOpcode)?;
+                    record.count -= 1;
+                    if record.count > 0 {
+                        record.definition.start
+                    } else {
+                        program = record.caller_program;
+                        let return_pc = record.return_pc;
+                        self.call_stack.pop();
+                        return_pc
+                    }
+                }
+                // LOOPCALL, CALL
+                0x2A | 0x2B => {
+                    let index = self.pop()?;
+                    let count = if opcode == 0x2A { self.pop()? } else { 1 };
+                    let definition = usize::try_from(index)
+                        .ok()
+                        .and_then(|i| self.functions.get(i).copied())
+                        .flatten()
+                        .ok_or(Error::InvalidReference)?;
+
+                    if count > 0 {
+                        self.call(definition, count, program, next_pc)?;
+                        program = definition.program;
+                        definition.start
+                    } else {
+                        next_pc
+                    }
+                }
+                _ => {
+                    if is_defined(opcode, self.is_variable) {
+                        self.execute_instruction(opcode, code.get(pc + 1..next_pc).unwrap_or(&[]))?;
+                        next_pc
+                    } else {
+                        // Undefined opcodes can be set via IDEF.
+                        let definition =
+                            self.instructions[usize::from(opcode)].ok_or(Error::InvalidOpcode)?;
+                        self.call(definition, 1, program, next_pc)?;
+                        program = definition.program;
+                        definition.start
+                    }
+                }
+            };
+        }
+    }
+
+    fn call(
+        &mut self,
+        definition: Definition,
+        count: i32,
+        caller_program: Program,
+        return_pc: usize,
+    ) -> Result<(), Error> {
+        if self.call_stack.len() >= MAX_CALL_DEPTH {
+            return Err(Error::CallStackOverflow);
+        }
+
+        self.call_stack.push(CallRecord {
+            caller_program,
+            return_pc,
+            definition,
+            count,
+        });
+
+        Ok(())
+    }
+
+    fn jump(&self, pc: usize, offset: i32) -> Result<usize, Error> {
+        if offset == 0 && self.stack.is_empty() {
+            return Err(Error::InvalidJump);
+        }
+
+        let pc = i64::try_from(pc).map_err(|_| Error::InvalidJump)? + i64::from(offset);
+        let pc = usize::try_from(pc).map_err(|_| Error::InvalidJump)?;
+        if let Some(record) = self.call_stack.last() {
+            if pc > record.definition.end {
+                return Err(Error::InvalidJump);
+            }
+        }
+
+        Ok(pc)
+    }
+
+    #[inline]
+    fn push(&mut self, value: i32) -> Result<(), Error> {
+        if self.stack.len() >= self.stack_limit {
+            return Err(Error::StackOverflow);
+        }
+
+        self.stack.push(value);
+        Ok(())
+    }
+
+    #[inline]
+    fn pop(&mut self) -> Result<i32, Error> {
+        self.stack.pop().ok_or(Error::StackUnderflow)
+    }
+
+    /// Pops a point or a contour index.
+    ///
+    /// Negative values are mapped to an invalid index.
+    #[inline]
+    fn pop_index(&mut self) -> Result<usize, Error> {
+        Ok(usize::try_from(self.pop()?).unwrap_or(usize::MAX))
+    }
+
+    #[inline]
+    fn zone(&self, zp: u8) -> &Zone {
+        if zp == 0 {
+            &self.twilight
+        } else {
+            &self.glyph
+        }
+    }
+
+    #[inline]
+    fn zone_mut(&mut self, zp: u8) -> &mut Zone {
+        if zp == 0 {
+            &mut self.twilight
+        } else {
+            &mut self.glyph
+        }
+    }
+
+    #[inline]
+    fn in_zone(&self, zp: u8, point: usize) -> bool {
+        point < self.zone(zp).len()
+    }
+
+    #[inline]
+    fn current(&self, zp: u8, point: usize) -> Point {
+        self.zone(zp).points[point]
+    }
+
+    #[inline]
+    fn original(&self, zp: u8, point: usize) -> Point {
+        self.zone(zp).original[point]
+    }
+
+    #[inline]
+    fn project(&self, p1: Point, p2: Point) -> i32 {
+        let d = p1.sub(p2);
+        self.gs.project(d.x, d.y)
+    }
+
+    #[inline]
+    fn dual_project(&self, p1: Point, p2: Point) -> i32 {
+        let d = p1.sub(p2);
+        self.gs.dual_project(d.x, d.y)
+    }
+
+    /// Measures an original distance between two points.
+    ///
+    /// Uses unscaled points outside of the twilight zone for better precision.
+    fn original_distance(&self, zp1: u8, p1: usize, zp2: u8, p2: usize) -> i32 {
+        if zp1 == 0 || zp2 == 0 {
+            self.dual_project(self.original(zp1, p1), self.original(zp2, p2))
+        } else {
+            let d = self.dual_project(self.zone(zp1).unscaled[p1], self.zone(zp2).unscaled[p2]);
+            math::mul_fix(d, self.unscaled_scale)
+        }
+    }
+
+    /// Moves a point along the freedom vector, so its projection changes by `distance`.
+    fn move_point(&mut self, zp: u8, point: usize, distance: i32) {
+        let fv = self.gs.freedom_vector;
+        let fdotp = self.gs.fdotp;
+        let zone = self.zone_mut(zp);
+        if point >= zone.len() {
+            return;
+        }
+
+        if fv.x != 0 {
+            let p = &mut zone.points[point];
+            p.x = p.x.wrapping_add(math::mul_div(distance, fv.x, fdotp));
+            zone.flags[point] |= TOUCHED_X;
+        }
+
+        if fv.y != 0 {
+            let p = &mut zone.points[point];
+            p.y = p.y.wrapping_add(math::mul_div(distance, fv.y, fdotp));
+            zone.flags[point] |= TOUCHED_Y;
+        }
+    }
+
+    /// Like `move_point`, but for original points.
+    fn move_original(&mut self, zp: u8, point: usize, distance: i32) {
+        let fv = self.gs.freedom_vector;
+        let fdotp = self.gs.fdotp;
+        if let Some(p) = self.zone_mut(zp).original.get_mut(point) {
+            if fv.x != 0 {
+                p.x = p.x.wrapping_add(math::mul_div(distance, fv.x, fdotp));
+            }
+
+            if fv.y != 0 {
+                p.y = p.y.wrapping_add(math::mul_div(distance, fv.y, fdotp));
+            }
+        }
+    }
+
+    /// Shifts a point in the `zp2` zone by a displacement.
+    fn shift_point(&mut self, point: usize, dx: i32, dy: i32, touch: bool) {
+        let fv = self.gs.freedom_vector;
+        let zone = self.zone_mut(self.gs.zp2);
+        if point >= zone.len() {
+            return;
+        }
+
+        if fv.x != 0 {
+            zone.points[point].x = zone.points[point].x.wrapping_add(dx);
+            if touch {
+                zone.flags[point] |= TOUCHED_X;
+            }
+        }
+
+        if fv.y != 0 {
+            zone.points[point].y = zone.points[point].y.wrapping_add(dy);
+            if touch {
+                zone.flags[point] |= TOUCHED_Y;
+            }
+        }
+    }
+
+    /// Returns a displacement of a reference point used by SHP, SHC and SHZ.
+    ///
+    /// Returns the displacement, the reference zone and the reference point.
+    fn point_displacement(&self, opcode: u8) -> Option<(i32, i32, u8, usize)> {
+        let (zp, point) = if opcode & 1 != 0 {
+            (self.gs.zp0, self.gs.rp1)
+        } else {
+            (self.gs.zp1, self.gs.rp2)
+        };
+
+        if !self.in_zone(zp, point) {
+            return None;
+        }
+
+        let d = self.project(self.current(zp, point), self.original(zp, point));
+        let fv = self.gs.freedom_vector;
+        let dx = math::mul_div(d, fv.x, self.gs.fdotp);
+        let dy = math::mul_div(d, fv.y, self.gs.fdotp);
+        Some((dx, dy, zp, point))
+    }
+
+    fn read_cvt(&self, index: usize) -> i32 {
+        self.cvt.get(index).copied().unwrap_or(0)
+    }
+
+    fn set_vector_from_line(
+        &mut self,
+        opcode: u8,
+        p1: usize,
+        p2: usize,
+        use_original: bool,
+    ) -> Option<Vector> {
+        let (zp1, zp2) = (self.gs.zp1, self.gs.zp2);
+        if !self.in_zone(zp2, p1) || !self.in_zone(zp1, p2) {
+            return None;
+        }
+
+        let (a, b) = if use_original {
+            (self.original(zp1, p2), self.original(zp2, p1))
+        } else {
+            (self.current(zp1, p2), self.current(zp2, p1))
+        };
+
+        let d = a.sub(b);
+        let (x, y) = if d.x == 0 && d.y == 0 {
+            (0x4000, 0)
+        } else if opcode & 1 != 0 {
+            // Rotate counter-clockwise.
+            (d.y.wrapping_neg(), d.x)
+        } else {
+            (d.x, d.y)
+        };
+
+        let (x, y) = math::normalize(x, y)?;
+        Some(Vector { x, y })
+    }
+
+    fn execute_instruction(&mut self, opcode: u8, data: &[u8]) -> Result<(), Error> {
+        match opcode {
+            // SVTCA, SPVTCA, SFVTCA
+            0x00..=0x05 => {
+                let axis = if opcode & 1 != 0 {
+                    Vector::X_AXIS
+                } else {
+                    Vector::Y_AXIS
+                };
+
+                // SVTCA sets both vectors.
+                let (set_proj, set_freedom) = match opcode >> 1 {
+                    0 => (true, true),
+                    1 => (true, false),
+                    _ => (false, true),
+                };
+
+                if set_proj {
+                    self.gs.proj_vector = axis;
+                    self.gs.dual_proj_vector = axis;
+                }
+
+                if set_freedom {
+                    self.gs.freedom_vector = axis;
+                }
+
+                self.gs.update_projection();
+            }
+            // SPVTL, SFVTL
+            0x06..=0x09 => {
+                let p1 = self.pop_index()?;
+                let p2 = self.pop_index()?;
+                if let Some(v) = self.set_vector_from_line(opcode, p1, p2, false) {
+                    if opcode < 0x08 {
+                        self.gs.proj_vector = v;
+                        self.gs.dual_proj_vector = v;
+                    } else {
+                        self.gs.freedom_vector = v;
+                    }
+
+                    self.gs.update_projection();
+                }
+            }
+            // SPVFS, SFVFS
+            0x0A | 0x0B => {
+                let y = i32::from(self.pop()? as i16);
+                let x = i32::from(self.pop()? as i16);
+                if let Some((x, y)) = math::normalize(x, y) {
+                    let v = Vector { x, y };
+                    if opcode == 0x0A {
+                        self.gs.proj_vector = v;
+                        self.gs.dual_proj_vector = v;
+                    } else {
+                        self.gs.freedom_vector = v;
+                    }
+
+                    self.gs.update_projection();
+                }
+            }
+            // GPV, GFV
+            0x0C | 0x0D => {
+                let v = if opcode == 0x0C {
+                    self.gs.proj_vector
+                } else {
+                    self.gs.freedom_vector
+                };
+
+                self.push(v.x)?;
+                self.push(v.y)?;
+            }
+            // SFVTPV
+            0x0E => {
+                self.gs.freedom_vector = self.gs.proj_vector;
+                self.gs.update_projection();
+            }
+            // ISECT
+            0x0F => self.op_isect()?,
+            // SRP0, SRP1, SRP2
+            0x10 => self.gs.rp0 = self.pop_index()?,
+            0x11 => self.gs.rp1 = self.pop_index()?,
+            0x12 => self.gs.rp2 = self.pop_index()?,
+            // SZP0, SZP1, SZP2, SZPS
+            0x13..=0x16 => {
+                let zone = self.pop()?;
+                if zone == 0 || zone == 1 {
+                    let zone = zone as u8;
+                    match opcode {
+                        0x13 => self.gs.zp0 = zone,
+                        0x14 => self.gs.zp1 = zone,
+                        0x15 => self.gs.zp2 = zone,
+                        _ => {
+                            self.gs.zp0 = zone;
+                            self.gs.zp1 = zone;
+                            self.gs.zp2 = zone;
+                        }
+                    }
+                }
+            }
+            // SLOOP
+            0x17 => {
+                let n = self.pop()?;
+                if n < 0 {
+                    return Err(Error::InvalidArgument);
+                }
+
+                self.gs.loop_counter = n.min(0xFFFF) as u32;
+            }
+            // RTG
+            0x18 => self.gs.round_state = RoundState::ToGrid,
+            // RTHG
+            0x19 => self.gs.round_state = RoundState::ToHalfGrid,
+            // SMD
+            0x1A => self.gs.min_distance = self.pop()?,
+            // SCVTCI
+            0x1D => self.gs.control_value_cutin = self.pop()?,
+            // SSWCI
+            0x1E => self.gs.single_width_cutin = self.pop()?,
+            // SSW
+            0x1F => self.gs.single_width_value = math::mul_fix(self.pop()?, self.scale),
+            // DUP
+            0x20 => {
+                let v = self.pop()?;
+                self.push(v)?;
+                self.push(v)?;
+            }
+            // POP
+            0x21 => {
+                self.pop()?;
+            }
+            // CLEAR
+            0x22 => self.stack.clear(),
+            // SWAP
+            0x23 => {
+                let a = self.pop()?;
+                let b = self.pop()?;
+                self.push(a)?;
+                self.push(b)?;
+            }
+            // DEPTH
+            0x24 => {
+                let depth = self.stack.len() as i32;
+                self.push(depth)?;
+            }
+            // CINDEX
+            0x25 => {
+                let k = self.pop()?;
+                let v = usize::try_from(k)
+                    .ok()
+                    .filter(|k| *k > 0 && *k <= self.stack.len())
+                    .map(|k| self.stack[self.stack.len() - k])
+                    .unwrap_or(0);
+                self.push(v)?;
+            }
+            // MINDEX
+            0x26 => {
+                let k = self.pop()?;
+                if let Some(k) = usize::try_from(k)
+                    .ok()
+                    .filter(|k| *k > 0 && *k <= self.stack.len())
+                {
+                    let v = self.stack.remove(self.stack.len() - k);
+                    self.push(v)?;
+                }
+            }
+            // ALIGNPTS
+            0x27 => {
+                let p2 = self.pop_index()?;
+                let p1 = self.pop_index()?;
+                let (zp0, zp1) = (self.gs.zp0, self.gs.zp1);
+                if self.in_zone(zp1, p1) && self.in_zone(zp0, p2) {
+                    let distance = self.project(self.current(zp0, p2), self.current(zp1, p1)) / 2;
+                    self.move_point(zp1, p1, distance);
+                    self.move_point(zp0, p2, distance.wrapping_neg());
+                }
+            }
+            // UTP
+            0x29 => {
+                let point = self.pop_index()?;
+                let fv = self.gs.freedom_vector;
+                let zp0 = self.gs.zp0;
+                if let Some(flags) = self.zone_mut(zp0).flags.get_mut(point) {
+                    if fv.x != 0 {
+                        *flags &= !TOUCHED_X;
+                    }
+
+                    if fv.y != 0 {
+                        *flags &= !TOUCHED_Y;
+                    }
+                }
+            }
+            // MDAP
+            0x2E | 0x2F => {
+                let point = self.pop_index()?;
+                let zp0 = self.gs.zp0;
+                if self.in_zone(zp0, point) {
+                    let distance = if opcode & 1 != 0 {
+                        let p = self.current(zp0, point);
+                        let d = self.gs.project(p.x, p.y);
+                        self.gs.round(d).wrapping_sub(d)
+                    } else {
+                        0
+                    };
+
+                    self.move_point(zp0, point, distance);
+                }
+
+                self.gs.rp0 = point;
+                self.gs.rp1 = point;
+            }
+            // IUP
+            0x30 | 0x31 => self.glyph.interpolate(opcode & 1 != 0),
+            // SHP
+            0x32 | 0x33 => {
+                let displacement = self.point_displacement(opcode);
+                for _ in 0..self.take_loop_counter() {
+                    let point = self.pop_index()?;
+                    if let Some((dx, dy, _, _)) = displacement {
+                        self.shift_point(point, dx, dy, true);
+                    }
+                }
+            }
+            // SHC
+            0x34 | 0x35 => {
+                let contour = self.pop_index()?;
+                if let Some((dx, dy, ref_zp, ref_point)) = self.point_displacement(opcode) {
+                    let zp2 = self.gs.zp2;
+                    // Contours are always taken from the glyph zone.
+                    let contours = &self.glyph.contours;
+                    let range = contours.get(contour).map(|end| {
+                        let start = match contour.checked_sub(1) {
+                            Some(prev) => contours[prev] + 1,
+                            None => 0,
+                        };
+
+                        // Use the number of points in the twilight zone.
+                        let limit = if zp2 == 0 {
+                            self.twilight.len()
+                        } else {
+                            end.saturating_add(1)
+                        };
+
+                        start..limit.min(self.zone(zp2).len())
+                    });
+
+                    if let Some(range) = range {
+                        for i in range {
+                            if ref_zp != zp2 || ref_point != i {
+                                self.shift_point(i, dx, dy, true);
+                            }
+                        }
+                    }
+                }
+            }
+            // SHZ
+            0x36 | 0x37 => {
+                let zone = self.pop()?;
+                if zone == 0 || zone == 1 {
+                    if let Some((dx, dy, ref_zp, ref_point)) = self.point_displacement(opcode) {
+                        let zp2 = self.gs.zp2;
+                        let z = self.zone(zp2);
+                        // Phantom points are not affected.
+                        let limit = if zp2 == 0 {
+                            z.len()
+                        } else {
+                            z.contours.last().map_or(0, |end| (end + 1).min(z.len()))
+                        };
+
+                        for i in 0..limit {
+                            if ref_zp != zp2 || ref_point != i {
+                                self.shift_point(i, dx, dy, false);
+                            }
+                        }
+                    }
+                }
+            }
+            // SHPIX
+            0x38 => {
+                let distance = self.pop()?;
+                let dx = math::mul14(distance, self.gs.freedom_vector.x);
+                let dy = math::mul14(distance, self.gs.freedom_vector.y);
+                for _ in 0..self.take_loop_counter() {
+                    let point = self.pop_index()?;
+                    self.shift_point(point, dx, dy, true);
+                }
+            }
+            // IP
+            0x39 => self.op_ip()?,
+            // MSIRP
+            0x3A | 0x3B => {
+                let distance = self.pop()?;
+                let point = self.pop_index()?;
+                let (zp0, zp1) = (self.gs.zp0, self.gs.zp1);
+                let rp0 = self.gs.rp0;
+                if self.in_zone(zp1, point) && self.in_zone(zp0, rp0) {
+                    if zp1 == 0 {
+                        let origin = self.original(zp0, rp0);
+                        self.twilight.original[point] = origin;
+                        self.move_original(zp1, point, distance);
+                        self.twilight.points[point] = self.twilight.original[point];
+                    }
+
+                    let d = self.project(self.current(zp1, point), self.current(zp0, rp0));
+                    self.move_point(zp1, point, distance.wrapping_sub(d));
+                    self.gs.rp1 = rp0;
+                    self.gs.rp2 = point;
+                    if opcode & 1 != 0 {
+                        self.gs.rp0 = point;
+                    }
+                }
+            }
+            // ALIGNRP
+            0x3C => {
+                let (zp0, zp1) = (self.gs.zp0, self.gs.zp1);
+                let rp0 = self.gs.rp0;
+                let valid = self.in_zone(zp0, rp0);
+                for _ in 0..self.take_loop_counter() {
+                    let point = self.pop_index()?;
+                    if valid && self.in_zone(zp1, point) {
+                        let d = self.project(self.current(zp1, point), self.current(zp0, rp0));
+                        self.move_point(zp1, point, d.wrapping_neg());
+                    }
+                }
+            }
+            // RTDG
+            0x3D => self.gs.round_state = RoundState::ToDoubleGrid,
+            // MIAP
+            0x3E | 0x3F => {
+                let cvt_index = self.pop_index()?;
+                let point = self.pop_index()?;
+                let zp0 = self.gs.zp0;
+                if self.in_zone(zp0, point) && cvt_index < self.cvt.len() {
+                    let mut distance = self.read_cvt(cvt_index);
+                    if zp0 == 0 {
+                        let fv = self.gs.freedom_vector;
+                        let p =
+                            Point::new(math::mul14(distance, fv.x), math::mul14(distance, fv.y));
+                        self.twilight.original[point] = p;
+                        self.twilight.points[point] = p;
+                    }
+
+                    let p = self.current(zp0, point);
+                    let original_distance = self.gs.project(p.x, p.y);
+                    if opcode & 1 != 0 {
+                        let delta = distance.wrapping_sub(original_distance).wrapping_abs();
+                        if delta > self.gs.control_value_cutin {
+                            distance = original_distance;
+                        }
+
+                        distance = self.gs.round(distance);
+                    }
+
+                    self.move_point(zp0, point, distance.wrapping_sub(original_distance));
+                }
+
+                self.gs.rp0 = point;
+                self.gs.rp1 = point;
+            }
+            // NPUSHB, PUSHB
+            0x40 | 0xB0..=0xB7 => {
+                let data = if opcode == 0x40 {
+                    data.get(1..).unwrap_or(&[])
+                } else {
+                    data
+                };
+
+                for b in data {
+                    self.push(i32::from(*b))?;
+                }
+            }
+            // NPUSHW, PUSHW
+            0x41 | 0xB8..=0xBF => {
+                let data = if opcode == 0x41 {
+                    data.get(1..).unwrap_or(&[])
+                } else {
+                    data
+                };
+
+                for w in data.chunks_exact(2) {
+                    self.push(i32::from(i16::from_be_bytes([w[0], w[1]])))?;
+                }
+            }
+            // WS
+            0x42 => {
+                let value = self.pop()?;
+                let index = self.pop_index()?;
+                if let Some(v) = self.storage.get_mut(index) {
+                    *v = value;
+                }
+            }
+            // RS
+            0x43 => {
+                let index = self.pop_index()?;
+                let value = self.storage.get(index).copied().unwrap_or(0);
+                self.push(value)?;
+            }
+            // WCVTP, WCVTF
+            0x44 | 0x70 => {
+                let mut value = self.pop()?;
+                let index = self.pop_index()?;
+                if opcode == 0x70 {
+                    value = math::mul_fix(value, self.scale);
+                }
+
+                if let Some(v) = self.cvt.get_mut(index) {
+                    *v = value;
+                }
+            }
+            // RCVT
+            0x45 => {
+                let index = self.pop_index()?;
+                let value = self.read_cvt(index);
+                self.push(value)?;
+            }
+            // GC
+            0x46 | 0x47 => {
+                let point = self.pop_index()?;
+                let zp2 = self.gs.zp2;
+                let value = if !self.in_zone(zp2, point) {
+                    0
+                } else if opcode & 1 != 0 {
+                    let p = self.original(zp2, point);
+                    self.gs.dual_project(p.x, p.y)
+                } else {
+                    let p = self.current(zp2, point);
+                    self.gs.project(p.x, p.y)
+                };
+
+                self.push(value)?;
+            }
+            // SCFS
+            0x48 => {
+                let value = self.pop()?;
+                let point = self.pop_index()?;
+                let zp2 = self.gs.zp2;
+                if self.in_zone(zp2, point) {
+                    let p = self.current(zp2, point);
+                    let d = self.gs.project(p.x, p.y);
+                    self.move_point(zp2, point, value.wrapping_sub(d));
+                    if zp2 == 0 {
+                        self.twilight.original[point] = self.twilight.points[point];
+                    }
+                }
+            }
+            // MD
+            0x49 | 0x4A => {
+                let k = self.pop_index()?;
+                let l = self.pop_index()?;
+                let (zp0, zp1) = (self.gs.zp0, self.gs.zp1);
+                let d = if !self.in_zone(zp0, l) || !self.in_zone(zp1, k) {
+                    0
+                } else if opcode & 1 != 0 {
+                    self.project(self.current(zp0, l), self.current(zp1, k))
+                } else {
+                    self.original_distance(zp0, l, zp1, k)
+                };
+
+                self.push(d)?;
+            }
+            // MPPEM
+            0x4B => self.push(i32::from(self.ppem))?,
+            // MPS
+            0x4C => self.push(i32::from(self.ppem) * math::ONE_PIXEL)?,
+            // FLIPON, FLIPOFF
+            0x4D => self.gs.auto_flip = true,
+            0x4E => self.gs.auto_flip = false,
+            // DEBUG
+            0x4F => return Err(Error::InvalidOpcode),
+            // LT, LTEQ, GT, GTEQ, EQ, NEQ
+            0x50..=0x55 => {
+                let b = self.pop()?;
+                let a = self.pop()?;
+                let v = match opcode {
+                    0x50 => a < b,
+                    0x51 => a <= b,
+                    0x52 => a > b,
+                    0x53 => a >= b,
+                    0x54 => a == b,
+                    _ => a != b,
+                };
+
+                self.push(v as i32)?;
+            }
+            // ODD, EVEN
+            0x56 | 0x57 => {
+                let v = self.pop()?;
+                let v = self.gs.round(v) & 127;
+                let v = if opcode == 0x56 { v == 64 } else { v == 0 };
+                self.push(v as i32)?;
+            }
+            // AND, OR
+            0x5A | 0x5B => {
+                let b = self.pop()? != 0;
+                let a = self.pop()? != 0;
+                let v = if opcode == 0x5A { a && b } else { a || b };
+                self.push(v as i32)?;
+            }
+            // NOT
+            0x5C => {
+                let v = self.pop()? == 0;
+                self.push(v as i32)?;
+            }
+            // DELTAP1, DELTAP2, DELTAP3, DELTAC1, DELTAC2, DELTAC3
+            0x5D | 0x71..=0x75 => self.op_delta(opcode)?,
+            // SDB
+            0x5E => self.gs.delta_base = self.pop()? as u16,
+            // SDS
+            0x5F => {
+                let n = self.pop()?;
+                if !(0..=6).contains(&n) {
+                    return Err(Error::InvalidArgument);
+                }
+
+                self.gs.delta_shift = n as u16;
+            }
+            // ADD, SUB, DIV, MUL, MAX, MIN
+            0x60..=0x63 | 0x8B | 0x8C => {
+                let b = self.pop()?;
+                let a = self.pop()?;
+                let v = match opcode {
+                    0x60 => a.wrapping_add(b),
+                    0x61 => a.wrapping_sub(b),
+                    0x62 => {
+                        if b == 0 {
+                            return Err(Error::DivisionByZero);
+                        }
+
+                        math::mul_div_no_round(a, 64, b)
+                    }
+                    0x63 => math::mul_div(a, b, 64),
+                    0x8B => a.max(b),
+                    _ => a.min(b),
+                };
+
+                self.push(v)?;
+            }
+            // ABS, NEG, FLOOR, CEILING
+            0x64..=0x67 => {
+                let a = self.pop()?;
+                let v = match opcode {
+                    0x64 => a.wrapping_abs(),
+                    0x65 => a.wrapping_neg(),
+                    0x66 => math::floor(a),
+                    _ => math::ceil(a),
+                };
+
+                self.push(v)?;
+            }
+            // ROUND
+            0x68..=0x6B => {
+                let v = self.pop()?;
+                let v = self.gs.round(v);
+                self.push(v)?;
+            }
+            // NROUND
+            0x6C..=0x6F => {}
+            // JROT, JROF are handled by the caller.
+            // ROFF
+            0x7A => self.gs.round_state = RoundState::Off,
+            // RUTG
+            0x7C => self.gs.round_state = RoundState::UpToGrid,
+            // RDTG
+            0x7D => self.gs.round_state = RoundState::DownToGrid,
+            // SROUND, S45ROUND
+            0x76 | 0x77 => {
+                let selector = self.pop()?;
+                let (period, state) = if opcode == 0x76 {
+                    (GRID_PERIOD, RoundState::Super)
+                } else {
+                    (GRID_PERIOD_45, RoundState::Super45)
+                };
+
+                self.gs.set_super_round(period, selector);
+                self.gs.round_state = state;
+            }
+            // SANGW, AA
+            0x7E | 0x7F => {
+                self.pop()?;
+            }
+            // FLIPPT
+            0x80 => {
+                for _ in 0..self.take_loop_counter() {
+                    let point = self.pop_index()?;
+                    if let Some(flags) = self.glyph.flags.get_mut(point) {
+                        *flags ^= ON_CURVE;
+                    }
+                }
+            }
+            // FLIPRGON, FLIPRGOFF
+            0x81 | 0x82 => {
+                let high = self.pop_index()?;
+                let low = self.pop_index()?;
+                let len = self.glyph.len();
+                if high < len && low < len {
+                    for flags in self.glyph.flags.iter_mut().take(high + 1).skip(low) {
+                        if opcode == 0x81 {
+                            *flags |= ON_CURVE;
+                        } else {
+                            *flags &= !ON_CURVE;
+                        }
+                    }
+                }
+            }
+            // SCANCTRL
+            0x85 => self.gs.scan_control = self.pop()?,
+            // SDPVTL
+            0x86 | 0x87 => {
+                let p1 = self.pop_index()?;
+                let p2 = self.pop_index()?;
+                let (zp1, zp2) = (self.gs.zp1, self.gs.zp2);
+                if self.in_zone(zp1, p2) && self.in_zone(zp2, p1) {
+                    // A zero original vector disables rotation for both vectors.
+                    let zero = self.original(zp1, p2) == self.original(zp2, p1);
+                    let opcode = if zero { 0 } else { opcode };
+                    if let Some(v) = self.set_vector_from_line(opcode, p1, p2, true) {
+                        self.gs.dual_proj_vector = v;
+                    }
+
+                    if let Some(v) = self.set_vector_from_line(opcode, p1, p2, false) {
+                        self.gs.proj_vector = v;
+                    }
+
+                    self.gs.update_projection();
+                }
+            }
+            // GETINFO
+            0x88 => {
+                let selector = self.pop()?;
+                let mut v = 0;
+                if selector & 1 != 0 {
+                    // Version 35 rasterizer.
+                    v = 35;
+                }
+
+                if selector & 8 != 0 && self.is_variable {
+                    v |= 1 << 10;
+                }
+
+                self.push(v)?;
+            }
+            // ROLL
+            0x8A => {
+                let a = self.pop()?;
+                let b = self.pop()?;
+                let c = self.pop()?;
+                self.push(b)?;
+                self.push(a)?;
+                self.push(c)?;
+            }
+            // SCANTYPE
+            0x8D => {
+                let n = self.pop()?;
+                if n >= 0 {
+                    self.gs.scan_type = n & 0xFFFF;
+                }
+            }
+            // INSTCTRL
+            0x8E => {
+                let selector = self.pop()?;
+                let value = self.pop()?;
+                if !(1..=3).contains(&selector) {
+                    return Err(Error::InvalidArgument);
+                }
+
+                // Can be set only by the control value program.
+                if self.program == Program::ControlValue {
+                    let mask = 1 << (selector - 1);
+                    self.gs.instruct_control &= !mask;
+                    if value != 0 {
+                        self.gs.instruct_control |= mask;
+                    }
+                }
+            }
+            // GETVARIATION
+            0x91 => {
+                for i in 0..self.coords.len() {
+                    self.push(self.coords[i])?;
+                }
+            }
+            // GETDATA
+            0x92 => self.push(17)?,
+            // MDRP
+            0xC0..=0xDF => self.op_mdrp(opcode)?,
+            // MIRP
+            0xE0..=0xFF => self.op_mirp(opcode)?,
+            _ => return Err(Error::InvalidOpcode),
+        }
+
+        Ok(())
+    }
+
+    /// Returns the loop counter and resets it.
+    fn take_loop_counter(&mut self) -> u32 {
+        let n = self.gs.loop_counter;
+        self.gs.loop_counter = 1;
+        n
+    }
+
+    fn op_isect(&mut self) -> Result<(), Error> {
+        let b1 = self.pop_index()?;
+        let b0 = self.pop_index()?;
+        let a1 = self.pop_index()?;
+        let a0 = self.pop_index()?;
+        let point = self.pop_index()?;
+        let (zp0, zp1, zp2) = (self.gs.zp0, self.gs.zp1, self.gs.zp2);
+        if !self.in_zone(zp2, point)
+            || !self.in_zone(zp1, a0)
+            || !self.in_zone(zp1, a1)
+            || !self.in_zone(zp0, b0)
+            || !self.in_zone(zp0, b1)
+        {
+            return Ok(());
+        }
+
+        let pa0 = self.current(zp1, a0);
+        let pa1 = self.current(zp1, a1);
+        let pb0 = self.current(zp0, b0);
+        let pb1 = self.current(zp0, b1);
+
+        let db = pb1.sub(pb0);
+        let da = pa1.sub(pa0);
+        let d = pb0.sub(pa0);
+
+        let discriminant = math::mul_div(da.x, db.y.wrapping_neg(), 0x40)
+            .wrapping_add(math::mul_div(da.y, db.x, 0x40));
+        let dot_product =
+            math::mul_div(da.x, db.x, 0x40).wrapping_add(math::mul_div(da.y, db.y, 0x40));
+
+        // Reject nearly parallel lines, i.e. an angle below 3 degrees.
+        let p = if i64::from(discriminant).abs() * 19 > i64::from(dot_product).abs() {
+            let v = math::mul_div(d.x, db.y.wrapping_neg(), 0x40)
+                .wrapping_add(math::mul_div(d.y, db.x, 0x40));
+            Point::new(
+                pa0.x.wrapping_add(math::mul_div(v, da.x, discriminant)),
+                pa0.y.wrapping_add(math::mul_div(v, da.y, discriminant)),
+            )
+        } else {
+            // Use the middle of the middles.
+            let mid = |a: i32, b: i32, c: i32, d: i32| {
+                ((i64::from(a) + i64::from(b) + i64::from(c) + i64::from(d)) / 4) as i32
+            };
+            Point::new(
+                mid(pa0.x, pa1.x, pb0.x, pb1.x),
+                mid(pa0.y, pa1.y, pb0.y, pb1.y),
+            )
+        };
+
+        let zone = self.zone_mut(zp2);
+        zone.points[point] = p;
+        zone.flags[point] |= TOUCHED;
+        Ok(())
+    }
+
+    fn op_ip(&mut self) -> Result<(), Error> {
+        let (zp0, zp1, zp2) = (self.gs.zp0, self.gs.zp1, self.gs.zp2);
+        let (rp1, rp2) = (self.gs.rp1, self.gs.rp2);
+        let count = self.take_loop_counter();
+        if !self.in_zone(zp0, rp1) {
+            for _ in 0..count {
+                self.pop()?;
+            }
+
+            return Ok(());
+        }
+
+        let twilight = zp0 == 0 || zp1 == 0 || zp2 == 0;
+        let base_point = |engine: &Self, zp: u8, point: usize| {
+            if twilight {
+                engine.original(zp, point)
+            } else {
+                engine.zone(zp).unscaled[point]
+            }
+        };
+
+        let original_base = base_point(self, zp0, rp1);
+        let current_base = self.current(zp0, rp1);
+        let (old_range, current_range) = if self.in_zone(zp1, rp2) {
+            (
+                self.dual_project(base_point(self, zp1, rp2), original_base),
+                self.project(self.current(zp1, rp2), current_base),
+            )
+        } else {
+            (0, 0)
+        };
+
+        for _ in 0..count {
+            let point = self.pop_index()?;
+            if !self.in_zone(zp2, point) {
+                continue;
+            }
+
+            let original_distance = self.dual_project(base_point(self, zp2, point), original_base);
+            let current_distance = self.project(self.current(zp2, point), current_base);
+            let new_distance = if original_distance == 0 {
+                0
+            } else if old_range != 0 {
+                math::mul_div(original_distance, current_range, old_range)
+            } else {
+                original_distance
+            };
+
+            self.move_point(zp2, point, new_distance.wrapping_sub(current_distance));
+        }
+
+        Ok(())
+    }
+
+    fn op_delta(&mut self, opcode: u8) -> Result<(), Error> {
+        let count = self.pop()? as u32;
+        let range = match opcode {
+            0x5D | 0x73 => 0,
+            0x71 | 0x74 => 16,
+            _ => 32,
+        };
+
+        let zp0 = self.gs.zp0;
+        for _ in 0..count {
+            if self.stack.len() < 2 {
+                self.stack.clear();
+                break;
+            }
+
+            let index = self.pop_index()?;
+            let arg = self.pop()?;
+
+            let ppem = ((arg & 0xF0) >> 4) as u32 + range + u32::from(self.gs.delta_base);
+            if ppem != u32::from(self.ppem) {
+                continue;
+            }
+
+            let mut steps = (arg & 0xF) - 8;
+            if steps >= 0 {
+                steps += 1;
+            }
+
+            let delta = steps * (1 << (6 - self.gs.delta_shift));
+            if opcode == 0x5D || opcode == 0x71 || opcode == 0x72 {
+                if self.in_zone(zp0, index) {
+                    self.move_point(zp0, index, delta);
+                }
+            } else if let Some(v) = self.cvt.get_mut(index) {
+                *v = v.wrapping_add(delta);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn op_mdrp(&mut self, opcode: u8) -> Result<(), Error> {
+        let point = self.pop_index()?;
+        let (zp0, zp1) = (self.gs.zp0, self.gs.zp1);
+        let rp0 = self.gs.rp0;
+        if self.in_zone(zp1, point) && self.in_zone(zp0, rp0) {
+            let mut original_distance = self.original_distance(zp1, point, zp0, rp0);
+
+            // Single width cut-in test.
+            let sw = self.gs.single_width_value;
+            let cutin = self.gs.single_width_cutin;
+            if cutin > 0
+                && original_distance < sw.wrapping_add(cutin)
+                && original_distance > sw.wrapping_sub(cutin)
+            {
+                original_distance = if original_distance >= 0 {
+                    sw
+                } else {
+                    sw.wrapping_neg()
+                };
+            }
+
+            let mut distance = if opcode & 4 != 0 {
+                self.gs.round(original_distance)
+            } else {
+                original_distance
+            };
+
+            if opcode & 8 != 0 {
+                distance = self.apply_min_distance(original_distance, distance);
+            }
+
+            let current_distance = self.project(self.current(zp1, point), self.current(zp0, rp0));
+            self.move_point(zp1, point, distance.wrapping_sub(current_distance));
+        }
+
+        self.gs.rp1 = rp0;
+        self.gs.rp2 = point;
+        if opcode & 16 != 0 {
+            self.gs.rp0 = point;
+        }
+
+        Ok(())
+    }
+
+    fn op_mirp(&mut self, opcode: u8) -> Result<(), Error> {
+        let cvt_index = self.pop()?;
+        let point = self.pop_index()?;
+        let (zp0, zp1) = (self.gs.zp0, self.gs.zp1);
+        let rp0 = self.gs.rp0;
+
+        // CVT index -1 is allowed and means a zero distance.
+        let cvt_index = i64::from(cvt_index) + 1;
+        let cvt_valid = cvt_index >= 0 && cvt_index <= self.cvt.len() as i64;
+        if self.in_zone(zp1, point) && self.in_zone(zp0, rp0) && cvt_valid {
+            let mut cvt_distance = if cvt_index == 0 {
+                0
+            } else {
+                self.read_cvt((cvt_index - 1) as usize)
+            };
+
+            // Single width cut-in test.
+            let sw = self.gs.single_width_value;
+            if cvt_distance.wrapping_sub(sw).wrapping_abs() < self.gs.single_width_cutin {
+                cvt_distance = if cvt_distance >= 0 {
+                    sw
+                } else {
+                    sw.wrapping_neg()
+                };
+            }
+
+            if zp1 == 0 {
+                let fv = self.gs.freedom_vector;
+                let origin = self.original(zp0, rp0);
+                let p = Point::new(
+                    origin.x.wrapping_add(math::mul14(cvt_distance, fv.x)),
+                    origin.y.wrapping_add(math::mul14(cvt_distance, fv.y)),
+                );
+                self.twilight.original[point] = p;
+                self.twilight.points[point] = p;
+            }
+
+            let original_distance =
+                self.dual_project(self.original(zp1, point), self.original(zp0, rp0));
+            let current_distance = self.project(self.current(zp1, point), self.current(zp0, rp0));
+
+            if self.gs.auto_flip && (original_distance ^ cvt_distance) < 0 {
+                cvt_distance = cvt_distance.wrapping_neg();
+            }
+
+            let mut distance = if opcode & 4 != 0 {
+                // Cut-in test is performed only when both points are in the same zone.
+                if zp0 == zp1 {
+                    let delta = cvt_distance.wrapping_sub(original_distance).wrapping_abs();
+                    if delta > self.gs.control_value_cutin {
+                        cvt_distance = original_distance;
+                    }
+                }
+
+                self.gs.round(cvt_distance)
+            } else {
+                cvt_distance
+            };
+
+            if opcode & 8 != 0 {
+                distance = self.apply_min_distance(original_distance, distance);
+            }
+
+            self.move_point(zp1, point, distance.wrapping_sub(current_distance));
+        }
+
+        self.gs.rp1 = rp0;
+        if opcode & 16 != 0 {
+            self.gs.rp0 = point;
+        }
+
+        self.gs.rp2 = point;
+        Ok(())
+    }
+
+    fn apply_min_distance(&self, original_distance: i32, distance: i32) -> i32 {
+        let min_distance = self.gs.min_distance;
+        if original_distance >= 0 {
+            distance.max(min_distance)
+        } else {
+            distance.min(min_distance.wrapping_neg())
+        }
+    }
+}
+
+/// Checks that an opcode is defined by the instruction set.
+fn is_defined(opcode: u8, is_variable: bool) -> bool {
+    match opcode {
+        0x28 | 0x7B | 0x83 | 0x84 | 0x8F | 0x90 | 0x93..=0xAF => false,
+        0x91 | 0x92 => is_variable,
+        _ => true,
+    }
+}
+
+/// Skips to the matching ELSE or EIF.
+///
+/// Returns the offset after the found instruction.
+fn skip_branch(code: &[u8], mut pc: usize, stop_at_else: bool) -> Result<usize, Error> {
+    let mut nesting = 1;
+    loop {
+        let opcode = *code.get(pc).ok_or(Error::UnexpectedEnd)?;
+        let next_pc = next_instruction(code, pc).ok_or(Error::UnexpectedEnd)?;
+        match opcode {
+            // IF
+            0x58 => nesting += 1,
+            // ELSE
+            0x1B if nesting == 1 && stop_at_else => return Ok(next_pc),
+            // EIF
+            0x59 => {
+                nesting -= 1;
+                if nesting == 0 {
+                    return Ok(next_pc);
+                }
+            }
+            _ => {}
+        }
+
+        pc = next_pc;
+    }
+}
+
+/// Finds the ENDF instruction of a definition.
+fn find_definition_end(code: &[u8], mut pc: usize) -> Result<usize, Error> {
+    loop {
+        let opcode = *code.get(pc).ok_or(Error::UnexpectedEnd)?;
+        match opcode {
+            // FDEF, IDEF
+            0x2C | 0x89 => return Err(Error::InvalidDefinition),
+            // ENDF
+            0x2D => return Ok(pc),
+            _ => pc = next_instruction(code, pc).ok_or(Error::UnexpectedEnd)?,
+        }
+    }
+}
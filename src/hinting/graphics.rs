@@ -0,0 +1,251 @@
+//! Graphics state.
+
+use super::math;
+
+/// A rounding mode.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum RoundState {
+    ToHalfGrid,
+    ToGrid,
+    ToDoubleGrid,
+    DownToGrid,
+    UpToGrid,
+    Off,
+    Super,
+    Super45,
+}
+
+/// A 2.14 unit vector.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Vector {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl Vector {
+    pub const X_AXIS: Vector = Vector { x: 0x4000, y: 0 };
+    pub const Y_AXIS: Vector = Vector { x: 0, y: 0x4000 };
+}
+
+/// A [graphics state](
+/// https://developer.apple.com/fonts/TrueType-Reference-Manual/RM04/Chap4.html).
+#[derive(Clone, Copy, Debug)]
+pub struct GraphicsState {
+    pub proj_vector: Vector,
+    pub dual_proj_vector: Vector,
+    pub freedom_vector: Vector,
+    /// A dot product of the freedom and projection vectors.
+    pub fdotp: i32,
+    pub rp0: usize,
+    pub rp1: usize,
+    pub rp2: usize,
+    pub zp0: u8,
+    pub zp1: u8,
+    pub zp2: u8,
+    pub loop_counter: u32,
+    pub min_distance: i32,
+    pub control_value_cutin: i32,
+    pub single_width_cutin: i32,
+    pub single_width_value: i32,
+    pub round_state: RoundState,
+    pub period: i32,
+    pub phase: i32,
+    pub threshold: i32,
+    pub auto_flip: bool,
+    pub delta_base: u16,
+    pub delta_shift: u16,
+    pub instruct_control: u8,
+    pub scan_control: i32,
+    pub scan_type: i32,
+}
+
+impl Default for GraphicsState {
+    fn default() -> Self {
+        GraphicsState {
+            proj_vector: Vector::X_AXIS,
+            dual_proj_vector: Vector::X_AXIS,
+            freedom_vector: Vector::X_AXIS,
+            fdotp: 0x4000,
+            rp0: 0,
+            rp1: 0,
+            rp2: 0,
+            zp0: 1,
+            zp1: 1,
+            zp2: 1,
+            loop_counter: 1,
+            min_distance: math::ONE_PIXEL,
+            control_value_cutin: 68,
+            single_width_cutin: 0,
+            single_width_value: 0,
+            round_state: RoundState::ToGrid,
+            period: math::ONE_PIXEL,
+            phase: 0,
+            threshold: 0,
+            auto_flip: true,
+            delta_base: 9,
+            delta_shift: 3,
+            instruct_control: 0,
+            scan_control: 0,
+            scan_type: 0,
+        }
+    }
+}
+
+impl GraphicsState {
+    /// Resets the state that doesn't persist between programs.
+    pub fn reset_program_state(&mut self) {
+        self.proj_vector = Vector::X_AXIS;
+        self.dual_proj_vector = Vector::X_AXIS;
+        self.freedom_vector = Vector::X_AXIS;
+        self.fdotp = 0x4000;
+        self.round_state = RoundState::ToGrid;
+        self.loop_counter = 1;
+        self.zp0 = 1;
+        self.zp1 = 1;
+        self.zp2 = 1;
+    }
+
+    /// Updates the cached dot product after the vectors change.
+    pub fn update_projection(&mut self) {
+        let fv = self.freedom_vector;
+        let pv = self.proj_vector;
+        self.fdotp = if fv.x == 0x4000 {
+            pv.x
+        } else if fv.y == 0x4000 {
+            pv.y
+        } else {
+            (pv.x * fv.x + pv.y * fv.y) >> 14
+        };
+
+        // Prevent division by a too small value.
+        if self.fdotp.abs() < 0x400 {
+            self.fdotp = 0x4000;
+        }
+    }
+
+    #[inline]
+    pub fn project(&self, dx: i32, dy: i32) -> i32 {
+        math::dot14(dx, dy, self.proj_vector.x, self.proj_vector.y)
+    }
+
+    #[inline]
+    pub fn dual_project(&self, dx: i32, dy: i32) -> i32 {
+        math::dot14(dx, dy, self.dual_proj_vector.x, self.dual_proj_vector.y)
+    }
+
+    /// Sets `SROUND` and `S45ROUND` parameters.
+    pub fn set_super_round(&mut self, grid_period: i32, selector: i32) {
+        self.period = match selector & 0xC0 {
+            0x00 => grid_period / 2,
+            0x80 => grid_period * 2,
+            _ => grid_period,
+        };
+
+        self.phase = match selector & 0x30 {
+            0x00 => 0,
+            0x10 => self.period / 4,
+            0x20 => self.period / 2,
+            _ => self.period * 3 / 4,
+        };
+
+        self.threshold = if selector & 0x0F == 0 {
+            self.period - 1
+        } else {
+            ((selector & 0x0F) - 4) * self.period / 8
+        };
+
+        // Convert to 26.6.
+        self.period >>= 8;
+        self.phase >>= 8;
+        self.threshold >>= 8;
+    }
+
+    /// Rounds a distance according to the current round state.
+    ///
+    /// Preserves the distance sign.
+    pub fn round(&self, distance: i32) -> i32 {
+        match self.round_state {
+            RoundState::ToHalfGrid => {
+                if distance >= 0 {
+                    math::floor(distance).wrapping_add(32).max(0)
+                } else {
+                    math::floor(distance.wrapping_neg())
+                        .wrapping_add(32)
+                        .wrapping_neg()
+                        .min(0)
+                }
+            }
+            RoundState::ToGrid => {
+                if distance >= 0 {
+                    math::round(distance).max(0)
+                } else {
+                    math::round(distance.wrapping_neg()).wrapping_neg().min(0)
+                }
+            }
+            RoundState::ToDoubleGrid => {
+                if distance >= 0 {
+                    (distance.wrapping_add(16) & -32).max(0)
+                } else {
+                    (distance.wrapping_neg().wrapping_add(16) & -32)
+                        .wrapping_neg()
+                        .min(0)
+                }
+            }
+            RoundState::DownToGrid => {
+                if distance >= 0 {
+                    math::floor(distance)
+                } else {
+                    math::floor(distance.wrapping_neg()).wrapping_neg()
+                }
+            }
+            RoundState::UpToGrid => {
+                if distance >= 0 {
+                    math::ceil(distance).max(0)
+                } else {
+                    math::ceil(distance.wrapping_neg()).wrapping_neg().min(0)
+                }
+            }
+            RoundState::Off => distance,
+            RoundState::Super => {
+                let bias = self.threshold.wrapping_sub(self.phase);
+                if distance >= 0 {
+                    let v = (distance.wrapping_add(bias) & -self.period).wrapping_add(self.phase);
+                    if v < 0 {
+                        self.phase
+                    } else {
+                        v
+                    }
+                } else {
+                    let v = (bias.wrapping_sub(distance) & -self.period).wrapping_neg();
+                    let v = v.wrapping_sub(self.phase);
+                    if v > 0 {
+                        -self.phase
+                    } else {
+                        v
+                    }
+                }
+            }
+            RoundState::Super45 => {
+                let period = self.period.max(1);
+                let bias = self.threshold.wrapping_sub(self.phase);
+                if distance >= 0 {
+                    let v = distance.wrapping_add(bias) / period * period;
+                    let v = v.wrapping_add(self.phase);
+                    if v < 0 {
+                        self.phase
+                    } else {
+                        v
+                    }
+                } else {
+                    let v = (bias.wrapping_sub(distance) / period * period).wrapping_neg();
+                    let v = v.wrapping_sub(self.phase);
+                    if v > 0 {
+                        -self.phase
+                    } else {
+                        v
+                    }
+                }
+            }
+        }
+    }
+}
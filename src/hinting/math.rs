@@ -0,0 +1,133 @@
+//! Fixed-point arithmetic.
+//!
+//! Mirrors the rounding behavior of the reference rasterizers,
+//! so results are bit-exact. All functions are wrapping and never panic.
+
+/// A 26.6 pixel.
+pub const ONE_PIXEL: i32 = 64;
+
+/// Computes `a * b / c` rounded to the nearest integer.
+///
+/// Returns the largest magnitude value with the sign of `a * b` when `c` is zero.
+pub fn mul_div(a: i32, b: i32, c: i32) -> i32 {
+    let negative = (a < 0) ^ (b < 0) ^ (c < 0);
+    let a = i64::from(a).unsigned_abs();
+    let b = i64::from(b).unsigned_abs();
+    let c = i64::from(c).unsigned_abs();
+    let d = (a * b + (c >> 1)).checked_div(c).unwrap_or(0x7FFF_FFFF);
+
+    let d = d.min(0x7FFF_FFFF) as i32;
+    if negative {
+        -d
+    } else {
+        d
+    }
+}
+
+/// Computes `a * b / c` truncated toward zero.
+pub fn mul_div_no_round(a: i32, b: i32, c: i32) -> i32 {
+    let negative = (a < 0) ^ (b < 0) ^ (c < 0);
+    let a = i64::from(a).unsigned_abs();
+    let b = i64::from(b).unsigned_abs();
+    let c = i64::from(c).unsigned_abs();
+    let d = (a * b).checked_div(c).unwrap_or(0x7FFF_FFFF);
+
+    let d = d.min(0x7FFF_FFFF) as i32;
+    if negative {
+        -d
+    } else {
+        d
+    }
+}
+
+/// Multiplies a value by a 16.16 number.
+pub fn mul_fix(a: i32, b: i32) -> i32 {
+    let ab = i64::from(a) * i64::from(b);
+    ((ab + 0x8000 + (ab >> 63)) >> 16) as i32
+}
+
+/// Divides a value by another one, producing a 16.16 number.
+pub fn div_fix(a: i32, b: i32) -> i32 {
+    mul_div(a, 0x10000, b)
+}
+
+/// Multiplies a value by a 2.14 number.
+pub fn mul14(a: i32, b: i32) -> i32 {
+    let ab = i64::from(a) * i64::from(b);
+    ((ab + 0x2000 + (ab >> 63)) >> 14) as i32
+}
+
+/// Computes a dot product of a vector and a 2.14 unit vector.
+pub fn dot14(ax: i32, ay: i32, bx: i32, by: i32) -> i32 {
+    let v = i64::from(ax) * i64::from(bx) + i64::from(ay) * i64::from(by);
+    ((v + 0x2000 + (v >> 63)) >> 14) as i32
+}
+
+/// Rounds a 26.6 value to the nearest pixel.
+pub fn round(x: i32) -> i32 {
+    x.wrapping_add(32) & -ONE_PIXEL
+}
+
+/// Rounds a 26.6 value down to the pixel.
+pub fn floor(x: i32) -> i32 {
+    x & -ONE_PIXEL
+}
+
+/// Rounds a 26.6 value up to the pixel.
+pub fn ceil(x: i32) -> i32 {
+    x.wrapping_add(63) & -ONE_PIXEL
+}
+
+/// Computes `sqrt(x * x + y * y)` of 16.16 values.
+pub fn hypot(x: i32, y: i32) -> i32 {
+    let x = i64::from(x);
+    let y = i64::from(y);
+    sqrt((x * x + y * y) as u64).min(0x7FFF_FFFF) as i32
+}
+
+/// Scales a vector to a 2.14 unit vector.
+///
+/// Returns `None` for a zero vector.
+pub fn normalize(x: i32, y: i32) -> Option<(i32, i32)> {
+    if x == 0 && y == 0 {
+        return None;
+    }
+
+    let mut x = i64::from(x);
+    let mut y = i64::from(y);
+
+    // Maximize the precision by scaling components up to 30 bits.
+    let max = x.unsigned_abs().max(y.unsigned_abs());
+    let shift = max.leading_zeros().saturating_sub(34);
+    x <<= shift;
+    y <<= shift;
+
+    let len = sqrt((x * x + y * y) as u64) as i64;
+    if len == 0 {
+        return None;
+    }
+
+    let scale = |v: i64| -> i32 {
+        let n = (v.abs() * 0x4000 + len / 2) / len;
+        (if v < 0 { -n } else { n }) as i32
+    };
+
+    Some((scale(x), scale(y)))
+}
+
+fn sqrt(n: u64) -> u64 {
+    if n < 2 {
+        return n;
+    }
+
+    // Newton's method, starting from a value that is never below the root.
+    let mut x = 1u64 << ((64 - n.leading_zeros()) / 2 + 1);
+    loop {
+        let y = (x + n / x) / 2;
+        if y >= x {
+            return x;
+        }
+
+        x = y;
+    }
+}
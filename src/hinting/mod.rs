@@ -0,0 +1,539 @@
+//! A [TrueType hinting](
+//! https://docs.microsoft.com/en-us/typography/opentype/spec/tt_instructions) implementation.
+//!
+//! Executes the `fpgm`, `prep` and glyph instructions to grid-fit TrueType outlines
+//! for a specific pixels per em size.
+//! The behavior matches the FreeType's "v35" interpreter,
+//! i.e. instructions are applied in both directions.
+//!
+//! Unlike the rest of the library, hinting requires heap allocations,
+//! since the interpreter state size is defined by the font.
+//! The interpreter is still guaranteed not to panic and uses a bounded call stack,
+//! a bounded instructions budget and a bounded composite glyphs recursion.
+//!
+//! # Example
+//!
+//! ```no_run
+//! # struct Builder;
+//! # impl ttf_parser::OutlineBuilder for Builder {
+//! #     fn move_to(&mut self, x: f32, y: f32) {}
+//! #     fn line_to(&mut self, x: f32, y: f32) {}
+//! #     fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {}
+//! #     fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {}
+//! #     fn close(&mut self) {}
+//! # }
+//! let data = std::fs::read("font.ttf").unwrap();
+//! let face = ttf_parser::Face::parse(&data, 0).unwrap();
+//! let mut hinter = ttf_parser::hinting::Hinter::new(&face, 16, &[]).unwrap();
+//! let mut builder = Builder;
+//! let bbox = hinter.outline_glyph(ttf_parser::GlyphId(1), &mut builder);
+//! ```
+
+use std::vec::Vec;
+
+use crate::parser::Stream;
+use crate::{glyf, Face, GlyphId, NormalizedCoordinate, OutlineBuilder, PointF, RectF, Transform};
+
+mod engine;
+mod graphics;
+mod math;
+mod zone;
+
+use engine::{Engine, Limits, Program};
+use graphics::GraphicsState;
+use zone::{Point, Zone, ON_CURVE};
+
+const PHANTOM_POINTS_LEN: usize = 4;
+
+/// A TrueType glyph hinter.
+///
+/// Holds the interpreter state produced by the `fpgm` and `prep` tables
+/// for a specific size and variation coordinates.
+/// Creating a hinter is relatively expensive, so it should be reused for all glyphs
+/// of the same size.
+pub struct Hinter<'a> {
+    face: &'a Face<'a>,
+    glyf: glyf::Table<'a>,
+    engine: Engine<'a>,
+    #[cfg(feature = "variable-fonts")]
+    coords: Vec<NormalizedCoordinate>,
+    // The state after the `prep` execution. Restored before each glyph.
+    cvt: Vec<i32>,
+    storage: Vec<i32>,
+    gs: GraphicsState,
+    twilight: Zone,
+    // Accumulated glyph points in 26.6.
+    outline: Zone,
+}
+
+impl core::fmt::Debug for Hinter<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "Hinter {{ ... }}")
+    }
+}
+
+impl<'a> Hinter<'a> {
+    /// Creates a new hinter for the specified pixels per em size.
+    ///
+    /// `coords` are normalized variation coordinates and must be empty
+    /// for non-variable fonts. Use `Face::variation_coordinates` to get the current ones.
+    ///
+    /// Returns `None` when the face has no `glyf` table, `ppem` is zero
+    /// or when the `fpgm` or `prep` program execution fails.
+    pub fn new(face: &'a Face<'a>, ppem: u16, coords: &[NormalizedCoordinate]) -> Option<Self> {
+        let tables = face.tables();
+        let glyf = tables.glyf?;
+        if ppem == 0 {
+            return None;
+        }
+
+        let maxp = tables.maxp;
+        let limits = Limits {
+            stack_elements: maxp.max_stack_elements,
+            storage: maxp.max_storage,
+            function_defs: maxp.max_function_defs,
+            instruction_defs: maxp.max_instruction_defs,
+            twilight_points: maxp.max_twilight_points,
+        };
+
+        let mut engine = Engine::new(
+            tables.fpgm.map(|t| t.instructions).unwrap_or(&[]),
+            tables.prep.map(|t| t.instructions).unwrap_or(&[]),
+            limits,
+        );
+
+        let scale = math::div_fix(
+            i32::from(ppem) * math::ONE_PIXEL,
+            i32::from(tables.head.units_per_em),
+        );
+        engine.ppem = ppem;
+        engine.scale = scale;
+        engine.unscaled_scale = scale;
+        engine.is_variable = face.is_variable();
+        if engine.is_variable {
+            engine.coords = coords.iter().map(|c| i32::from(c.get())).collect();
+        }
+
+        if let Some(cvt) = tables.cvt {
+            #[cfg_attr(not(feature = "variable-fonts"), allow(unused_mut))]
+            let mut values: Vec<f32> = cvt.values.into_iter().map(f32::from).collect();
+
+            #[cfg(feature = "variable-fonts")]
+            {
+                if let Some(cvar) = tables.cvar {
+                    if coords.iter().any(|c| c.get() != 0) {
+                        // Malformed variation data is ignored, like in `glyph_deltas`.
+                        let _ = cvar.apply(coords, &mut values);
+                    }
+                }
+            }
+
+            engine.cvt = values
+                .into_iter()
+                .map(|v| math::mul_fix(v.round() as i32, scale))
+                .collect();
+        }
+
+        engine.execute(Program::Font).ok()?;
+        engine.execute(Program::ControlValue).ok()?;
+
+        Some(Hinter {
+            face,
+            glyf,
+            cvt: engine.cvt.clone(),
+            storage: engine.storage.clone(),
+            gs: engine.gs,
+            twilight: engine.twilight.clone(),
+            engine,
+            #[cfg(feature = "variable-fonts")]
+            coords: coords.to_vec(),
+            outline: Zone::default(),
+        })
+    }
+
+    /// Outlines a hinted glyph.
+    ///
+    /// Coordinates are in pixels, with the horizontal origin at the hinted left side bearing point.
+    ///
+    /// Returns a tight glyph bounding box in pixels.
+    pub fn outline_glyph(
+        &mut self,
+        glyph_id: GlyphId,
+        builder: &mut dyn OutlineBuilder,
+    ) -> Option<RectF> {
+        let phantom_points = self.load(glyph_id)?;
+        if self.outline.len() == 0 {
+            return None;
+        }
+
+        let origin = phantom_points[0].x as f32;
+        let scale = 1.0 / math::ONE_PIXEL as f32;
+        let ts = Transform::new(scale, 0.0, 0.0, scale, -origin * scale, 0.0);
+        let mut b = glyf::Builder::new(ts, RectF::new(), builder);
+
+        let mut start = 0;
+        for end in &self.outline.contours {
+            for i in start..=*end {
+                let p = self.outline.points[i];
+                let on_curve = self.outline.flags[i] & ON_CURVE != 0;
+                b.push_point(p.x as f32, p.y as f32, on_curve, i == *end);
+            }
+
+            start = end + 1;
+        }
+
+        Some(b.bbox)
+    }
+
+    /// Returns a hinted glyph horizontal advance in pixels.
+    pub fn glyph_hor_advance(&mut self, glyph_id: GlyphId) -> Option<f32> {
+        let phantom_points = self.load(glyph_id)?;
+        let advance = phantom_points[1].x.wrapping_sub(phantom_points[0].x);
+        Some(advance as f32 / math::ONE_PIXEL as f32)
+    }
+
+    fn load(&mut self, glyph_id: GlyphId) -> Option<[Point; PHANTOM_POINTS_LEN]> {
+        if glyph_id.0 >= self.face.number_of_glyphs() {
+            return None;
+        }
+
+        self.outline.clear();
+        self.load_glyph(glyph_id, 0)
+    }
+
+    /// Loads a glyph into the outline accumulator.
+    ///
+    /// Returns hinted phantom points.
+    fn load_glyph(&mut self, glyph_id: GlyphId, depth: u8) -> Option<[Point; PHANTOM_POINTS_LEN]> {
+        if depth >= glyf::MAX_COMPONENTS {
+            return None;
+        }
+
+        // Empty glyphs are not stored in the `glyf` table.
+        let data = self.glyf.get(glyph_id).unwrap_or(&[]);
+        let mut s = Stream::new(data);
+        let number_of_contours = s.read::<i16>().unwrap_or(0);
+        let x_min = s.read::<i16>().unwrap_or(0);
+        s.skip::<i16>(); // y_min
+        s.skip::<i16>(); // x_max
+        let y_max = s.read::<i16>().unwrap_or(0);
+
+        let deltas = self.glyph_deltas(glyph_id);
+        let delta = |i: usize| {
+            deltas
+                .get(i)
+                .map(|d| Point::new(round_delta(d.x), round_delta(d.y)))
+                .unwrap_or_default()
+        };
+
+        if number_of_contours > 0 {
+            let number_of_contours = core::num::NonZeroU16::new(number_of_contours as u16)?;
+            let points = glyf::parse_simple_outline(s.tail()?, number_of_contours)?;
+
+            let mut zone = core::mem::take(&mut self.engine.glyph);
+            zone.clear();
+            for (i, point) in points.enumerate() {
+                let d = delta(i);
+                let p = Point::new(
+                    i32::from(point.x).wrapping_add(d.x),
+                    i32::from(point.y).wrapping_add(d.y),
+                );
+                if point.last_point {
+                    zone.contours.push(i);
+                }
+
+                let flags = if point.on_curve_point { ON_CURVE } else { 0 };
+                zone.push(p, self.scale_point(p), flags);
+            }
+
+            let points_len = zone.len();
+            let phantom_points =
+                self.phantom_points(glyph_id, x_min, y_max, |i| delta(points_len + i));
+            for p in &phantom_points {
+                zone.push(*p, self.scale_point(*p), 0);
+            }
+
+            self.engine.glyph = zone;
+            self.engine.unscaled_scale = self.engine.scale;
+            let instructions = glyf::glyph_instructions(data).unwrap_or(&[]);
+            self.hint(instructions);
+
+            let zone = &self.engine.glyph;
+            let base = self.outline.len();
+            self.outline
+                .contours
+                .extend(zone.contours.iter().map(|end| base + end));
+            for i in 0..points_len {
+                let p = zone.points[i];
+                self.outline.push(p, p, zone.flags[i] & ON_CURVE);
+            }
+
+            Some(last_phantom_points(&zone.points))
+        } else if number_of_contours < 0 {
+            let start = self.outline.len();
+            let start_contour = self.outline.contours.len();
+
+            let components = glyf::CompositeGlyphIter::new(s.tail()?);
+            let components_len = components.clone().count();
+            let mut phantom_points =
+                self.phantom_points(glyph_id, x_min, y_max, |i| delta(components_len + i));
+            for p in &mut phantom_points {
+                *p = self.scale_point(*p);
+            }
+
+            let mut has_instructions = false;
+            for (i, component) in components.enumerate() {
+                let component_start = self.outline.len();
+                let component_phantom_points = self.load_glyph(component.glyph_id, depth + 1)?;
+                if component.flags.use_my_metrics() {
+                    phantom_points = component_phantom_points;
+                }
+
+                let ts = component.transform;
+                let has_transform = ts.a != 1.0 || ts.b != 0.0 || ts.c != 0.0 || ts.d != 1.0;
+                let (xx, yx, xy, yy) = (
+                    to_fixed(ts.a),
+                    to_fixed(ts.b),
+                    to_fixed(ts.c),
+                    to_fixed(ts.d),
+                );
+                if has_transform {
+                    for p in &mut self.outline.points[component_start..] {
+                        *p = Point::new(
+                            math::mul_fix(p.x, xx).wrapping_add(math::mul_fix(p.y, xy)),
+                            math::mul_fix(p.x, yx).wrapping_add(math::mul_fix(p.y, yy)),
+                        );
+                    }
+                }
+
+                let offset = if let Some((parent, child)) = component.matching_points {
+                    // Align the parent point with the component one.
+                    let k = start + usize::from(parent);
+                    let l = component_start + usize::from(child);
+                    if k >= component_start || l >= self.outline.len() {
+                        return None;
+                    }
+
+                    self.outline.points[k].sub(self.outline.points[l])
+                } else {
+                    let d = delta(i);
+                    let mut x = (ts.e as i32).wrapping_add(d.x);
+                    let mut y = (ts.f as i32).wrapping_add(d.y);
+                    if x != 0 || y != 0 {
+                        if has_transform && component.flags.scaled_component_offset() {
+                            x = math::mul_fix(x, math::hypot(xx, xy));
+                            y = math::mul_fix(y, math::hypot(yy, yx));
+                        }
+
+                        x = math::mul_fix(x, self.engine.scale);
+                        y = math::mul_fix(y, self.engine.scale);
+                        if component.flags.round_xy_to_grid() {
+                            x = math::round(x);
+                            y = math::round(y);
+                        }
+                    }
+
+                    Point::new(x, y)
+                };
+
+                if offset.x != 0 || offset.y != 0 {
+                    for p in &mut self.outline.points[component_start..] {
+                        p.x = p.x.wrapping_add(offset.x);
+                        p.y = p.y.wrapping_add(offset.y);
+                    }
+                }
+
+                has_instructions = component.flags.we_have_instructions();
+            }
+
+            if has_instructions && self.outline.len() > start {
+                let instructions = glyf::glyph_instructions(data).unwrap_or(&[]);
+                phantom_points =
+                    self.hint_composite(start, start_contour, phantom_points, instructions);
+            }
+
+            Some(phantom_points)
+        } else {
+            let phantom_points = self.phantom_points(glyph_id, 0, 0, delta);
+            let mut zone = core::mem::take(&mut self.engine.glyph);
+            zone.clear();
+            for p in &phantom_points {
+                zone.push(*p, self.scale_point(*p), 0);
+            }
+
+            self.engine.glyph = zone;
+            self.engine.unscaled_scale = self.engine.scale;
+            self.hint(&[]);
+            Some(last_phantom_points(&self.engine.glyph.points))
+        }
+    }
+
+    /// Hints composite glyph points starting from `start`.
+    fn hint_composite(
+        &mut self,
+        start: usize,
+        start_contour: usize,
+        phantom_points: [Point; PHANTOM_POINTS_LEN],
+        instructions: &'a [u8],
+    ) -> [Point; PHANTOM_POINTS_LEN] {
+        let mut zone = core::mem::take(&mut self.engine.glyph);
+        zone.clear();
+        // Components are already hinted, so we have to use scaled points as unscaled.
+        for i in start..self.outline.len() {
+            let p = self.outline.points[i];
+            zone.push(p, p, self.outline.flags[i] & ON_CURVE);
+        }
+
+        for p in &phantom_points {
+            zone.push(*p, *p, 0);
+        }
+
+        zone.contours.extend(
+            self.outline.contours[start_contour..]
+                .iter()
+                .map(|end| end - start),
+        );
+
+        self.engine.glyph = zone;
+        self.engine.unscaled_scale = 0x10000;
+        self.hint(instructions);
+
+        let zone = &self.engine.glyph;
+        for (i, j) in (start..self.outline.len()).enumerate() {
+            self.outline.points[j] = zone.points[i];
+            self.outline.flags[j] = zone.flags[i] & ON_CURVE;
+        }
+
+        last_phantom_points(&zone.points)
+    }
+
+    /// Executes glyph instructions on the current glyph zone.
+    ///
+    /// The last four zone points must be phantom points.
+    fn hint(&mut self, instructions: &'a [u8]) {
+        // Phantom points are always rounded, even when there are no instructions.
+        let len = self.engine.glyph.len();
+        if let Some(points) = self
+            .engine
+            .glyph
+            .points
+            .get_mut(len.saturating_sub(PHANTOM_POINTS_LEN)..)
+        {
+            if points.len() == PHANTOM_POINTS_LEN {
+                points[0].x = math::round(points[0].x);
+                points[1].x = math::round(points[1].x);
+                points[2].y = math::round(points[2].y);
+                points[3].y = math::round(points[3].y);
+            }
+        }
+
+        if instructions.is_empty() || self.gs.instruct_control & 1 != 0 {
+            return;
+        }
+
+        self.engine.cvt.clone_from(&self.cvt);
+        self.engine.storage.clone_from(&self.storage);
+        self.engine.twilight.copy_from(&self.twilight);
+        self.engine.gs = if self.gs.instruct_control & 2 != 0 {
+            GraphicsState::default()
+        } else {
+            self.gs
+        };
+
+        self.engine.glyph_program = instructions;
+        // Errors are ignored, like in other implementations.
+        // Points moved before an error remain moved.
+        let _ = self.engine.execute(Program::Glyph);
+    }
+
+    /// Returns unscaled phantom points.
+    fn phantom_points(
+        &self,
+        glyph_id: GlyphId,
+        x_min: i16,
+        y_max: i16,
+        delta: impl Fn(usize) -> Point,
+    ) -> [Point; PHANTOM_POINTS_LEN] {
+        let tables = self.face.tables();
+        let hmtx = tables.hmtx;
+        let advance = hmtx.and_then(|t| t.advance(glyph_id)).unwrap_or(0);
+        let lsb = hmtx.and_then(|t| t.side_bearing(glyph_id)).unwrap_or(0);
+
+        let ascender = i32::from(tables.hhea.ascender);
+        let descender = i32::from(tables.hhea.descender);
+        let vmtx = tables.vmtx;
+        let (vertical_advance, tsb) = match (
+            vmtx.and_then(|t| t.advance(glyph_id)),
+            vmtx.and_then(|t| t.side_bearing(glyph_id)),
+        ) {
+            (Some(advance), Some(tsb)) => (i32::from(advance), i32::from(tsb)),
+            _ => ((ascender - descender).abs(), ascender - i32::from(y_max)),
+        };
+
+        let x = i32::from(x_min) - i32::from(lsb);
+        let y = i32::from(y_max) + tsb;
+        let points = [
+            Point::new(x, 0),
+            Point::new(x + i32::from(advance), 0),
+            Point::new(0, y),
+            Point::new(0, y - vertical_advance),
+        ];
+
+        let mut result = [Point::default(); PHANTOM_POINTS_LEN];
+        for (i, p) in points.iter().enumerate() {
+            let d = delta(i);
+            result[i] = Point::new(p.x.wrapping_add(d.x), p.y.wrapping_add(d.y));
+        }
+
+        result
+    }
+
+    #[inline]
+    fn scale_point(&self, p: Point) -> Point {
+        Point::new(
+            math::mul_fix(p.x, self.engine.scale),
+            math::mul_fix(p.y, self.engine.scale),
+        )
+    }
+
+    /// Returns glyph points deltas for the current variation coordinates,
+    /// followed by phantom points deltas.
+    #[cfg(feature = "variable-fonts")]
+    fn glyph_deltas(&self, glyph_id: GlyphId) -> Vec<PointF> {
+        let mut deltas = Vec::new();
+        if let Some(gvar) = self.face.tables().gvar {
+            if self.coords.iter().any(|c| c.get() != 0) {
+                let _ = gvar.glyph_deltas(self.glyf, &self.coords, glyph_id, |d| deltas.push(d));
+            }
+        }
+
+        deltas
+    }
+
+    #[cfg(not(feature = "variable-fonts"))]
+    fn glyph_deltas(&self, _: GlyphId) -> Vec<PointF> {
+        Vec::new()
+    }
+}
+
+fn last_phantom_points(points: &[Point]) -> [Point; PHANTOM_POINTS_LEN] {
+    let mut phantom_points = [Point::default(); PHANTOM_POINTS_LEN];
+    if let Some(points) = points.get(points.len().saturating_sub(PHANTOM_POINTS_LEN)..) {
+        for (a, b) in phantom_points.iter_mut().zip(points) {
+            *a = *b;
+        }
+    }
+
+    phantom_points
+}
+
+#[inline]
+fn round_delta(v: f32) -> i32 {
+    // `as` saturates, so this never overflows.
+    v.round() as i32
+}
+
+#[inline]
+fn to_fixed(v: f32) -> i32 {
+    (v * 65536.0) as i32
+}
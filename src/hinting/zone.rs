@@ -0,0 +1,226 @@
+//! Glyph and twilight zones.
+
+use std::vec::Vec;
+
+use super::math;
+
+/// A point in 26.6 or in font units.
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl Point {
+    #[inline]
+    pub fn new(x: i32, y: i32) -> Self {
+        Point { x, y }
+    }
+
+    #[inline]
+    pub fn sub(self, other: Point) -> Point {
+        Point {
+            x: self.x.wrapping_sub(other.x),
+            y: self.y.wrapping_sub(other.y),
+        }
+    }
+}
+
+pub const ON_CURVE: u8 = 0x01;
+pub const TOUCHED_X: u8 = 0x02;
+pub const TOUCHED_Y: u8 = 0x04;
+pub const TOUCHED: u8 = TOUCHED_X | TOUCHED_Y;
+
+/// A set of points the instructions operate on.
+#[derive(Clone, Default, Debug)]
+pub struct Zone {
+    /// Points in font units, used to measure original distances.
+    pub unscaled: Vec<Point>,
+    /// Scaled points before hinting.
+    pub original: Vec<Point>,
+    /// Scaled points after hinting.
+    pub points: Vec<Point>,
+    pub flags: Vec<u8>,
+    /// Indices of the last point of each contour.
+    pub contours: Vec<usize>,
+}
+
+impl Zone {
+    /// Resets the zone to `len` points at the origin.
+    pub fn reset(&mut self, len: usize) {
+        self.clear();
+        self.unscaled.resize(len, Point::default());
+        self.original.resize(len, Point::default());
+        self.points.resize(len, Point::default());
+        self.flags.resize(len, 0);
+    }
+
+    pub fn clear(&mut self) {
+        self.unscaled.clear();
+        self.original.clear();
+        self.points.clear();
+        self.flags.clear();
+        self.contours.clear();
+    }
+
+    pub fn push(&mut self, unscaled: Point, original: Point, flags: u8) {
+        self.unscaled.push(unscaled);
+        self.original.push(original);
+        self.points.push(original);
+        self.flags.push(flags);
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    /// Copies the state of another zone, reusing allocations.
+    pub fn copy_from(&mut self, other: &Zone) {
+        self.unscaled.clone_from(&other.unscaled);
+        self.original.clone_from(&other.original);
+        self.points.clone_from(&other.points);
+        self.flags.clone_from(&other.flags);
+        self.contours.clone_from(&other.contours);
+    }
+
+    /// Interpolates untouched points along an axis.
+    ///
+    /// Implements the `IUP` instruction.
+    pub fn interpolate(&mut self, is_x: bool) {
+        let mask = if is_x { TOUCHED_X } else { TOUCHED_Y };
+        let len = self.len();
+        if len == 0 {
+            return;
+        }
+
+        let mut point = 0;
+        for i in 0..self.contours.len() {
+            let end_point = self.contours[i].min(len.saturating_sub(1));
+            let first_point = point;
+            while point <= end_point && self.flags[point] & mask == 0 {
+                point += 1;
+            }
+
+            if point <= end_point {
+                let first_touched = point;
+                let mut cur_touched = point;
+                point += 1;
+                while point <= end_point {
+                    if self.flags[point] & mask != 0 {
+                        self.interpolate_range(
+                            is_x,
+                            cur_touched + 1,
+                            point - 1,
+                            cur_touched,
+                            point,
+                        );
+                        cur_touched = point;
+                    }
+
+                    point += 1;
+                }
+
+                if cur_touched == first_touched {
+                    self.shift_range(is_x, first_point, end_point, cur_touched);
+                } else {
+                    self.interpolate_range(
+                        is_x,
+                        cur_touched + 1,
+                        end_point,
+                        cur_touched,
+                        first_touched,
+                    );
+                    if first_touched > 0 {
+                        self.interpolate_range(
+                            is_x,
+                            first_point,
+                            first_touched - 1,
+                            cur_touched,
+                            first_touched,
+                        );
+                    }
+                }
+            }
+
+            point = point.max(end_point + 1);
+        }
+    }
+
+    fn shift_range(&mut self, is_x: bool, p1: usize, p2: usize, p: usize) {
+        let delta = coord(is_x, self.points[p]).wrapping_sub(coord(is_x, self.original[p]));
+        if delta == 0 {
+            return;
+        }
+
+        for i in (p1..=p2).filter(|i| *i != p) {
+            let v = coord(is_x, self.points[i]).wrapping_add(delta);
+            set_coord(is_x, &mut self.points[i], v);
+        }
+    }
+
+    fn interpolate_range(
+        &mut self,
+        is_x: bool,
+        p1: usize,
+        p2: usize,
+        mut ref1: usize,
+        mut ref2: usize,
+    ) {
+        if p1 > p2 || ref1 >= self.len() || ref2 >= self.len() {
+            return;
+        }
+
+        let mut unscaled1 = coord(is_x, self.unscaled[ref1]);
+        let mut unscaled2 = coord(is_x, self.unscaled[ref2]);
+        if unscaled1 > unscaled2 {
+            core::mem::swap(&mut unscaled1, &mut unscaled2);
+            core::mem::swap(&mut ref1, &mut ref2);
+        }
+
+        let org1 = coord(is_x, self.original[ref1]);
+        let org2 = coord(is_x, self.original[ref2]);
+        let cur1 = coord(is_x, self.points[ref1]);
+        let cur2 = coord(is_x, self.points[ref2]);
+        let delta1 = cur1.wrapping_sub(org1);
+        let delta2 = cur2.wrapping_sub(org2);
+
+        let mut scale = None;
+        for i in p1..=p2 {
+            let x = coord(is_x, self.original[i]);
+            let v = if x <= org1 {
+                x.wrapping_add(delta1)
+            } else if x >= org2 {
+                x.wrapping_add(delta2)
+            } else if cur1 == cur2 || unscaled1 == unscaled2 {
+                cur1
+            } else {
+                let scale = *scale.get_or_insert_with(|| {
+                    math::div_fix(cur2.wrapping_sub(cur1), unscaled2.wrapping_sub(unscaled1))
+                });
+                let unscaled = coord(is_x, self.unscaled[i]);
+                cur1.wrapping_add(math::mul_fix(unscaled.wrapping_sub(unscaled1), scale))
+            };
+
+            set_coord(is_x, &mut self.points[i], v);
+        }
+    }
+}
+
+#[inline]
+fn coord(is_x: bool, p: Point) -> i32 {
+    if is_x {
+        p.x
+    } else {
+        p.y
+    }
+}
+
+#[inline]
+fn set_coord(is_x: bool, p: &mut Point, v: i32) {
+    if is_x {
+        p.x = v;
+    } else {
+        p.y = v;
+    }
+}
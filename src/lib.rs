@@ -61,33 +61,47 @@ mod aat;
 mod delta_set;
 #[cfg(feature = "opentype-layout")]
 mod ggg;
+#[cfg(feature = "hinting")]
+pub mod hinting;
 mod language;
 mod parser;
 mod tables;
+pub mod truetype_instructions;
 #[cfg(feature = "variable-fonts")]
 mod var_store;
+#[cfg(feature = "woff")]
+pub mod woff;
+#[cfg(feature = "woff2")]
+pub mod woff2;
+
+use core::convert::TryFrom;
 
 use head::IndexToLocationFormat;
-pub use parser::{Fixed, FromData, LazyArray16, LazyArray32, LazyArrayIter16, LazyArrayIter32};
+pub use parser::{
+    Fixed, FromData, LazyArray16, LazyArray32, LazyArrayIter16, LazyArrayIter32, U24,
+};
 use parser::{NumFrom, Offset, Offset32, Stream, TryNumFrom};
 
 #[cfg(feature = "variable-fonts")]
 pub use fvar::VariationAxis;
+#[cfg(feature = "variable-fonts")]
+pub use var_store::RegionAxisCoordinates;
 
 pub use language::Language;
 pub use name::{name_id, PlatformId};
 pub use os2::{Permissions, ScriptMetrics, Style, UnicodeRanges, Weight, Width};
-pub use tables::CFFError;
 #[cfg(feature = "apple-layout")]
 pub use tables::{ankr, feat, kerx, morx, trak};
 #[cfg(feature = "variable-fonts")]
-pub use tables::{avar, cff2, fvar, gvar, hvar, mvar, vvar};
+pub use tables::{avar, cff2, cvar, fvar, gvar, hvar, mvar, stat, vvar};
+#[cfg(feature = "opentype-layout")]
+pub use tables::{base, gdef, gpos, gsub, jstf, math};
 pub use tables::{cbdt, cblc, cff1 as cff, vhea};
 pub use tables::{
-    cmap, colr, cpal, glyf, head, hhea, hmtx, kern, loca, maxp, name, os2, post, sbix, svg, vorg,
+    cmap, colr, cpal, cvt, fpgm, gasp, glyf, hdmx, head, hhea, hmtx, kern, loca, ltsh, maxp, meta,
+    name, os2, post, prep, sbix, svg, vdmx, vorg,
 };
-#[cfg(feature = "opentype-layout")]
-pub use tables::{gdef, gpos, gsub, math};
+pub use tables::{CFFError, CFFHintsBuilder};
 
 #[cfg(feature = "opentype-layout")]
 pub mod opentype_layout {
@@ -730,6 +744,9 @@ const MAX_VAR_COORDS: usize = 64;
 #[derive(Clone)]
 struct VarCoords {
     data: [NormalizedCoordinate; MAX_VAR_COORDS],
+    // Coordinates before the `avar` mapping.
+    // `avar` mapping is not idempotent, therefore we have to preserve them.
+    unmapped: [NormalizedCoordinate; MAX_VAR_COORDS],
     len: u8,
 }
 
@@ -738,6 +755,7 @@ impl Default for VarCoords {
     fn default() -> Self {
         Self {
             data: [NormalizedCoordinate::default(); MAX_VAR_COORDS],
+            unmapped: [NormalizedCoordinate::default(); MAX_VAR_COORDS],
             len: u8::default(),
         }
     }
@@ -751,12 +769,28 @@ impl VarCoords {
     }
 
     #[inline]
-    fn as_mut_slice(&mut self) -> &mut [NormalizedCoordinate] {
+    fn unmapped_slice(&self) -> &[NormalizedCoordinate] {
+        &self.unmapped[0..usize::from(self.len)]
+    }
+
+    fn apply_avar(&mut self, avar: Option<avar::Table>) {
         let end = usize::from(self.len);
-        &mut self.data[0..end]
+        self.data[0..end].copy_from_slice(&self.unmapped[0..end]);
+        if let Some(avar) = avar {
+            // Ignore error.
+            let _ = avar.map_coordinates(&mut self.data[0..end]);
+        }
     }
 }
 
+// A source of layout variation deltas.
+#[cfg(feature = "opentype-layout")]
+#[derive(Clone, Copy)]
+enum LayoutDeltas {
+    Gdef,
+    Base,
+}
+
 /// A list of font face parsing errors.
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub enum FaceParsingError {
@@ -928,21 +962,31 @@ pub struct RawFaceTables<'a> {
     pub cmap: Option<&'a [u8]>,
     pub colr: Option<&'a [u8]>,
     pub cpal: Option<&'a [u8]>,
+    pub cvt: Option<&'a [u8]>,
     pub ebdt: Option<&'a [u8]>,
     pub eblc: Option<&'a [u8]>,
+    pub fpgm: Option<&'a [u8]>,
+    pub gasp: Option<&'a [u8]>,
     pub glyf: Option<&'a [u8]>,
+    pub hdmx: Option<&'a [u8]>,
     pub hmtx: Option<&'a [u8]>,
     pub kern: Option<&'a [u8]>,
     pub loca: Option<&'a [u8]>,
+    pub ltsh: Option<&'a [u8]>,
+    pub meta: Option<&'a [u8]>,
     pub name: Option<&'a [u8]>,
     pub os2: Option<&'a [u8]>,
     pub post: Option<&'a [u8]>,
+    pub prep: Option<&'a [u8]>,
     pub sbix: Option<&'a [u8]>,
     pub svg: Option<&'a [u8]>,
+    pub vdmx: Option<&'a [u8]>,
     pub vhea: Option<&'a [u8]>,
     pub vmtx: Option<&'a [u8]>,
     pub vorg: Option<&'a [u8]>,
 
+    #[cfg(feature = "opentype-layout")]
+    pub base: Option<&'a [u8]>,
     #[cfg(feature = "opentype-layout")]
     pub gdef: Option<&'a [u8]>,
     #[cfg(feature = "opentype-layout")]
@@ -950,6 +994,8 @@ pub struct RawFaceTables<'a> {
     #[cfg(feature = "opentype-layout")]
     pub gsub: Option<&'a [u8]>,
     #[cfg(feature = "opentype-layout")]
+    pub jstf: Option<&'a [u8]>,
+    #[cfg(feature = "opentype-layout")]
     pub math: Option<&'a [u8]>,
 
     #[cfg(feature = "apple-layout")]
@@ -968,6 +1014,8 @@ pub struct RawFaceTables<'a> {
     #[cfg(feature = "variable-fonts")]
     pub cff2: Option<&'a [u8]>,
     #[cfg(feature = "variable-fonts")]
+    pub cvar: Option<&'a [u8]>,
+    #[cfg(feature = "variable-fonts")]
     pub fvar: Option<&'a [u8]>,
     #[cfg(feature = "variable-fonts")]
     pub gvar: Option<&'a [u8]>,
@@ -976,9 +1024,95 @@ pub struct RawFaceTables<'a> {
     #[cfg(feature = "variable-fonts")]
     pub mvar: Option<&'a [u8]>,
     #[cfg(feature = "variable-fonts")]
+    pub stat: Option<&'a [u8]>,
+    #[cfg(feature = "variable-fonts")]
     pub vvar: Option<&'a [u8]>,
 }
 
+impl<'a> RawFaceTables<'a> {
+    /// Sets a table data by tag.
+    ///
+    /// Unsupported tables are ignored.
+    pub(crate) fn set(&mut self, tag: Tag, table_data: Option<&'a [u8]>) {
+        match &tag.to_bytes() {
+            b"bdat" => self.bdat = table_data,
+            b"bloc" => self.bloc = table_data,
+            b"CBDT" => self.cbdt = table_data,
+            b"CBLC" => self.cblc = table_data,
+            b"CFF " => self.cff = table_data,
+            #[cfg(feature = "variable-fonts")]
+            b"CFF2" => self.cff2 = table_data,
+            b"COLR" => self.colr = table_data,
+            b"CPAL" => self.cpal = table_data,
+            b"EBDT" => self.ebdt = table_data,
+            b"EBLC" => self.eblc = table_data,
+            #[cfg(feature = "opentype-layout")]
+            b"BASE" => self.base = table_data,
+            #[cfg(feature = "opentype-layout")]
+            b"GDEF" => self.gdef = table_data,
+            #[cfg(feature = "opentype-layout")]
+            b"GPOS" => self.gpos = table_data,
+            #[cfg(feature = "opentype-layout")]
+            b"GSUB" => self.gsub = table_data,
+            #[cfg(feature = "opentype-layout")]
+            b"JSTF" => self.jstf = table_data,
+            #[cfg(feature = "opentype-layout")]
+            b"MATH" => self.math = table_data,
+            #[cfg(feature = "variable-fonts")]
+            b"HVAR" => self.hvar = table_data,
+            b"LTSH" => self.ltsh = table_data,
+            #[cfg(feature = "variable-fonts")]
+            b"MVAR" => self.mvar = table_data,
+            b"OS/2" => self.os2 = table_data,
+            #[cfg(feature = "variable-fonts")]
+            b"STAT" => self.stat = table_data,
+            b"SVG " => self.svg = table_data,
+            b"VDMX" => self.vdmx = table_data,
+            b"VORG" => self.vorg = table_data,
+            #[cfg(feature = "variable-fonts")]
+            b"VVAR" => self.vvar = table_data,
+            #[cfg(feature = "apple-layout")]
+            b"ankr" => self.ankr = table_data,
+            #[cfg(feature = "variable-fonts")]
+            b"avar" => self.avar = table_data,
+            b"cmap" => self.cmap = table_data,
+            #[cfg(feature = "variable-fonts")]
+            b"cvar" => self.cvar = table_data,
+            b"cvt " => self.cvt = table_data,
+            #[cfg(feature = "apple-layout")]
+            b"feat" => self.feat = table_data,
+            b"fpgm" => self.fpgm = table_data,
+            #[cfg(feature = "variable-fonts")]
+            b"fvar" => self.fvar = table_data,
+            b"gasp" => self.gasp = table_data,
+            b"glyf" => self.glyf = table_data,
+            #[cfg(feature = "variable-fonts")]
+            b"gvar" => self.gvar = table_data,
+            b"head" => self.head = table_data.unwrap_or_default(),
+            b"hdmx" => self.hdmx = table_data,
+            b"hhea" => self.hhea = table_data.unwrap_or_default(),
+            b"hmtx" => self.hmtx = table_data,
+            b"kern" => self.kern = table_data,
+            #[cfg(feature = "apple-layout")]
+            b"kerx" => self.kerx = table_data,
+            b"loca" => self.loca = table_data,
+            b"maxp" => self.maxp = table_data.unwrap_or_default(),
+            b"meta" => self.meta = table_data,
+            #[cfg(feature = "apple-layout")]
+            b"morx" => self.morx = table_data,
+            b"name" => self.name = table_data,
+            b"post" => self.post = table_data,
+            b"prep" => self.prep = table_data,
+            b"sbix" => self.sbix = table_data,
+            #[cfg(feature = "apple-layout")]
+            b"trak" => self.trak = table_data,
+            b"vhea" => self.vhea = table_data,
+            b"vmtx" => self.vmtx = table_data,
+            _ => {}
+        }
+    }
+}
+
 /// Parsed face tables.
 ///
 /// Unlike [`Face`], provides a low-level parsing abstraction over TrueType tables.
@@ -1000,19 +1134,29 @@ pub struct FaceTables<'a> {
     pub cff: Option<cff::Table<'a>>,
     pub cmap: Option<cmap::Table<'a>>,
     pub colr: Option<colr::Table<'a>>,
+    pub cvt: Option<cvt::Table<'a>>,
     pub ebdt: Option<cbdt::Table<'a>>,
+    pub fpgm: Option<fpgm::Table<'a>>,
+    pub gasp: Option<gasp::Table<'a>>,
     pub glyf: Option<glyf::Table<'a>>,
+    pub hdmx: Option<hdmx::Table<'a>>,
     pub hmtx: Option<hmtx::Table<'a>>,
     pub kern: Option<kern::Table<'a>>,
+    pub ltsh: Option<ltsh::Table<'a>>,
+    pub meta: Option<meta::Table<'a>>,
     pub name: Option<name::Table<'a>>,
     pub os2: Option<os2::Table<'a>>,
     pub post: Option<post::Table<'a>>,
+    pub prep: Option<prep::Table<'a>>,
     pub sbix: Option<sbix::Table<'a>>,
     pub svg: Option<svg::Table<'a>>,
+    pub vdmx: Option<vdmx::Table<'a>>,
     pub vhea: Option<vhea::Table>,
     pub vmtx: Option<hmtx::Table<'a>>,
     pub vorg: Option<vorg::Table<'a>>,
 
+    #[cfg(feature = "opentype-layout")]
+    pub base: Option<base::Table<'a>>,
     #[cfg(feature = "opentype-layout")]
     pub gdef: Option<gdef::Table<'a>>,
     #[cfg(feature = "opentype-layout")]
@@ -1020,6 +1164,8 @@ pub struct FaceTables<'a> {
     #[cfg(feature = "opentype-layout")]
     pub gsub: Option<opentype_layout::LayoutTable<'a>>,
     #[cfg(feature = "opentype-layout")]
+    pub jstf: Option<jstf::Table<'a>>,
+    #[cfg(feature = "opentype-layout")]
     pub math: Option<math::Table<'a>>,
 
     #[cfg(feature = "apple-layout")]
@@ -1038,6 +1184,8 @@ pub struct FaceTables<'a> {
     #[cfg(feature = "variable-fonts")]
     pub cff2: Option<cff2::Table<'a>>,
     #[cfg(feature = "variable-fonts")]
+    pub cvar: Option<cvar::Table<'a>>,
+    #[cfg(feature = "variable-fonts")]
     pub fvar: Option<fvar::Table<'a>>,
     #[cfg(feature = "variable-fonts")]
     pub gvar: Option<gvar::Table<'a>>,
@@ -1046,6 +1194,8 @@ pub struct FaceTables<'a> {
     #[cfg(feature = "variable-fonts")]
     pub mvar: Option<mvar::Table<'a>>,
     #[cfg(feature = "variable-fonts")]
+    pub stat: Option<stat::Table<'a>>,
+    #[cfg(feature = "variable-fonts")]
     pub vvar: Option<vvar::Table<'a>>,
 }
 
@@ -1135,66 +1285,7 @@ impl<'a> Face<'a> {
             };
 
             let table_data = raw_face.data.get(start..end);
-            match &record.tag.to_bytes() {
-                b"bdat" => tables.bdat = table_data,
-                b"bloc" => tables.bloc = table_data,
-                b"CBDT" => tables.cbdt = table_data,
-                b"CBLC" => tables.cblc = table_data,
-                b"CFF " => tables.cff = table_data,
-                #[cfg(feature = "variable-fonts")]
-                b"CFF2" => tables.cff2 = table_data,
-                b"COLR" => tables.colr = table_data,
-                b"CPAL" => tables.cpal = table_data,
-                b"EBDT" => tables.ebdt = table_data,
-                b"EBLC" => tables.eblc = table_data,
-                #[cfg(feature = "opentype-layout")]
-                b"GDEF" => tables.gdef = table_data,
-                #[cfg(feature = "opentype-layout")]
-                b"GPOS" => tables.gpos = table_data,
-                #[cfg(feature = "opentype-layout")]
-                b"GSUB" => tables.gsub = table_data,
-                #[cfg(feature = "opentype-layout")]
-                b"MATH" => tables.math = table_data,
-                #[cfg(feature = "variable-fonts")]
-                b"HVAR" => tables.hvar = table_data,
-                #[cfg(feature = "variable-fonts")]
-                b"MVAR" => tables.mvar = table_data,
-                b"OS/2" => tables.os2 = table_data,
-                b"SVG " => tables.svg = table_data,
-                b"VORG" => tables.vorg = table_data,
-                #[cfg(feature = "variable-fonts")]
-                b"VVAR" => tables.vvar = table_data,
-                #[cfg(feature = "apple-layout")]
-                b"ankr" => tables.ankr = table_data,
-                #[cfg(feature = "variable-fonts")]
-                b"avar" => tables.avar = table_data,
-                b"cmap" => tables.cmap = table_data,
-                #[cfg(feature = "apple-layout")]
-                b"feat" => tables.feat = table_data,
-                #[cfg(feature = "variable-fonts")]
-                b"fvar" => tables.fvar = table_data,
-                b"glyf" => tables.glyf = table_data,
-                #[cfg(feature = "variable-fonts")]
-                b"gvar" => tables.gvar = table_data,
-                b"head" => tables.head = table_data.unwrap_or_default(),
-                b"hhea" => tables.hhea = table_data.unwrap_or_default(),
-                b"hmtx" => tables.hmtx = table_data,
-                b"kern" => tables.kern = table_data,
-                #[cfg(feature = "apple-layout")]
-                b"kerx" => tables.kerx = table_data,
-                b"loca" => tables.loca = table_data,
-                b"maxp" => tables.maxp = table_data.unwrap_or_default(),
-                #[cfg(feature = "apple-layout")]
-                b"morx" => tables.morx = table_data,
-                b"name" => tables.name = table_data,
-                b"post" => tables.post = table_data,
-                b"sbix" => tables.sbix = table_data,
-                #[cfg(feature = "apple-layout")]
-                b"trak" => tables.trak = table_data,
-                b"vhea" => tables.vhea = table_data,
-                b"vmtx" => tables.vmtx = table_data,
-                _ => {}
-            }
+            tables.set(record.tag, table_data);
         }
 
         tables
@@ -1295,21 +1386,33 @@ impl<'a> Face<'a> {
             cff: raw_tables.cff.and_then(cff::Table::parse),
             cmap: raw_tables.cmap.and_then(cmap::Table::parse),
             colr,
+            cvt: raw_tables.cvt.and_then(cvt::Table::parse),
             ebdt,
+            fpgm: raw_tables.fpgm.and_then(fpgm::Table::parse),
+            gasp: raw_tables.gasp.and_then(gasp::Table::parse),
             glyf,
+            hdmx: raw_tables
+                .hdmx
+                .and_then(|data| hdmx::Table::parse(maxp.number_of_glyphs, data)),
             hmtx,
             kern: raw_tables.kern.and_then(kern::Table::parse),
+            ltsh: raw_tables.ltsh.and_then(ltsh::Table::parse),
+            meta: raw_tables.meta.and_then(meta::Table::parse),
             name: raw_tables.name.and_then(name::Table::parse),
             os2: raw_tables.os2.and_then(os2::Table::parse),
             post: raw_tables.post.and_then(post::Table::parse),
+            prep: raw_tables.prep.and_then(prep::Table::parse),
             sbix: raw_tables
                 .sbix
                 .and_then(|data| sbix::Table::parse(maxp.number_of_glyphs, data)),
             svg: raw_tables.svg.and_then(svg::Table::parse),
+            vdmx: raw_tables.vdmx.and_then(vdmx::Table::parse),
             vhea: raw_tables.vhea.and_then(vhea::Table::parse),
             vmtx,
             vorg: raw_tables.vorg.and_then(vorg::Table::parse),
 
+            #[cfg(feature = "opentype-layout")]
+            base: raw_tables.base.and_then(base::Table::parse),
             #[cfg(feature = "opentype-layout")]
             gdef: raw_tables.gdef.and_then(gdef::Table::parse),
             #[cfg(feature = "opentype-layout")]
@@ -1321,6 +1424,8 @@ impl<'a> Face<'a> {
                 .gsub
                 .and_then(opentype_layout::LayoutTable::parse),
             #[cfg(feature = "opentype-layout")]
+            jstf: raw_tables.jstf.and_then(jstf::Table::parse),
+            #[cfg(feature = "opentype-layout")]
             math: raw_tables.math.and_then(math::Table::parse),
 
             #[cfg(feature = "apple-layout")]
@@ -1345,6 +1450,8 @@ impl<'a> Face<'a> {
             #[cfg(feature = "variable-fonts")]
             cff2: raw_tables.cff2.and_then(cff2::Table::parse),
             #[cfg(feature = "variable-fonts")]
+            cvar: raw_tables.cvar.and_then(cvar::Table::parse),
+            #[cfg(feature = "variable-fonts")]
             fvar: raw_tables.fvar.and_then(fvar::Table::parse),
             #[cfg(feature = "variable-fonts")]
             gvar: raw_tables.gvar.and_then(gvar::Table::parse),
@@ -1353,6 +1460,8 @@ impl<'a> Face<'a> {
             #[cfg(feature = "variable-fonts")]
             mvar: raw_tables.mvar.and_then(mvar::Table::parse),
             #[cfg(feature = "variable-fonts")]
+            stat: raw_tables.stat.and_then(stat::Table::parse),
+            #[cfg(feature = "variable-fonts")]
             vvar: raw_tables.vvar.and_then(vvar::Table::parse),
         })
     }
@@ -1820,6 +1929,68 @@ impl<'a> Face<'a> {
             .unwrap_or_default()
     }
 
+    /// Returns a rendering behavior for the specified pixels-per-em.
+    ///
+    /// Based on the [Grid-fitting and Scan-conversion Procedure Table](
+    /// https://docs.microsoft.com/en-us/typography/opentype/spec/gasp).
+    ///
+    /// Returns `None` when the `gasp` table is missing or doesn't cover `ppem`.
+    #[inline]
+    pub fn gasp_behavior(&self, ppem: u16) -> Option<gasp::Behavior> {
+        self.tables.gasp?.behavior(ppem)
+    }
+
+    /// Returns vertical extents in pixels for the specified pixels-per-em and device aspect ratio.
+    ///
+    /// Based on the [Vertical Device Metrics Table](
+    /// https://docs.microsoft.com/en-us/typography/opentype/spec/vdmx).
+    ///
+    /// Returns `None` when the `VDMX` table is missing or doesn't cover the requested size.
+    #[inline]
+    pub fn device_vertical_extents(
+        &self,
+        pixels_per_em: u16,
+        x_ratio: u16,
+        y_ratio: u16,
+    ) -> Option<vdmx::Record> {
+        self.tables.vdmx?.extents(pixels_per_em, x_ratio, y_ratio)
+    }
+
+    /// Writes control values in font units into `values`.
+    ///
+    /// Based on the [Control Value Table](
+    /// https://docs.microsoft.com/en-us/typography/opentype/spec/cvt).
+    ///
+    /// For variable fonts, values are adjusted by the `cvar` table
+    /// using the current variation coordinates. Results are not rounded.
+    ///
+    /// Writes at most `values.len()` values. The total number of values can be queried
+    /// via `Face::tables().cvt`.
+    ///
+    /// Returns the number of written values or `None` when the `cvt ` table is missing.
+    ///
+    /// This method is affected by variation axes.
+    pub fn cvt_values(&self, values: &mut [f32]) -> Option<usize> {
+        let cvt = self.tables.cvt?;
+        let mut len = 0;
+        for (value, cvt_value) in values.iter_mut().zip(cvt.values) {
+            *value = f32::from(cvt_value);
+            len += 1;
+        }
+
+        #[cfg(feature = "variable-fonts")]
+        {
+            if let Some(cvar) = self.tables.cvar {
+                if self.has_non_default_variation_coordinates() {
+                    // Ignore malformed variation data, like the rest of the variation code does.
+                    let _ = cvar.apply(self.coords(), &mut values[0..len]);
+                }
+            }
+        }
+
+        Some(len)
+    }
+
     /// Returns a total number of glyphs in the face.
     ///
     /// Never zero.
@@ -1834,7 +2005,7 @@ impl<'a> Face<'a> {
     ///
     /// Returns `None` instead of `0` when glyph is not found.
     ///
-    /// All subtable formats except Mixed Coverage (8) are supported.
+    /// All subtable formats are supported.
     ///
     /// If you need a more low-level control, prefer `Face::tables().cmap`.
     #[inline]
@@ -2047,6 +2218,22 @@ impl<'a> Face<'a> {
         }
     }
 
+    /// Returns glyph's horizontal advance in pixels for the specified pixels-per-em.
+    ///
+    /// Based on the [Horizontal Device Metrics Table](
+    /// https://docs.microsoft.com/en-us/typography/opentype/spec/hdmx).
+    ///
+    /// Returns `None` when the `hdmx` table is missing or doesn't have a record
+    /// for the requested size.
+    #[inline]
+    pub fn glyph_hor_device_advance(&self, glyph_id: GlyphId, pixels_per_em: u16) -> Option<u16> {
+        let pixels_per_em = u8::try_from(pixels_per_em).ok()?;
+        self.tables
+            .hdmx?
+            .glyph_advance(glyph_id, pixels_per_em)
+            .map(u16::from)
+    }
+
     /// Returns glyph's name.
     ///
     /// Uses the `post` and `CFF` tables as sources.
@@ -2181,6 +2368,140 @@ impl<'a> Face<'a> {
         self.tables.head.global_bbox
     }
 
+    /// Returns a glyph outline point at the specified index.
+    ///
+    /// Only `glyf` outlines are supported. Points of composite glyphs are indexed
+    /// in the order of their components.
+    ///
+    /// This method is affected by variation axes.
+    pub fn glyph_contour_point(&self, glyph_id: GlyphId, index: u16) -> Option<PointF> {
+        let glyf = self.tables.glyf?;
+
+        #[cfg(feature = "variable-fonts")]
+        {
+            if let Some(ref gvar) = self.tables.gvar {
+                return gvar.contour_point(glyf, self.coords(), glyph_id, index);
+            }
+        }
+
+        glyf.contour_point(glyph_id, index)
+    }
+
+    /// Resolves a `GPOS` anchor position for a glyph, in design units.
+    ///
+    /// When an anchor references a glyph contour point, the point position is used,
+    /// unless it cannot be resolved.
+    /// For variable fonts, `GDEF` variation deltas are applied as well.
+    ///
+    /// Hinting device adjustments are ignored.
+    ///
+    /// This method is affected by variation axes.
+    #[cfg(feature = "opentype-layout")]
+    pub fn glyph_anchor(&self, glyph_id: GlyphId, anchor: &gpos::Anchor) -> PointF {
+        self.layout_point(
+            anchor.contour_point.map(|index| (glyph_id, index)),
+            (anchor.x, anchor.y),
+            (anchor.x_device, anchor.y_device),
+            LayoutDeltas::Gdef,
+        )
+    }
+
+    /// Resolves a `GDEF` ligature caret position for a glyph, in design units.
+    ///
+    /// Returns an X coordinate for horizontal text and an Y coordinate otherwise.
+    ///
+    /// Contour points and variation deltas are resolved
+    /// just like in [`glyph_anchor`](Face::glyph_anchor).
+    #[cfg(feature = "opentype-layout")]
+    pub fn glyph_ligature_caret(
+        &self,
+        glyph_id: GlyphId,
+        caret: &gdef::CaretValue,
+        vertical: bool,
+    ) -> f32 {
+        let contour_point = caret.contour_point.map(|index| (glyph_id, index));
+        if vertical {
+            let (value, device) = ((0, caret.coordinate), (None, caret.device));
+            let point = self.layout_point(contour_point, value, device, LayoutDeltas::Gdef);
+            point.y
+        } else {
+            let (value, device) = ((caret.coordinate, 0), (caret.device, None));
+            let point = self.layout_point(contour_point, value, device, LayoutDeltas::Gdef);
+            point.x
+        }
+    }
+
+    /// Resolves a `BASE` coordinate, in design units.
+    ///
+    /// Returns an Y coordinate for the horizontal axis and an X coordinate
+    /// for the vertical one.
+    ///
+    /// Contour points are resolved just like in [`glyph_anchor`](Face::glyph_anchor),
+    /// but `BASE` variation deltas are used instead.
+    #[cfg(feature = "opentype-layout")]
+    pub fn base_coordinate(&self, coord: &base::BaseCoord, vertical: bool) -> f32 {
+        let contour_point = coord.reference_glyph.zip(coord.contour_point);
+        if vertical {
+            let (value, device) = ((coord.coordinate, 0), (coord.device, None));
+            let point = self.layout_point(contour_point, value, device, LayoutDeltas::Base);
+            point.x
+        } else {
+            let (value, device) = ((0, coord.coordinate), (None, coord.device));
+            let point = self.layout_point(contour_point, value, device, LayoutDeltas::Base);
+            point.y
+        }
+    }
+
+    // Resolves a layout position, which can reference a glyph contour point
+    // and have variation devices.
+    #[cfg(feature = "opentype-layout")]
+    #[cfg_attr(not(feature = "variable-fonts"), allow(unused_variables))]
+    fn layout_point(
+        &self,
+        contour_point: Option<(GlyphId, u16)>,
+        value: (i16, i16),
+        devices: (Option<gpos::Device>, Option<gpos::Device>),
+        deltas: LayoutDeltas,
+    ) -> PointF {
+        if let Some((glyph_id, index)) = contour_point {
+            if let Some(point) = self.glyph_contour_point(glyph_id, index) {
+                return point;
+            }
+        }
+
+        #[allow(unused_mut)]
+        let mut point = PointF {
+            x: f32::from(value.0),
+            y: f32::from(value.1),
+        };
+
+        #[cfg(feature = "variable-fonts")]
+        {
+            let delta = |device: Option<gpos::Device>| {
+                let d = match device {
+                    Some(gpos::Device::Variation(d)) => d,
+                    _ => return 0.0,
+                };
+
+                let coords = self.coords();
+                match deltas {
+                    LayoutDeltas::Gdef => self.tables.gdef.and_then(|gdef| {
+                        gdef.glyph_variation_delta(d.outer_index, d.inner_index, coords)
+                    }),
+                    LayoutDeltas::Base => self.tables.base.and_then(|base| {
+                        base.variation_delta(d.outer_index, d.inner_index, coords)
+                    }),
+                }
+                .unwrap_or(0.0)
+            };
+
+            point.x += delta(devices.0);
+            point.y += delta(devices.1);
+        }
+
+        point
+    }
+
     /// Returns a reference to a glyph's raster image.
     ///
     /// A font can define a glyph using a raster or a vector image instead of a simple outline.
@@ -2303,6 +2624,49 @@ impl<'a> Face<'a> {
         self.tables.fvar.map(|fvar| fvar.axes).unwrap_or_default()
     }
 
+    /// Returns an iterator over named instances.
+    ///
+    /// Instance names can be resolved via [`Face::names`].
+    #[cfg(feature = "variable-fonts")]
+    #[inline]
+    pub fn named_instances(&self) -> fvar::NamedInstances<'a> {
+        self.tables
+            .fvar
+            .map(|fvar| fvar.instances)
+            .unwrap_or_default()
+    }
+
+    /// Sets variation axes coordinates from a named instance.
+    ///
+    /// Applies instance user-space coordinates to all axes at once,
+    /// just like [`Face::set_variation`] does for a single axis.
+    ///
+    /// Returns `None` when face is not variable or doesn't have such instance.
+    #[cfg(feature = "variable-fonts")]
+    pub fn set_named_instance(&mut self, index: u16) -> Option<()> {
+        if !self.is_variable() {
+            return None;
+        }
+
+        if usize::from(self.variation_axes().len()) >= MAX_VAR_COORDS {
+            return None;
+        }
+
+        let instance = self.named_instances().get(index)?;
+        for (i, (var_axis, value)) in self
+            .variation_axes()
+            .into_iter()
+            .zip(instance.coordinates)
+            .enumerate()
+        {
+            self.coordinates.unmapped[i] = var_axis.normalized_value(value.0);
+        }
+
+        self.coordinates.apply_avar(self.tables.avar);
+
+        Some(())
+    }
+
     /// Sets a variation axis coordinate.
     ///
     /// This is one of the two only mutable methods in the library.
@@ -2324,15 +2688,11 @@ impl<'a> Face<'a> {
 
         for (i, var_axis) in self.variation_axes().into_iter().enumerate() {
             if var_axis.tag == axis {
-                self.coordinates.data[i] = var_axis.normalized_value(value);
+                self.coordinates.unmapped[i] = var_axis.normalized_value(value);
             }
         }
 
-        // TODO: optimize
-        if let Some(avar) = self.tables.avar {
-            // Ignore error.
-            let _ = avar.map_coordinates(self.coordinates.as_mut_slice());
-        }
+        self.coordinates.apply_avar(self.tables.avar);
 
         Some(())
     }
@@ -2351,6 +2711,40 @@ impl<'a> Face<'a> {
         self.coordinates.as_slice().iter().any(|c| c.0 != 0)
     }
 
+    /// Returns `STAT` axis value names matching the current variation coordinates.
+    ///
+    /// Yields at most one name per design axis, in the design axes ordering,
+    /// which makes it suitable for composing face names like "SemiBold Condensed".
+    /// Elidable names, like "Regular", are yielded as well and can be skipped
+    /// using [`stat::AxisValueFlags::elidable`].
+    ///
+    /// This method is affected by variation axes.
+    #[cfg(feature = "variable-fonts")]
+    #[inline]
+    pub fn style_names(&self) -> stat::StyleNames<'a> {
+        stat::StyleNames::new(
+            self.tables.stat.unwrap_or_default(),
+            self.names(),
+            self.variation_axes(),
+            self.coordinates.unmapped_slice(),
+        )
+    }
+
+    /// Returns a name that should be used when all [`style_names`](Face::style_names)
+    /// are elidable.
+    ///
+    /// Falls back to the subfamily name when the `STAT` table doesn't specify one.
+    #[cfg(feature = "variable-fonts")]
+    #[inline]
+    pub fn style_elided_fallback_name(&self) -> Option<name::Name<'a>> {
+        let name_id = self
+            .tables
+            .stat
+            .and_then(|stat| stat.elided_fallback_name_id)
+            .unwrap_or(name_id::SUBFAMILY);
+        self.names().find_preferred(name_id)
+    }
+
     /// Parses glyph's phantom points.
     ///
     /// Available only for variable fonts with the `gvar` table.
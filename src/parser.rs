@@ -620,6 +620,13 @@ impl<'a, T: FromSlice<'a>> LazyOffsetArray16<'a, T> {
     }
 }
 
+impl<'a, T: FromSlice<'a>> Default for LazyOffsetArray16<'a, T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new(&[], LazyArray16::default())
+    }
+}
+
 impl<'a, T: FromSlice<'a> + core::fmt::Debug + Copy> core::fmt::Debug for LazyOffsetArray16<'a, T> {
     fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
         f.debug_list().entries(*self).finish()
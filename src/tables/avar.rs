@@ -3,9 +3,14 @@
 
 use core::convert::TryFrom;
 
-use crate::parser::{FromData, LazyArray16, Stream};
+use crate::delta_set::DeltaSetIndexMap;
+use crate::parser::{FromData, LazyArray16, Offset, Offset32, Stream};
+use crate::var_store::ItemVariationStore;
 use crate::NormalizedCoordinate;
 
+// Matches the maximum number of variation coordinates stored by `Face`.
+const MAX_COORDS: usize = 64;
+
 /// An axis value map.
 #[derive(Clone, Copy, Debug)]
 pub struct AxisValueMap {
@@ -92,6 +97,10 @@ pub struct Table<'a> {
     /// The segment maps array — one segment map for each axis
     /// in the order of axes specified in the `fvar` table.
     pub segment_maps: SegmentMaps<'a>,
+    /// Version 2 axis index map.
+    axis_index_map: Option<DeltaSetIndexMap<'a>>,
+    /// Version 2 variation store.
+    variation_store: Option<ItemVariationStore<'a>>,
 }
 
 impl<'a> Table<'a> {
@@ -100,34 +109,99 @@ impl<'a> Table<'a> {
         let mut s = Stream::new(data);
 
         let version = s.read::<u32>()?;
-        if version != 0x00010000 {
+        if version != 0x00010000 && version != 0x00020000 {
             return None;
         }
 
         s.skip::<u16>(); // reserved
+
+        // TODO: check that `axisCount` is the same as in `fvar`?
+        let axis_count = s.read::<u16>()?;
+        let segment_maps = SegmentMaps {
+            count: axis_count,
+            data: s.tail()?,
+        };
+
+        let mut axis_index_map = None;
+        let mut variation_store = None;
+        if version == 0x00020000 {
+            // Skip segment maps.
+            for _ in 0..axis_count {
+                let count = s.read::<u16>()?;
+                s.advance_checked(usize::from(count) * AxisValueMap::SIZE)?;
+            }
+
+            let axis_index_map_offset = s.read::<Option<Offset32>>()?;
+            let variation_store_offset = s.read::<Option<Offset32>>()?;
+
+            if let Some(offset) = axis_index_map_offset {
+                axis_index_map = Some(DeltaSetIndexMap::new(data.get(offset.to_usize()..)?));
+            }
+
+            if let Some(offset) = variation_store_offset {
+                let s = Stream::new_at(data, offset.to_usize())?;
+                variation_store = Some(ItemVariationStore::parse(s)?);
+            }
+        }
+
         Some(Self {
-            segment_maps: SegmentMaps {
-                // TODO: check that `axisCount` is the same as in `fvar`?
-                count: s.read::<u16>()?,
-                data: s.tail()?,
-            },
+            segment_maps,
+            axis_index_map,
+            variation_store,
         })
     }
 
     /// Maps coordinates.
+    ///
+    /// Coordinates must be normalized using the default normalization
+    /// and must not be already mapped.
     pub fn map_coordinates(&self, coordinates: &mut [NormalizedCoordinate]) -> Option<()> {
         if usize::from(self.segment_maps.count) != coordinates.len() {
             return None;
         }
 
-        for (map, coord) in self.segment_maps.into_iter().zip(coordinates) {
+        for (map, coord) in self.segment_maps.into_iter().zip(coordinates.iter_mut()) {
             *coord = NormalizedCoordinate::from(map_value(&map, coord.0)?);
         }
 
+        if let Some(variation_store) = self.variation_store {
+            if coordinates.len() > MAX_COORDS {
+                return None;
+            }
+
+            // Deltas must be calculated using coordinates mapped by the segment maps only.
+            let mut mapped = [NormalizedCoordinate::default(); MAX_COORDS];
+            mapped[..coordinates.len()].copy_from_slice(coordinates);
+            let mapped = &mapped[..coordinates.len()];
+
+            for (i, coord) in coordinates.iter_mut().enumerate() {
+                let (outer, inner) = match self.axis_index_map {
+                    Some(map) => map.map(i as u32)?,
+                    None => (0, i as u16),
+                };
+
+                let delta = variation_store
+                    .parse_delta(outer, inner, mapped)
+                    .unwrap_or(0.0);
+                // `NormalizedCoordinate::from` clamps the value.
+                let v = i32::from(coord.0) + round(delta);
+                *coord = NormalizedCoordinate::from(v.clamp(-16384, 16384) as i16);
+            }
+        }
+
         Some(())
     }
 }
 
+// `f32::round` is not available in `core`.
+fn round(v: f32) -> i32 {
+    if v >= 0.0 {
+        (v + 0.5) as i32
+    } else {
+        (v - 0.5) as i32
+    }
+}
+
 fn map_value(map: &LazyArray16<AxisValueMap>, value: i16) -> Option<i16> {
     // This code is based on harfbuzz implementation.
 
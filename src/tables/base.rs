@@ -0,0 +1,397 @@
+//! A [Baseline Table](https://docs.microsoft.com/en-us/typography/opentype/spec/base) implementation.
+
+use crate::gpos::Device;
+use crate::opentype_layout::{RecordList, RecordListItem};
+use crate::parser::{
+    FromData, FromSlice, LazyArray16, LazyOffsetArray16, Offset, Offset16, Stream,
+};
+use crate::{GlyphId, Tag};
+
+#[cfg(feature = "variable-fonts")]
+use crate::parser::Offset32;
+#[cfg(feature = "variable-fonts")]
+use crate::var_store::ItemVariationStore;
+#[cfg(feature = "variable-fonts")]
+use crate::NormalizedCoordinate;
+
+/// A [Base Coordinate Table](https://docs.microsoft.com/en-us/typography/opentype/spec/base#base-coordinate-tables).
+///
+/// Use [`Face::base_coordinate`](../struct.Face.html#method.base_coordinate) to resolve
+/// the actual coordinate value.
+#[derive(Clone, Copy, Debug)]
+pub struct BaseCoord<'a> {
+    /// X or Y value, in design units, depending on the axis.
+    pub coordinate: i16,
+    /// A glyph which contour point defines the coordinate.
+    ///
+    /// Set only for the *BaseCoord Format 2*.
+    pub reference_glyph: Option<GlyphId>,
+    /// An index to a contour point of the `reference_glyph`.
+    ///
+    /// Set only for the *BaseCoord Format 2*.
+    pub contour_point: Option<u16>,
+    /// A [`Device`] table with coordinate adjustment.
+    ///
+    /// Set only for the *BaseCoord Format 3*.
+    pub device: Option<Device<'a>>,
+}
+
+impl<'a> FromSlice<'a> for BaseCoord<'a> {
+    fn parse(data: &'a [u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+        let format = s.read::<u16>()?;
+        let mut coord = BaseCoord {
+            coordinate: s.read::<i16>()?,
+            reference_glyph: None,
+            contour_point: None,
+            device: None,
+        };
+
+        match format {
+            1 => {}
+            2 => {
+                coord.reference_glyph = Some(s.read::<GlyphId>()?);
+                coord.contour_point = Some(s.read::<u16>()?);
+            }
+            3 => {
+                coord.device = s
+                    .read::<Option<Offset16>>()?
+                    .and_then(|offset| data.get(offset.to_usize()..))
+                    .and_then(Device::parse);
+            }
+            _ => return None,
+        }
+
+        Some(coord)
+    }
+}
+
+/// A [Base Values Table](https://docs.microsoft.com/en-us/typography/opentype/spec/base#basevalues-table).
+#[derive(Clone, Copy, Debug)]
+pub struct BaseValues<'a> {
+    /// An index of the default baseline in the axis baseline tags list.
+    pub default_baseline_index: u16,
+    /// Baseline coordinates in the same order as the axis baseline tags list.
+    pub coordinates: LazyOffsetArray16<'a, BaseCoord<'a>>,
+}
+
+impl<'a> FromSlice<'a> for BaseValues<'a> {
+    fn parse(data: &'a [u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+        let default_baseline_index = s.read::<u16>()?;
+        let count = s.read::<u16>()?;
+        let offsets = s.read_array16::<Option<Offset16>>(count)?;
+        Some(BaseValues {
+            default_baseline_index,
+            coordinates: LazyOffsetArray16::new(data, offsets),
+        })
+    }
+}
+
+#[derive(Clone, Copy)]
+struct FeatureMinMaxRecord {
+    tag: Tag,
+    min_coord_offset: Option<Offset16>,
+    max_coord_offset: Option<Offset16>,
+}
+
+impl FromData for FeatureMinMaxRecord {
+    const SIZE: usize = 8;
+
+    #[inline]
+    fn parse(data: &[u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+        Some(FeatureMinMaxRecord {
+            tag: s.read::<Tag>()?,
+            min_coord_offset: s.read::<Option<Offset16>>()?,
+            max_coord_offset: s.read::<Option<Offset16>>()?,
+        })
+    }
+}
+
+/// Feature-specific min/max extent values.
+#[derive(Clone, Copy, Debug)]
+pub struct FeatureMinMax<'a> {
+    /// A feature tag.
+    pub tag: Tag,
+    /// A minimum extent value.
+    pub min: Option<BaseCoord<'a>>,
+    /// A maximum extent value.
+    pub max: Option<BaseCoord<'a>>,
+}
+
+/// A list of [`FeatureMinMax`] values.
+#[derive(Clone, Copy)]
+pub struct FeatureMinMaxList<'a> {
+    data: &'a [u8],
+    records: LazyArray16<'a, FeatureMinMaxRecord>,
+}
+
+impl<'a> FeatureMinMaxList<'a> {
+    /// Returns a [`FeatureMinMax`] at index.
+    pub fn get(&self, index: u16) -> Option<FeatureMinMax<'a>> {
+        let record = self.records.get(index)?;
+        Some(FeatureMinMax {
+            tag: record.tag,
+            min: parse_coord(self.data, record.min_coord_offset),
+            max: parse_coord(self.data, record.max_coord_offset),
+        })
+    }
+
+    /// Returns a [`FeatureMinMax`] by a feature tag.
+    pub fn find(&self, tag: Tag) -> Option<FeatureMinMax<'a>> {
+        let index = self.records.into_iter().position(|r| r.tag == tag)?;
+        self.get(index as u16)
+    }
+
+    /// Returns the number of items.
+    pub fn len(&self) -> u16 {
+        self.records.len()
+    }
+
+    /// Checks if there are any items.
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+}
+
+impl core::fmt::Debug for FeatureMinMaxList<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_list().entries(*self).finish()
+    }
+}
+
+impl<'a> IntoIterator for FeatureMinMaxList<'a> {
+    type Item = FeatureMinMax<'a>;
+    type IntoIter = FeatureMinMaxListIter<'a>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        FeatureMinMaxListIter {
+            list: self,
+            index: 0,
+        }
+    }
+}
+
+/// An iterator over [`FeatureMinMaxList`].
+#[allow(missing_debug_implementations)]
+pub struct FeatureMinMaxListIter<'a> {
+    list: FeatureMinMaxList<'a>,
+    index: u16,
+}
+
+impl<'a> Iterator for FeatureMinMaxListIter<'a> {
+    type Item = FeatureMinMax<'a>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index < self.list.len() {
+            self.index += 1;
+            self.list.get(self.index - 1)
+        } else {
+            None
+        }
+    }
+}
+
+/// A [MinMax Table](https://docs.microsoft.com/en-us/typography/opentype/spec/base#minmax-table).
+#[derive(Clone, Copy, Debug)]
+pub struct MinMax<'a> {
+    /// Language tag.
+    ///
+    /// Set to `dflt` for the script's default min/max values.
+    pub tag: Tag,
+    /// A minimum extent value.
+    pub min: Option<BaseCoord<'a>>,
+    /// A maximum extent value.
+    pub max: Option<BaseCoord<'a>>,
+    /// Feature-specific min/max extent values.
+    pub features: FeatureMinMaxList<'a>,
+}
+
+impl<'a> RecordListItem<'a> for MinMax<'a> {
+    fn parse(tag: Tag, data: &'a [u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+        let min = parse_coord(data, s.read::<Option<Offset16>>()?);
+        let max = parse_coord(data, s.read::<Option<Offset16>>()?);
+        let count = s.read::<u16>()?;
+        let records = s.read_array16::<FeatureMinMaxRecord>(count)?;
+        Some(MinMax {
+            tag,
+            min,
+            max,
+            features: FeatureMinMaxList { data, records },
+        })
+    }
+}
+
+/// A list of language-specific [`MinMax`] records.
+pub type MinMaxList<'a> = RecordList<'a, MinMax<'a>>;
+
+/// A [Base Script Table](https://docs.microsoft.com/en-us/typography/opentype/spec/base#basescript-table).
+#[derive(Clone, Copy, Debug)]
+pub struct BaseScript<'a> {
+    /// Script tag.
+    pub tag: Tag,
+    /// Script's baseline values.
+    pub values: Option<BaseValues<'a>>,
+    /// Default min/max extent values.
+    pub default_min_max: Option<MinMax<'a>>,
+    /// Language-specific min/max extent values. Listed alphabetically.
+    pub languages: MinMaxList<'a>,
+}
+
+impl<'a> RecordListItem<'a> for BaseScript<'a> {
+    fn parse(tag: Tag, data: &'a [u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+        let values = s
+            .read::<Option<Offset16>>()?
+            .and_then(|offset| data.get(offset.to_usize()..))
+            .and_then(BaseValues::parse);
+        let default_min_max = s
+            .read::<Option<Offset16>>()?
+            .and_then(|offset| data.get(offset.to_usize()..))
+            .and_then(|data| MinMax::parse(Tag::from_bytes(b"dflt"), data));
+        let mut languages = RecordList::parse(s.tail()?)?;
+        // Offsets are relative to this table.
+        languages.data = data;
+        Some(BaseScript {
+            tag,
+            values,
+            default_min_max,
+            languages,
+        })
+    }
+}
+
+/// A list of [`BaseScript`] records.
+pub type BaseScriptList<'a> = RecordList<'a, BaseScript<'a>>;
+
+/// An [Axis Table](https://docs.microsoft.com/en-us/typography/opentype/spec/base#axis-tables-horizaxis-and-vertaxis).
+#[derive(Clone, Copy, Debug)]
+pub struct Axis<'a> {
+    /// Baseline tags. Listed alphabetically.
+    ///
+    /// [`BaseValues`] coordinates are stored in the same order.
+    pub baseline_tags: LazyArray16<'a, Tag>,
+    /// Per-script baseline data. Listed alphabetically.
+    pub scripts: BaseScriptList<'a>,
+}
+
+impl<'a> Axis<'a> {
+    fn parse(data: &'a [u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+        let baseline_tags = match s.read::<Option<Offset16>>()? {
+            Some(offset) => {
+                let mut s = Stream::new_at(data, offset.to_usize())?;
+                let count = s.read::<u16>()?;
+                s.read_array16::<Tag>(count)?
+            }
+            None => LazyArray16::default(),
+        };
+        let scripts = RecordList::parse(s.read_at_offset16(data)?)?;
+        Some(Axis {
+            baseline_tags,
+            scripts,
+        })
+    }
+
+    /// Returns a baseline coordinate for a script.
+    ///
+    /// Falls back to the `DFLT` script when the specified one is not present.
+    pub fn baseline(&self, script: Tag, baseline: Tag) -> Option<BaseCoord<'a>> {
+        let index = self
+            .baseline_tags
+            .into_iter()
+            .position(|tag| tag == baseline)?;
+        let script = self
+            .scripts
+            .find(script)
+            .or_else(|| self.scripts.find(Tag::from_bytes(b"DFLT")))?;
+        script.values?.coordinates.get(index as u16)
+    }
+}
+
+/// A [Baseline Table](https://docs.microsoft.com/en-us/typography/opentype/spec/base).
+#[derive(Clone, Copy)]
+pub struct Table<'a> {
+    /// Baseline data for horizontal text layout.
+    pub horizontal: Option<Axis<'a>>,
+    /// Baseline data for vertical text layout.
+    pub vertical: Option<Axis<'a>>,
+    #[cfg(feature = "variable-fonts")]
+    variation_store: Option<ItemVariationStore<'a>>,
+}
+
+impl<'a> Table<'a> {
+    /// Parses a table from raw data.
+    pub fn parse(data: &'a [u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+        let major_version = s.read::<u16>()?;
+        let minor_version = s.read::<u16>()?;
+        if major_version != 1 {
+            return None;
+        }
+
+        let horizontal = s
+            .read::<Option<Offset16>>()?
+            .and_then(|offset| data.get(offset.to_usize()..))
+            .and_then(Axis::parse);
+        let vertical = s
+            .read::<Option<Offset16>>()?
+            .and_then(|offset| data.get(offset.to_usize()..))
+            .and_then(Axis::parse);
+
+        #[cfg(feature = "variable-fonts")]
+        {
+            let mut variation_store = None;
+            if minor_version >= 1 {
+                variation_store = s
+                    .read::<Option<Offset32>>()?
+                    .and_then(|offset| data.get(offset.to_usize()..))
+                    .and_then(|data| ItemVariationStore::parse(Stream::new(data)));
+            }
+
+            Some(Table {
+                horizontal,
+                vertical,
+                variation_store,
+            })
+        }
+
+        #[cfg(not(feature = "variable-fonts"))]
+        {
+            let _ = minor_version;
+            Some(Table {
+                horizontal,
+                vertical,
+            })
+        }
+    }
+
+    /// Returns a variation delta at a specified index according to
+    /// [Item Variation Store Table](
+    /// https://docs.microsoft.com/en-us/typography/opentype/spec/base#item-variation-store-table).
+    #[cfg(feature = "variable-fonts")]
+    #[inline]
+    pub fn variation_delta(
+        &self,
+        outer_index: u16,
+        inner_index: u16,
+        coordinates: &[NormalizedCoordinate],
+    ) -> Option<f32> {
+        self.variation_store
+            .and_then(|store| store.parse_delta(outer_index, inner_index, coordinates))
+    }
+}
+
+impl core::fmt::Debug for Table<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "Table {{ ... }}")
+    }
+}
+
+fn parse_coord(data: &[u8], offset: Option<Offset16>) -> Option<BaseCoord<'_>> {
+    data.get(offset?.to_usize()..).and_then(BaseCoord::parse)
+}
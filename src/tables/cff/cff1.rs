@@ -12,16 +12,21 @@ use core::ops::Range;
 
 use super::argstack::ArgumentsStack;
 use super::charset::{parse_charset, Charset};
-use super::charstring::CharStringParser;
-use super::dict::DictionaryParser;
+use super::charstring::{parse_operand, CharStringParser};
+use super::dict::{is_dict_one_byte_op, parse_number, DictionaryParser};
 use super::encoding::{parse_encoding, Encoding, STANDARD_ENCODING};
 use super::index::{parse_index, skip_index, Index};
 #[cfg(feature = "glyph-names")]
 use super::std_names::STANDARD_NAMES;
-use super::{calc_subroutine_bias, conv_subroutine_index, Builder, CFFError, IsEven, StringId};
+use super::{
+    calc_subroutine_bias, conv_subroutine_index, operator, Builder, CFFError, CFFHintsBuilder,
+    IgnoreHints, IsEven, StringId,
+};
 use crate::parser::{LazyArray16, NumFrom, Stream, TryNumFrom};
 use crate::{DummyOutline, GlyphId, OutlineBuilder, Rect, RectF};
 
+pub use super::decompiler::{Operator, Token, Tokens};
+
 // Limits according to the Adobe Technical Note #5176, chapter 4 DICT Data.
 const MAX_OPERANDS_LEN: usize = 48;
 
@@ -31,42 +36,17 @@ const MAX_ARGUMENTS_STACK_LEN: usize = 48;
 
 const TWO_BYTE_OPERATOR_MARK: u8 = 12;
 
-/// Enumerates some operators defined in the Adobe Technical Note #5177.
-mod operator {
-    pub const HORIZONTAL_STEM: u8 = 1;
-    pub const VERTICAL_STEM: u8 = 3;
-    pub const VERTICAL_MOVE_TO: u8 = 4;
-    pub const LINE_TO: u8 = 5;
-    pub const HORIZONTAL_LINE_TO: u8 = 6;
-    pub const VERTICAL_LINE_TO: u8 = 7;
-    pub const CURVE_TO: u8 = 8;
-    pub const CALL_LOCAL_SUBROUTINE: u8 = 10;
-    pub const RETURN: u8 = 11;
-    pub const ENDCHAR: u8 = 14;
-    pub const HORIZONTAL_STEM_HINT_MASK: u8 = 18;
-    pub const HINT_MASK: u8 = 19;
-    pub const COUNTER_MASK: u8 = 20;
-    pub const MOVE_TO: u8 = 21;
-    pub const HORIZONTAL_MOVE_TO: u8 = 22;
-    pub const VERTICAL_STEM_HINT_MASK: u8 = 23;
-    pub const CURVE_LINE: u8 = 24;
-    pub const LINE_CURVE: u8 = 25;
-    pub const VV_CURVE_TO: u8 = 26;
-    pub const HH_CURVE_TO: u8 = 27;
-    pub const SHORT_INT: u8 = 28;
-    pub const CALL_GLOBAL_SUBROUTINE: u8 = 29;
-    pub const VH_CURVE_TO: u8 = 30;
-    pub const HV_CURVE_TO: u8 = 31;
-    pub const HFLEX: u8 = 34;
-    pub const FLEX: u8 = 35;
-    pub const HFLEX1: u8 = 36;
-    pub const FLEX1: u8 = 37;
-    pub const FIXED_16_16: u8 = 255;
-}
+// The number of standard strings, according to the Adobe Technical Note #5176, Appendix A.
+const STANDARD_STRINGS_LEN: usize = 391;
 
 /// Enumerates some operators defined in the Adobe Technical Note #5176,
 /// Table 9 Top DICT Operator Entries
 mod top_dict_operator {
+    pub const VERSION: u16 = 0;
+    pub const NOTICE: u16 = 1;
+    pub const FULL_NAME: u16 = 2;
+    pub const FAMILY_NAME: u16 = 3;
+    pub const WEIGHT: u16 = 4;
     pub const CHARSET_OFFSET: u16 = 15;
     pub const ENCODING_OFFSET: u16 = 16;
     pub const CHAR_STRINGS_OFFSET: u16 = 17;
@@ -75,14 +55,26 @@ mod top_dict_operator {
     pub const ROS: u16 = 1230;
     pub const FD_ARRAY: u16 = 1236;
     pub const FD_SELECT: u16 = 1237;
+    pub const FONT_NAME: u16 = 1238;
 }
 
 /// Enumerates some operators defined in the Adobe Technical Note #5176,
 /// Table 23 Private DICT Operators
 mod private_dict_operator {
+    pub const BLUE_VALUES: u16 = 6;
+    pub const OTHER_BLUES: u16 = 7;
+    pub const FAMILY_BLUES: u16 = 8;
+    pub const FAMILY_OTHER_BLUES: u16 = 9;
+    pub const STD_HW: u16 = 10;
+    pub const STD_VW: u16 = 11;
     pub const LOCAL_SUBROUTINES_OFFSET: u16 = 19;
     pub const DEFAULT_WIDTH: u16 = 20;
     pub const NOMINAL_WIDTH: u16 = 21;
+    pub const BLUE_SCALE: u16 = 1209;
+    pub const BLUE_SHIFT: u16 = 1210;
+    pub const BLUE_FUZZ: u16 = 1211;
+    pub const STEM_SNAP_H: u16 = 1212;
+    pub const STEM_SNAP_V: u16 = 1213;
 }
 
 /// Enumerates Charset IDs defined in the Adobe Technical Note #5176, Table 22
@@ -112,6 +104,7 @@ pub(crate) struct SIDMetadata<'a> {
     /// Can be zero.
     nominal_width: f32,
     encoding: Encoding<'a>,
+    private_dict: Option<&'a [u8]>,
 }
 
 #[derive(Clone, Copy, Default, Debug)]
@@ -145,8 +138,31 @@ impl Default for Matrix {
     }
 }
 
+/// CIDFont Registry, Ordering and Supplement.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct CIDSystemInfo<'a> {
+    /// Character collection registry, like `Adobe`.
+    pub registry: &'a str,
+    /// Character collection name, like `Japan1`.
+    pub ordering: &'a str,
+    /// Character collection version.
+    pub supplement: i32,
+}
+
+/// Top DICT strings.
+#[derive(Clone, Copy, Default, Debug)]
+struct TopDictStrings {
+    version: Option<StringId>,
+    notice: Option<StringId>,
+    full_name: Option<StringId>,
+    family_name: Option<StringId>,
+    weight: Option<StringId>,
+    ros: Option<(StringId, StringId, i32)>,
+}
+
 #[derive(Default)]
 struct TopDict {
+    strings: TopDictStrings,
     charset_offset: Option<usize>,
     encoding_offset: Option<usize>,
     char_strings_offset: usize,
@@ -169,6 +185,21 @@ fn parse_top_dict(s: &mut Stream) -> Option<TopDict> {
     let mut dict_parser = DictionaryParser::new(data, &mut operands_buffer);
     while let Some(operator) = dict_parser.parse_next() {
         match operator.get() {
+            top_dict_operator::VERSION => {
+                top_dict.strings.version = parse_string_id(&mut dict_parser);
+            }
+            top_dict_operator::NOTICE => {
+                top_dict.strings.notice = parse_string_id(&mut dict_parser);
+            }
+            top_dict_operator::FULL_NAME => {
+                top_dict.strings.full_name = parse_string_id(&mut dict_parser);
+            }
+            top_dict_operator::FAMILY_NAME => {
+                top_dict.strings.family_name = parse_string_id(&mut dict_parser);
+            }
+            top_dict_operator::WEIGHT => {
+                top_dict.strings.weight = parse_string_id(&mut dict_parser);
+            }
             top_dict_operator::CHARSET_OFFSET => {
                 top_dict.charset_offset = dict_parser.parse_offset();
             }
@@ -197,6 +228,18 @@ fn parse_top_dict(s: &mut Stream) -> Option<TopDict> {
             }
             top_dict_operator::ROS => {
                 top_dict.has_ros = true;
+
+                dict_parser.parse_operands()?;
+                let operands = dict_parser.operands();
+                if operands.len() == 3 {
+                    let registry = u16::try_from(operands[0] as i32).ok();
+                    let ordering = u16::try_from(operands[1] as i32).ok();
+                    if let (Some(registry), Some(ordering)) = (registry, ordering) {
+                        let supplement = operands[2] as i32;
+                        top_dict.strings.ros =
+                            Some((StringId(registry), StringId(ordering), supplement));
+                    }
+                }
             }
             top_dict_operator::FD_ARRAY => {
                 top_dict.fd_array_offset = dict_parser.parse_offset();
@@ -211,6 +254,22 @@ fn parse_top_dict(s: &mut Stream) -> Option<TopDict> {
     Some(top_dict)
 }
 
+fn parse_string_id(dict_parser: &mut DictionaryParser) -> Option<StringId> {
+    let n = dict_parser.parse_number()?;
+    u16::try_from(n as i32).ok().map(StringId)
+}
+
+#[cfg(feature = "glyph-names")]
+fn standard_string(sid: usize) -> Option<&'static str> {
+    debug_assert_eq!(STANDARD_NAMES.len(), STANDARD_STRINGS_LEN);
+    STANDARD_NAMES.get(sid).copied()
+}
+
+#[cfg(not(feature = "glyph-names"))]
+fn standard_string(_: usize) -> Option<&'static str> {
+    None
+}
+
 // TODO: move to integration
 #[cfg(test)]
 mod tests {
@@ -304,16 +363,153 @@ fn parse_private_dict(data: &[u8]) -> PrivateDict {
     dict
 }
 
-fn parse_font_dict(data: &[u8]) -> Option<Range<usize>> {
+/// An iterator over a delta-encoded Private DICT array.
+///
+/// Yields absolute values.
+#[derive(Clone, Copy, Default, Debug)]
+pub struct DeltaArray<'a> {
+    data: &'a [u8],
+    offset: usize,
+    value: f32,
+}
+
+impl<'a> DeltaArray<'a> {
+    fn new(data: &'a [u8], offset: usize) -> Self {
+        DeltaArray {
+            data,
+            offset,
+            value: 0.0,
+        }
+    }
+}
+
+impl Iterator for DeltaArray<'_> {
+    type Item = f32;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut s = Stream::new_at(self.data, self.offset)?;
+        let b = s.read::<u8>()?;
+        // Operands are followed by an operator.
+        if is_dict_one_byte_op(b) {
+            return None;
+        }
+
+        let n = parse_number(b, &mut s)?;
+        self.offset = s.offset();
+        self.value += n as f32;
+        Some(self.value)
+    }
+}
+
+/// Private DICT hinting values.
+///
+/// Arrays are stored as zones or stem widths, in font units.
+#[derive(Clone, Copy, Debug)]
+pub struct PrivateDictHints<'a> {
+    /// Alignment zones pairs, starting with the baseline overshoot zone.
+    pub blue_values: DeltaArray<'a>,
+    /// Additional descender alignment zones pairs.
+    pub other_blues: DeltaArray<'a>,
+    /// Family-wide `blue_values`.
+    pub family_blues: DeltaArray<'a>,
+    /// Family-wide `other_blues`.
+    pub family_other_blues: DeltaArray<'a>,
+    /// Dominant horizontal stem height.
+    pub std_hw: Option<f32>,
+    /// Dominant vertical stem width.
+    ///
+    /// Also known as `StemV` in PDF font descriptors.
+    pub std_vw: Option<f32>,
+    /// Common horizontal stem heights.
+    pub stem_snap_h: DeltaArray<'a>,
+    /// Common vertical stem widths.
+    pub stem_snap_v: DeltaArray<'a>,
+    /// Overshoot suppression scale. 0.039625 by default.
+    pub blue_scale: f32,
+    /// Overshoot enforcement threshold. 7 by default.
+    pub blue_shift: f32,
+    /// Alignment zones extension. 1 by default.
+    pub blue_fuzz: f32,
+}
+
+impl Default for PrivateDictHints<'_> {
+    fn default() -> Self {
+        PrivateDictHints {
+            blue_values: DeltaArray::default(),
+            other_blues: DeltaArray::default(),
+            family_blues: DeltaArray::default(),
+            family_other_blues: DeltaArray::default(),
+            std_hw: None,
+            std_vw: None,
+            stem_snap_h: DeltaArray::default(),
+            stem_snap_v: DeltaArray::default(),
+            blue_scale: 0.039625,
+            blue_shift: 7.0,
+            blue_fuzz: 1.0,
+        }
+    }
+}
+
+fn parse_private_dict_hints(data: &[u8]) -> PrivateDictHints<'_> {
+    let mut hints = PrivateDictHints::default();
     let mut operands_buffer = [0.0; MAX_OPERANDS_LEN];
     let mut dict_parser = DictionaryParser::new(data, &mut operands_buffer);
     while let Some(operator) = dict_parser.parse_next() {
-        if operator.get() == top_dict_operator::PRIVATE_DICT_SIZE_AND_OFFSET {
-            return dict_parser.parse_range();
+        let delta_array = DeltaArray::new(data, dict_parser.operands_offset());
+        match operator.get() {
+            private_dict_operator::BLUE_VALUES => hints.blue_values = delta_array,
+            private_dict_operator::OTHER_BLUES => hints.other_blues = delta_array,
+            private_dict_operator::FAMILY_BLUES => hints.family_blues = delta_array,
+            private_dict_operator::FAMILY_OTHER_BLUES => hints.family_other_blues = delta_array,
+            private_dict_operator::STEM_SNAP_H => hints.stem_snap_h = delta_array,
+            private_dict_operator::STEM_SNAP_V => hints.stem_snap_v = delta_array,
+            private_dict_operator::STD_HW => {
+                hints.std_hw = dict_parser.parse_number().map(|n| n as f32);
+            }
+            private_dict_operator::STD_VW => {
+                hints.std_vw = dict_parser.parse_number().map(|n| n as f32);
+            }
+            private_dict_operator::BLUE_SCALE => {
+                if let Some(n) = dict_parser.parse_number() {
+                    hints.blue_scale = n as f32;
+                }
+            }
+            private_dict_operator::BLUE_SHIFT => {
+                if let Some(n) = dict_parser.parse_number() {
+                    hints.blue_shift = n as f32;
+                }
+            }
+            private_dict_operator::BLUE_FUZZ => {
+                if let Some(n) = dict_parser.parse_number() {
+                    hints.blue_fuzz = n as f32;
+                }
+            }
+            _ => {}
         }
     }
 
-    None
+    hints
+}
+
+#[derive(Default)]
+struct FontDict {
+    font_name: Option<StringId>,
+    private_dict_range: Option<Range<usize>>,
+}
+
+fn parse_font_dict(data: &[u8]) -> FontDict {
+    let mut dict = FontDict::default();
+    let mut operands_buffer = [0.0; MAX_OPERANDS_LEN];
+    let mut dict_parser = DictionaryParser::new(data, &mut operands_buffer);
+    while let Some(operator) = dict_parser.parse_next() {
+        if operator.get() == top_dict_operator::FONT_NAME {
+            dict.font_name = parse_string_id(&mut dict_parser);
+        } else if operator.get() == top_dict_operator::PRIVATE_DICT_SIZE_AND_OFFSET {
+            dict.private_dict_range = dict_parser.parse_range();
+        }
+    }
+
+    dict
 }
 
 /// In CID fonts, to get local subroutines we have to:
@@ -329,7 +525,7 @@ fn parse_cid_local_subrs<'a>(
 ) -> Option<Index<'a>> {
     let font_dict_index = cid.fd_select.font_dict_index(glyph_id)?;
     let font_dict_data = cid.fd_array.get(u32::from(font_dict_index))?;
-    let private_dict_range = parse_font_dict(font_dict_data)?;
+    let private_dict_range = parse_font_dict(font_dict_data).private_dict_range?;
     let private_dict_data = data.get(private_dict_range.clone())?;
     let private_dict = parse_private_dict(private_dict_data);
     let subroutines_offset = private_dict.local_subroutines_offset?;
@@ -357,7 +553,7 @@ fn parse_char_string(
     metadata: &Table,
     glyph_id: GlyphId,
     width_only: bool,
-    builder: &mut dyn OutlineBuilder,
+    builder: &mut dyn CFFHintsBuilder,
 ) -> Result<(Rect, Option<f32>), CFFError> {
     let local_subrs = match metadata.kind {
         FontKind::SID(ref sid) => Some(sid.local_subrs),
@@ -438,16 +634,23 @@ fn _parse_char_string(
                 // x dx {dxa dxb}* vstemhm
 
                 // If the stack length is uneven, than the first value is a `width`.
-                let len = if p.stack.len().is_odd() && ctx.width.is_none() {
+                let start = if p.stack.len().is_odd() && ctx.width.is_none() {
                     ctx.width = Some(p.stack.at(0));
-                    p.stack.len() - 1
+                    1
                 } else {
-                    p.stack.len()
+                    0
                 };
 
+                let len = p.stack.len() - start;
                 ctx.stems_len += len as u32 >> 1;
 
-                // We are ignoring the hint operators.
+                let is_horizontal = matches!(
+                    op,
+                    operator::HORIZONTAL_STEM | operator::HORIZONTAL_STEM_HINT_MASK
+                );
+                p.builder
+                    .stems(&p.stack.data[start..p.stack.len()], is_horizontal);
+
                 p.stack.clear();
             }
             operator::VERTICAL_MOVE_TO => {
@@ -578,22 +781,28 @@ fn _parse_char_string(
                 break;
             }
             operator::HINT_MASK | operator::COUNTER_MASK => {
-                let mut len = p.stack.len();
-
-                // We are ignoring the hint operators.
-                p.stack.clear();
-
                 // If the stack length is uneven, than the first value is a `width`.
-                if len.is_odd() {
-                    len -= 1;
+                let start = if p.stack.len().is_odd() {
                     if ctx.width.is_none() {
                         ctx.width = Some(p.stack.at(0));
                     }
-                }
 
+                    1
+                } else {
+                    0
+                };
+
+                // Values left on the stack are implicit `vstem` hints.
+                let len = p.stack.len() - start;
                 ctx.stems_len += len as u32 >> 1;
+                p.builder.stems(&p.stack.data[start..p.stack.len()], false);
+                p.stack.clear();
 
-                s.advance(usize::num_from((ctx.stems_len + 7) >> 3));
+                let mask_len = usize::num_from((ctx.stems_len + 7) >> 3);
+                match s.read_bytes(mask_len) {
+                    Some(mask) => p.builder.mask(mask, op == operator::COUNTER_MASK),
+                    None => s.advance(mask_len),
+                }
             }
             operator::MOVE_TO => {
                 let mut i = 0;
@@ -629,10 +838,6 @@ fn _parse_char_string(
             operator::HH_CURVE_TO => {
                 p.parse_hh_curve_to()?;
             }
-            operator::SHORT_INT => {
-                let n = s.read::<i16>().ok_or(CFFError::ReadOutOfBounds)?;
-                p.stack.push(f32::from(n))?;
-            }
             operator::CALL_GLOBAL_SUBROUTINE => {
                 if p.stack.is_empty() {
                     return Err(CFFError::InvalidArgumentsStackLength);
@@ -665,17 +870,8 @@ fn _parse_char_string(
             operator::HV_CURVE_TO => {
                 p.parse_hv_curve_to()?;
             }
-            32..=246 => {
-                p.parse_int1(op)?;
-            }
-            247..=250 => {
-                p.parse_int2(op, &mut s)?;
-            }
-            251..=254 => {
-                p.parse_int3(op, &mut s)?;
-            }
-            operator::FIXED_16_16 => {
-                p.parse_fixed(&mut s)?;
+            operator::SHORT_INT | 32..=255 => {
+                p.stack.push(parse_operand(op, &mut s)?)?;
             }
         }
 
@@ -775,7 +971,9 @@ fn parse_sid_metadata<'a>(
     metadata.encoding = encoding;
 
     let private_dict = if let Some(range) = top_dict.private_dict_range.clone() {
-        parse_private_dict(data.get(range)?)
+        let private_dict_data = data.get(range)?;
+        metadata.private_dict = Some(private_dict_data);
+        parse_private_dict(private_dict_data)
     } else {
         return Some(FontKind::SID(metadata));
     };
@@ -838,8 +1036,8 @@ pub struct Table<'a> {
     // Used to resolve a local subroutine in a CID font.
     table_data: &'a [u8],
 
-    #[allow(dead_code)]
     strings: Index<'a>,
+    top_dict_strings: TopDictStrings,
     global_subrs: Index<'a>,
     charset: Charset<'a>,
     number_of_glyphs: NonZeroU16,
@@ -906,6 +1104,7 @@ impl<'a> Table<'a> {
         };
 
         let matrix = top_dict.matrix;
+        let top_dict_strings = top_dict.strings;
 
         let kind = if top_dict.has_ros {
             parse_cid_metadata(data, top_dict, number_of_glyphs.get())?
@@ -924,6 +1123,7 @@ impl<'a> Table<'a> {
         Some(Self {
             table_data: data,
             strings,
+            top_dict_strings,
             global_subrs,
             charset,
             number_of_glyphs,
@@ -947,11 +1147,138 @@ impl<'a> Table<'a> {
         self.matrix
     }
 
+    /// Returns the Top DICT `version` string.
+    ///
+    /// Returns `None` for standard strings, like `Regular`,
+    /// when the `glyph-names` feature is disabled.
+    #[inline]
+    pub fn version(&self) -> Option<&'a str> {
+        self.string(self.top_dict_strings.version?)
+    }
+
+    /// Returns the Top DICT `Notice` string.
+    ///
+    /// Returns `None` for standard strings, like `Regular`,
+    /// when the `glyph-names` feature is disabled.
+    #[inline]
+    pub fn notice(&self) -> Option<&'a str> {
+        self.string(self.top_dict_strings.notice?)
+    }
+
+    /// Returns the Top DICT `FullName` string.
+    ///
+    /// Returns `None` for standard strings, like `Regular`,
+    /// when the `glyph-names` feature is disabled.
+    #[inline]
+    pub fn full_name(&self) -> Option<&'a str> {
+        self.string(self.top_dict_strings.full_name?)
+    }
+
+    /// Returns the Top DICT `FamilyName` string.
+    ///
+    /// Returns `None` for standard strings, like `Regular`,
+    /// when the `glyph-names` feature is disabled.
+    #[inline]
+    pub fn family_name(&self) -> Option<&'a str> {
+        self.string(self.top_dict_strings.family_name?)
+    }
+
+    /// Returns the Top DICT `Weight` string.
+    ///
+    /// Returns `None` for standard strings, like `Regular`,
+    /// when the `glyph-names` feature is disabled.
+    #[inline]
+    pub fn weight(&self) -> Option<&'a str> {
+        self.string(self.top_dict_strings.weight?)
+    }
+
+    /// Returns the CIDFont Registry, Ordering and Supplement.
+    ///
+    /// Returns `None` if this is not a CIDFont.
+    ///
+    /// Returns `None` for standard strings, like `Regular`,
+    /// when the `glyph-names` feature is disabled.
+    pub fn ros(&self) -> Option<CIDSystemInfo<'a>> {
+        let (registry, ordering, supplement) = self.top_dict_strings.ros?;
+        Some(CIDSystemInfo {
+            registry: self.string(registry)?,
+            ordering: self.string(ordering)?,
+            supplement,
+        })
+    }
+
+    /// Returns the number of Font DICTs.
+    ///
+    /// CIDFonts have a Font DICT per FDArray entry.
+    /// Other fonts have only the Top DICT.
+    pub fn number_of_font_dicts(&self) -> u16 {
+        match self.kind {
+            FontKind::SID(_) => 1,
+            FontKind::CID(ref cid) => u16::try_from(cid.fd_array.len()).unwrap_or(u16::MAX),
+        }
+    }
+
+    /// Returns Private DICT hinting values of a Font DICT.
+    ///
+    /// See [`number_of_font_dicts`](Self::number_of_font_dicts) for details.
+    pub fn private_dict_hints(&self, font_dict_index: u16) -> Option<PrivateDictHints<'a>> {
+        let data = match self.kind {
+            FontKind::SID(ref sid) if font_dict_index == 0 => sid.private_dict?,
+            FontKind::SID(_) => return None,
+            FontKind::CID(ref cid) => {
+                let font_dict_data = cid.fd_array.get(u32::from(font_dict_index))?;
+                let private_dict_range = parse_font_dict(font_dict_data).private_dict_range?;
+                self.table_data.get(private_dict_range)?
+            }
+        };
+
+        Some(parse_private_dict_hints(data))
+    }
+
+    /// Returns the Font DICT `FontName` string.
+    ///
+    /// Only CIDFonts have named Font DICTs.
+    ///
+    /// Returns `None` for standard strings, like `Regular`,
+    /// when the `glyph-names` feature is disabled.
+    pub fn font_dict_name(&self, font_dict_index: u16) -> Option<&'a str> {
+        match self.kind {
+            FontKind::SID(_) => None,
+            FontKind::CID(ref cid) => {
+                let font_dict_data = cid.fd_array.get(u32::from(font_dict_index))?;
+                self.string(parse_font_dict(font_dict_data).font_name?)
+            }
+        }
+    }
+
+    /// Returns the index of a Font DICT used by a glyph.
+    ///
+    /// Returns `None` if this is not a CIDFont.
+    pub fn glyph_font_dict_index(&self, glyph_id: GlyphId) -> Option<u16> {
+        match self.kind {
+            FontKind::SID(_) => None,
+            FontKind::CID(ref cid) => cid.fd_select.font_dict_index(glyph_id).map(u16::from),
+        }
+    }
+
     /// Outlines a glyph.
     pub fn outline(
         &self,
         glyph_id: GlyphId,
         builder: &mut dyn OutlineBuilder,
+    ) -> Result<Rect, CFFError> {
+        let data = self
+            .char_strings
+            .get(u32::from(glyph_id.0))
+            .ok_or(CFFError::NoGlyph)?;
+        parse_char_string(data, self, glyph_id, false, &mut IgnoreHints(builder)).map(|v| v.0)
+    }
+
+    /// Outlines a glyph and reports its stem hints and hint masks.
+    pub fn outline_with_hints(
+        &self,
+        glyph_id: GlyphId,
+        builder: &mut dyn CFFHintsBuilder,
     ) -> Result<Rect, CFFError> {
         let data = self
             .char_strings
@@ -960,6 +1287,25 @@ impl<'a> Table<'a> {
         parse_char_string(data, self, glyph_id, false, builder).map(|v| v.0)
     }
 
+    /// Decompiles a glyph charstring.
+    ///
+    /// When `flatten` is set, subroutine bodies are emitted in place of their calls.
+    pub fn char_string_tokens(&self, glyph_id: GlyphId, flatten: bool) -> Option<Tokens<'a>> {
+        let data = self.char_strings.get(u32::from(glyph_id.0))?;
+        let local_subrs = match self.kind {
+            FontKind::SID(ref sid) => Some(sid.local_subrs),
+            FontKind::CID(ref cid) => parse_cid_local_subrs(self.table_data, glyph_id, cid),
+        };
+
+        Some(Tokens::new_cff(
+            data,
+            self.global_subrs,
+            local_subrs,
+            MAX_ARGUMENTS_STACK_LEN,
+            flatten,
+        ))
+    }
+
     /// Resolves a Glyph ID for a code point.
     ///
     /// Similar to [`Face::glyph_index`](crate::Face::glyph_index) but 8bit
@@ -989,8 +1335,14 @@ impl<'a> Table<'a> {
         match self.kind {
             FontKind::SID(ref sid) => {
                 let data = self.char_strings.get(u32::from(glyph_id.0))?;
-                let (_, width) =
-                    parse_char_string(data, self, glyph_id, true, &mut DummyOutline).ok()?;
+                let (_, width) = parse_char_string(
+                    data,
+                    self,
+                    glyph_id,
+                    true,
+                    &mut IgnoreHints(&mut DummyOutline),
+                )
+                .ok()?;
                 let width = width
                     .map(|w| sid.nominal_width + w)
                     .unwrap_or(sid.default_width);
@@ -1025,18 +1377,7 @@ impl<'a> Table<'a> {
     #[cfg(feature = "glyph-names")]
     pub fn glyph_name(&self, glyph_id: GlyphId) -> Option<&'a str> {
         match self.kind {
-            FontKind::SID(_) => {
-                let sid = self.charset.gid_to_sid(glyph_id)?;
-                let sid = usize::from(sid.0);
-                match STANDARD_NAMES.get(sid) {
-                    Some(name) => Some(name),
-                    None => {
-                        let idx = u32::try_from(sid - STANDARD_NAMES.len()).ok()?;
-                        let name = self.strings.get(idx)?;
-                        core::str::from_utf8(name).ok()
-                    }
-                }
-            }
+            FontKind::SID(_) => self.string(self.charset.gid_to_sid(glyph_id)?),
             FontKind::CID(_) => None,
         }
     }
@@ -1051,6 +1392,30 @@ impl<'a> Table<'a> {
             FontKind::CID(_) => self.charset.gid_to_sid(glyph_id).map(|id| id.0),
         }
     }
+
+    /// Returns a glyph ID by a CID.
+    ///
+    /// Returns `None` if this is not a CIDFont.
+    pub fn glyph_index_by_cid(&self, cid: u16) -> Option<GlyphId> {
+        match self.kind {
+            FontKind::SID(_) => None,
+            FontKind::CID(_) => self.charset.sid_to_gid(StringId(cid)),
+        }
+    }
+
+    /// Resolves a string using the standard strings and the String INDEX.
+    ///
+    /// Standard strings are resolved only when the `glyph-names` feature is enabled.
+    fn string(&self, sid: StringId) -> Option<&'a str> {
+        let sid = usize::from(sid.0);
+        match sid.checked_sub(STANDARD_STRINGS_LEN) {
+            Some(idx) => {
+                let name = self.strings.get(u32::try_from(idx).ok()?)?;
+                core::str::from_utf8(name).ok()
+            }
+            None => standard_string(sid),
+        }
+    }
 }
 
 impl core::fmt::Debug for Table<'_> {
@@ -7,14 +7,19 @@ use core::convert::TryFrom;
 use core::ops::Range;
 
 use super::argstack::ArgumentsStack;
-use super::charstring::CharStringParser;
+use super::charstring::{parse_operand, CharStringParser};
 use super::dict::DictionaryParser;
 use super::index::{parse_index, Index};
-use super::{calc_subroutine_bias, conv_subroutine_index, Builder, CFFError};
-use crate::parser::{NumFrom, Stream, TryNumFrom};
+use super::{
+    calc_subroutine_bias, conv_subroutine_index, operator, Builder, CFFError, CFFHintsBuilder,
+    IgnoreHints,
+};
+use crate::parser::{LazyArray16, NumFrom, Stream, TryNumFrom};
 use crate::var_store::*;
 use crate::{GlyphId, NormalizedCoordinate, OutlineBuilder, Rect, RectF};
 
+pub use super::decompiler::{Operator, Token, Tokens};
+
 // https://docs.microsoft.com/en-us/typography/opentype/spec/cff2#7-top-dict-data
 // 'Operators in DICT may be preceded by up to a maximum of 513 operands.'
 const MAX_OPERANDS_LEN: usize = 513;
@@ -25,39 +30,6 @@ const MAX_ARGUMENTS_STACK_LEN: usize = 513;
 
 const TWO_BYTE_OPERATOR_MARK: u8 = 12;
 
-// https://docs.microsoft.com/en-us/typography/opentype/spec/cff2charstr#4-charstring-operators
-mod operator {
-    pub const HORIZONTAL_STEM: u8 = 1;
-    pub const VERTICAL_STEM: u8 = 3;
-    pub const VERTICAL_MOVE_TO: u8 = 4;
-    pub const LINE_TO: u8 = 5;
-    pub const HORIZONTAL_LINE_TO: u8 = 6;
-    pub const VERTICAL_LINE_TO: u8 = 7;
-    pub const CURVE_TO: u8 = 8;
-    pub const CALL_LOCAL_SUBROUTINE: u8 = 10;
-    pub const VS_INDEX: u8 = 15;
-    pub const BLEND: u8 = 16;
-    pub const HORIZONTAL_STEM_HINT_MASK: u8 = 18;
-    pub const HINT_MASK: u8 = 19;
-    pub const COUNTER_MASK: u8 = 20;
-    pub const MOVE_TO: u8 = 21;
-    pub const HORIZONTAL_MOVE_TO: u8 = 22;
-    pub const VERTICAL_STEM_HINT_MASK: u8 = 23;
-    pub const CURVE_LINE: u8 = 24;
-    pub const LINE_CURVE: u8 = 25;
-    pub const VV_CURVE_TO: u8 = 26;
-    pub const HH_CURVE_TO: u8 = 27;
-    pub const SHORT_INT: u8 = 28;
-    pub const CALL_GLOBAL_SUBROUTINE: u8 = 29;
-    pub const VH_CURVE_TO: u8 = 30;
-    pub const HV_CURVE_TO: u8 = 31;
-    pub const HFLEX: u8 = 34;
-    pub const FLEX: u8 = 35;
-    pub const HFLEX1: u8 = 36;
-    pub const FLEX1: u8 = 37;
-    pub const FIXED_16_16: u8 = 255;
-}
-
 // https://docs.microsoft.com/en-us/typography/opentype/spec/cff2#table-9-top-dict-operator-entries
 mod top_dict_operator {
     pub const CHAR_STRINGS_OFFSET: u16 = 17;
@@ -233,7 +205,7 @@ fn parse_char_string(
     data: &[u8],
     metadata: &Table,
     coordinates: &[NormalizedCoordinate],
-    builder: &mut dyn OutlineBuilder,
+    builder: &mut dyn CFFHintsBuilder,
 ) -> Result<Rect, CFFError> {
     let mut ctx = CharStringParserContext {
         metadata,
@@ -304,7 +276,13 @@ fn _parse_char_string(
 
                 ctx.stems_len += p.stack.len() as u32 >> 1;
 
-                // We are ignoring the hint operators.
+                let is_horizontal = matches!(
+                    op,
+                    operator::HORIZONTAL_STEM | operator::HORIZONTAL_STEM_HINT_MASK
+                );
+                p.builder
+                    .stems(&p.stack.data[..p.stack.len()], is_horizontal);
+
                 p.stack.clear();
             }
             operator::VERTICAL_MOVE_TO => {
@@ -397,11 +375,16 @@ fn _parse_char_string(
                 }
             }
             operator::HINT_MASK | operator::COUNTER_MASK => {
+                // Values left on the stack are implicit `vstem` hints.
                 ctx.stems_len += p.stack.len() as u32 >> 1;
-                s.advance(usize::num_from((ctx.stems_len + 7) >> 3));
-
-                // We are ignoring the hint operators.
+                p.builder.stems(&p.stack.data[..p.stack.len()], false);
                 p.stack.clear();
+
+                let mask_len = usize::num_from((ctx.stems_len + 7) >> 3);
+                match s.read_bytes(mask_len) {
+                    Some(mask) => p.builder.mask(mask, op == operator::COUNTER_MASK),
+                    None => s.advance(mask_len),
+                }
             }
             operator::MOVE_TO => {
                 p.parse_move_to(0)?;
@@ -421,10 +404,6 @@ fn _parse_char_string(
             operator::HH_CURVE_TO => {
                 p.parse_hh_curve_to()?;
             }
-            operator::SHORT_INT => {
-                let n = s.read::<i16>().ok_or(CFFError::ReadOutOfBounds)?;
-                p.stack.push(f32::from(n))?;
-            }
             operator::CALL_GLOBAL_SUBROUTINE => {
                 if p.stack.is_empty() {
                     return Err(CFFError::InvalidArgumentsStackLength);
@@ -449,17 +428,8 @@ fn _parse_char_string(
             operator::HV_CURVE_TO => {
                 p.parse_hv_curve_to()?;
             }
-            32..=246 => {
-                p.parse_int1(op)?;
-            }
-            247..=250 => {
-                p.parse_int2(op, &mut s)?;
-            }
-            251..=254 => {
-                p.parse_int3(op, &mut s)?;
-            }
-            operator::FIXED_16_16 => {
-                p.parse_fixed(&mut s)?;
+            operator::SHORT_INT | 32..=255 => {
+                p.stack.push(parse_operand(op, &mut s)?)?;
             }
         }
     }
@@ -467,6 +437,82 @@ fn _parse_char_string(
     Ok(())
 }
 
+/// A blended value before blending.
+#[derive(Clone, Copy)]
+pub struct BlendValue {
+    /// The default value.
+    pub default: f32,
+    deltas: [f32; SCALARS_MAX as usize],
+    deltas_len: u8,
+}
+
+impl BlendValue {
+    /// Returns deltas for each region used by the glyph.
+    ///
+    /// See [`Table::variation_region_indices`] for regions.
+    #[inline]
+    pub fn deltas(&self) -> &[f32] {
+        &self.deltas[..usize::from(self.deltas_len)]
+    }
+}
+
+impl core::fmt::Debug for BlendValue {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("BlendValue")
+            .field("default", &self.default)
+            .field("deltas", &self.deltas())
+            .finish()
+    }
+}
+
+/// An iterator over glyph blended values.
+///
+/// Each `blend` operator produces a value per blended operand,
+/// in the charstring order.
+#[derive(Clone, Debug)]
+pub struct BlendValues<'a> {
+    tokens: Tokens<'a>,
+    index: usize,
+    len: usize,
+}
+
+impl Iterator for BlendValues<'_> {
+    type Item = Result<BlendValue, CFFError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.index < self.len {
+                let (defaults, deltas) = self.tokens.last_blend();
+                let k = usize::from(self.tokens.regions_len());
+                if k > usize::from(SCALARS_MAX) {
+                    self.len = 0;
+                    return Some(Err(CFFError::BlendRegionsLimitReached));
+                }
+
+                let mut value = BlendValue {
+                    default: defaults[self.index],
+                    deltas: [0.0; SCALARS_MAX as usize],
+                    deltas_len: k as u8,
+                };
+                let start = self.index * k;
+                value.deltas[..k].copy_from_slice(&deltas[start..start + k]);
+
+                self.index += 1;
+                return Some(Ok(value));
+            }
+
+            match self.tokens.next()? {
+                Ok(Token::Operator(Operator::Blend)) => {
+                    self.index = 0;
+                    self.len = self.tokens.last_blend().0.len();
+                }
+                Ok(_) => {}
+                Err(e) => return Some(Err(e)),
+            }
+        }
+    }
+}
+
 /// A [Compact Font Format 2 Table](
 /// https://docs.microsoft.com/en-us/typography/opentype/spec/cff2).
 #[derive(Clone, Copy, Default)]
@@ -548,6 +594,22 @@ impl<'a> Table<'a> {
         coordinates: &[NormalizedCoordinate],
         glyph_id: GlyphId,
         builder: &mut dyn OutlineBuilder,
+    ) -> Result<Rect, CFFError> {
+        let data = self
+            .char_strings
+            .get(u32::from(glyph_id.0))
+            .ok_or(CFFError::NoGlyph)?;
+        parse_char_string(data, self, coordinates, &mut IgnoreHints(builder))
+    }
+
+    /// Outlines a glyph and reports its stem hints and hint masks.
+    ///
+    /// Hints are adjusted for the specified variation coordinates.
+    pub fn outline_with_hints(
+        &self,
+        coordinates: &[NormalizedCoordinate],
+        glyph_id: GlyphId,
+        builder: &mut dyn CFFHintsBuilder,
     ) -> Result<Rect, CFFError> {
         let data = self
             .char_strings
@@ -555,6 +617,65 @@ impl<'a> Table<'a> {
             .ok_or(CFFError::NoGlyph)?;
         parse_char_string(data, self, coordinates, builder)
     }
+
+    /// Returns the ItemVariationData index used by glyph blends.
+    ///
+    /// This is the `vsindex` operator value. Defaults to 0.
+    pub fn glyph_variation_store_index(&self, glyph_id: GlyphId) -> Option<u16> {
+        let mut last_operand = None;
+        for token in self.char_string_tokens(glyph_id, true)? {
+            match token.ok()? {
+                Token::Operand(n) => last_operand = Some(n),
+                Token::Operator(Operator::VariationStoreIndex) => {
+                    return u16::try_num_from(last_operand?);
+                }
+                // `vsindex` must precede the first `blend` operator.
+                Token::Operator(Operator::Blend) => break,
+                Token::Operator(_) => last_operand = None,
+            }
+        }
+
+        Some(0)
+    }
+
+    /// Returns variation region indices used by blends with the specified `vsindex`.
+    pub fn variation_region_indices(
+        &self,
+        variation_store_index: u16,
+    ) -> Option<LazyArray16<'a, u16>> {
+        self.item_variation_store
+            .region_indices(variation_store_index)
+    }
+
+    /// Returns variation region coordinates for each axis.
+    pub fn variation_region(&self, index: u16) -> Option<LazyArray16<'a, RegionAxisCoordinates>> {
+        self.item_variation_store.regions.region(index)
+    }
+
+    /// Returns glyph blended values before blending.
+    ///
+    /// Subroutines are flattened.
+    pub fn glyph_blend_values(&self, glyph_id: GlyphId) -> Option<BlendValues<'a>> {
+        Some(BlendValues {
+            tokens: self.char_string_tokens(glyph_id, true)?,
+            index: 0,
+            len: 0,
+        })
+    }
+
+    /// Decompiles a glyph charstring.
+    ///
+    /// When `flatten` is set, subroutine bodies are emitted in place of their calls.
+    pub fn char_string_tokens(&self, glyph_id: GlyphId, flatten: bool) -> Option<Tokens<'a>> {
+        let data = self.char_strings.get(u32::from(glyph_id.0))?;
+        Some(Tokens::new_cff2(
+            data,
+            self.global_subrs,
+            self.local_subrs,
+            self.item_variation_store,
+            flatten,
+        ))
+    }
 }
 
 impl core::fmt::Debug for Table<'_> {
@@ -1,5 +1,5 @@
 use super::argstack::ArgumentsStack;
-use super::{f32_abs, Builder, CFFError, IsEven};
+use super::{f32_abs, operator, Builder, CFFError, IsEven};
 use crate::parser::{Fixed, Stream};
 
 pub(crate) struct CharStringParser<'a> {
@@ -578,36 +578,30 @@ impl CharStringParser<'_> {
         self.stack.clear();
         Ok(())
     }
+}
 
-    #[inline]
-    pub fn parse_int1(&mut self, op: u8) -> Result<(), CFFError> {
-        let n = i16::from(op) - 139;
-        self.stack.push(f32::from(n))?;
-        Ok(())
-    }
-
-    #[inline]
-    pub fn parse_int2(&mut self, op: u8, s: &mut Stream) -> Result<(), CFFError> {
-        let b1 = s.read::<u8>().ok_or(CFFError::ReadOutOfBounds)?;
-        let n = (i16::from(op) - 247) * 256 + i16::from(b1) + 108;
-        debug_assert!((108..=1131).contains(&n));
-        self.stack.push(f32::from(n))?;
-        Ok(())
-    }
-
-    #[inline]
-    pub fn parse_int3(&mut self, op: u8, s: &mut Stream) -> Result<(), CFFError> {
-        let b1 = s.read::<u8>().ok_or(CFFError::ReadOutOfBounds)?;
-        let n = -(i16::from(op) - 251) * 256 - i16::from(b1) - 108;
-        debug_assert!((-1131..=-108).contains(&n));
-        self.stack.push(f32::from(n))?;
-        Ok(())
-    }
-
-    #[inline]
-    pub fn parse_fixed(&mut self, s: &mut Stream) -> Result<(), CFFError> {
-        let n = s.read::<Fixed>().ok_or(CFFError::ReadOutOfBounds)?;
-        self.stack.push(n.0)?;
-        Ok(())
+/// Parses a charstring number that starts with the `op` byte.
+pub(crate) fn parse_operand(op: u8, s: &mut Stream) -> Result<f32, CFFError> {
+    match op {
+        operator::SHORT_INT => {
+            let n = s.read::<i16>().ok_or(CFFError::ReadOutOfBounds)?;
+            Ok(f32::from(n))
+        }
+        32..=246 => Ok(f32::from(i16::from(op) - 139)),
+        247..=250 => {
+            let b1 = s.read::<u8>().ok_or(CFFError::ReadOutOfBounds)?;
+            Ok(f32::from((i16::from(op) - 247) * 256 + i16::from(b1) + 108))
+        }
+        251..=254 => {
+            let b1 = s.read::<u8>().ok_or(CFFError::ReadOutOfBounds)?;
+            Ok(f32::from(
+                -(i16::from(op) - 251) * 256 - i16::from(b1) - 108,
+            ))
+        }
+        operator::FIXED_16_16 => {
+            let n = s.read::<Fixed>().ok_or(CFFError::ReadOutOfBounds)?;
+            Ok(n.0)
+        }
+        _ => Err(CFFError::InvalidOperator),
     }
 }
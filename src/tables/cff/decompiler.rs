@@ -0,0 +1,468 @@
+//! A CFF and CFF2 charstring decompiler.
+
+use super::argstack::ArgumentsStack;
+use super::charstring::parse_operand;
+use super::index::Index;
+use super::{calc_subroutine_bias, conv_subroutine_index, operator, CFFError};
+#[cfg(feature = "variable-fonts")]
+use crate::parser::TryNumFrom;
+use crate::parser::{NumFrom, Stream};
+#[cfg(feature = "variable-fonts")]
+use crate::var_store::ItemVariationStore;
+
+// Limits according to the Adobe Technical Note #5177 Appendix B
+// and the CFF2 charstring implementation limits.
+const STACK_LIMIT: u8 = 10;
+const MAX_ARGUMENTS_STACK_LEN: usize = 513;
+
+const TWO_BYTE_OPERATOR_MARK: u8 = 12;
+
+/// A charstring operator.
+#[allow(missing_docs)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Operator<'a> {
+    HorizontalStem,
+    VerticalStem,
+    VerticalMoveTo,
+    LineTo,
+    HorizontalLineTo,
+    VerticalLineTo,
+    CurveTo,
+    CallLocalSubroutine,
+    /// CFF only.
+    Return,
+    /// CFF only.
+    ///
+    /// Has four extra operands when used as `seac`.
+    EndChar,
+    /// CFF2 only.
+    VariationStoreIndex,
+    /// CFF2 only.
+    Blend,
+    HorizontalStemHintMask,
+    /// A hint mask with its bytes.
+    HintMask(&'a [u8]),
+    /// A counter mask with its bytes.
+    CounterMask(&'a [u8]),
+    MoveTo,
+    HorizontalMoveTo,
+    VerticalStemHintMask,
+    CurveLine,
+    LineCurve,
+    VVCurveTo,
+    HHCurveTo,
+    CallGlobalSubroutine,
+    VHCurveTo,
+    HVCurveTo,
+    HFlex,
+    Flex,
+    HFlex1,
+    Flex1,
+}
+
+impl Operator<'_> {
+    /// Returns the operator name as defined by the Adobe Technical Note #5177.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Operator::HorizontalStem => "hstem",
+            Operator::VerticalStem => "vstem",
+            Operator::VerticalMoveTo => "vmoveto",
+            Operator::LineTo => "rlineto",
+            Operator::HorizontalLineTo => "hlineto",
+            Operator::VerticalLineTo => "vlineto",
+            Operator::CurveTo => "rrcurveto",
+            Operator::CallLocalSubroutine => "callsubr",
+            Operator::Return => "return",
+            Operator::EndChar => "endchar",
+            Operator::VariationStoreIndex => "vsindex",
+            Operator::Blend => "blend",
+            Operator::HorizontalStemHintMask => "hstemhm",
+            Operator::HintMask(_) => "hintmask",
+            Operator::CounterMask(_) => "cntrmask",
+            Operator::MoveTo => "rmoveto",
+            Operator::HorizontalMoveTo => "hmoveto",
+            Operator::VerticalStemHintMask => "vstemhm",
+            Operator::CurveLine => "rcurveline",
+            Operator::LineCurve => "rlinecurve",
+            Operator::VVCurveTo => "vvcurveto",
+            Operator::HHCurveTo => "hhcurveto",
+            Operator::CallGlobalSubroutine => "callgsubr",
+            Operator::VHCurveTo => "vhcurveto",
+            Operator::HVCurveTo => "hvcurveto",
+            Operator::HFlex => "hflex",
+            Operator::Flex => "flex",
+            Operator::HFlex1 => "hflex1",
+            Operator::Flex1 => "flex1",
+        }
+    }
+}
+
+/// A charstring token.
+///
+/// Operands precede their operator.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Token<'a> {
+    /// A number.
+    Operand(f32),
+    /// An operator.
+    Operator(Operator<'a>),
+}
+
+#[derive(Clone, Copy)]
+struct DecoderState<'a> {
+    global_subrs: Index<'a>,
+    local_subrs: Option<Index<'a>>,
+    is_cff2: bool,
+    #[cfg(feature = "variable-fonts")]
+    item_variation_store: ItemVariationStore<'a>,
+    #[cfg(feature = "variable-fonts")]
+    regions_len: u16,
+    // Start and number of values of the last blend on the stack.
+    #[cfg(feature = "variable-fonts")]
+    last_blend: (usize, usize),
+    flatten: bool,
+    // A charstring and an offset in it for each call depth.
+    frames: [(&'a [u8], usize); STACK_LIMIT as usize + 1],
+    depth: u8,
+    stems_len: u32,
+    finished: bool,
+}
+
+/// An iterator over charstring tokens.
+///
+/// Subroutines are always executed, so hint masks are decoded properly,
+/// but their tokens are emitted only when flattening.
+/// In that case, subroutine calls, their indices and returns are omitted.
+///
+/// Blends are not applied.
+///
+/// Stops after the first error.
+#[derive(Clone)]
+pub struct Tokens<'a> {
+    state: DecoderState<'a>,
+    stack_data: [f32; MAX_ARGUMENTS_STACK_LEN], // 2052B
+    stack_len: usize,
+    max_stack_len: usize,
+}
+
+impl<'a> Tokens<'a> {
+    pub(crate) fn new_cff(
+        char_string: &'a [u8],
+        global_subrs: Index<'a>,
+        local_subrs: Option<Index<'a>>,
+        max_stack_len: usize,
+        flatten: bool,
+    ) -> Self {
+        let mut frames = [(&[][..], 0); STACK_LIMIT as usize + 1];
+        frames[0] = (char_string, 0);
+
+        Tokens {
+            state: DecoderState {
+                global_subrs,
+                local_subrs,
+                is_cff2: false,
+                #[cfg(feature = "variable-fonts")]
+                item_variation_store: ItemVariationStore::default(),
+                #[cfg(feature = "variable-fonts")]
+                regions_len: 0,
+                #[cfg(feature = "variable-fonts")]
+                last_blend: (0, 0),
+                flatten,
+                frames,
+                depth: 0,
+                stems_len: 0,
+                finished: false,
+            },
+            stack_data: [0.0; MAX_ARGUMENTS_STACK_LEN],
+            stack_len: 0,
+            max_stack_len,
+        }
+    }
+
+    #[cfg(feature = "variable-fonts")]
+    pub(crate) fn new_cff2(
+        char_string: &'a [u8],
+        global_subrs: Index<'a>,
+        local_subrs: Index<'a>,
+        item_variation_store: ItemVariationStore<'a>,
+        flatten: bool,
+    ) -> Self {
+        let mut tokens = Self::new_cff(
+            char_string,
+            global_subrs,
+            Some(local_subrs),
+            MAX_ARGUMENTS_STACK_LEN,
+            flatten,
+        );
+        tokens.state.is_cff2 = true;
+        // Blends use the first ItemVariationData by default.
+        tokens.state.regions_len = item_variation_store
+            .region_indices(0)
+            .map(|indices| indices.len())
+            .unwrap_or(0);
+        tokens.state.item_variation_store = item_variation_store;
+        tokens
+    }
+
+    /// Returns default values and region deltas of the last blend.
+    ///
+    /// Must be called right after the `Blend` token, before deltas are overwritten.
+    #[cfg(feature = "variable-fonts")]
+    pub(crate) fn last_blend(&self) -> (&[f32], &[f32]) {
+        let (start, n) = self.state.last_blend;
+        let k = usize::from(self.state.regions_len);
+        let defaults = &self.stack_data[start..start + n];
+        let deltas = &self.stack_data[start + n..start + n + n * k];
+        (defaults, deltas)
+    }
+
+    /// Returns the number of regions used by blends.
+    #[cfg(feature = "variable-fonts")]
+    pub(crate) fn regions_len(&self) -> u16 {
+        self.state.regions_len
+    }
+}
+
+impl<'a> Iterator for Tokens<'a> {
+    type Item = Result<Token<'a>, CFFError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.state.finished {
+            return None;
+        }
+
+        let mut stack = ArgumentsStack {
+            data: &mut self.stack_data,
+            len: self.stack_len,
+            max_len: self.max_stack_len,
+        };
+        let result = self.state.next_token(&mut stack);
+        self.stack_len = stack.len;
+
+        match result {
+            Ok(Some(token)) => Some(Ok(token)),
+            Ok(None) => {
+                self.state.finished = true;
+                None
+            }
+            Err(e) => {
+                self.state.finished = true;
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+impl core::fmt::Debug for Tokens<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "Tokens {{ ... }}")
+    }
+}
+
+impl<'a> DecoderState<'a> {
+    fn next_token(&mut self, stack: &mut ArgumentsStack) -> Result<Option<Token<'a>>, CFFError> {
+        loop {
+            let depth = self.depth;
+            let (data, offset) = self.frames[usize::from(depth)];
+            let mut s = Stream::new_at(data, offset).ok_or(CFFError::ReadOutOfBounds)?;
+            if s.at_end() {
+                if depth == 0 {
+                    return Ok(None);
+                }
+
+                // A subroutine without `return`.
+                self.depth -= 1;
+                continue;
+            }
+
+            let (token, is_visible) = self.parse_token(&mut s, stack)?;
+            self.frames[usize::from(depth)].1 = s.offset();
+
+            if self.finished && depth != 0 && !self.flatten {
+                // `endchar` inside a subroutine.
+                return Ok(None);
+            }
+
+            if is_visible && (depth == 0 || self.flatten) {
+                return Ok(Some(token));
+            }
+        }
+    }
+
+    fn parse_token(
+        &mut self,
+        s: &mut Stream<'a>,
+        stack: &mut ArgumentsStack,
+    ) -> Result<(Token<'a>, bool), CFFError> {
+        let op = s.read::<u8>().ok_or(CFFError::ReadOutOfBounds)?;
+        let operator = match op {
+            operator::SHORT_INT | 32..=255 => {
+                let n = parse_operand(op, s)?;
+                stack.push(n)?;
+
+                // Subroutine indices are omitted when flattening.
+                let is_index = matches!(
+                    s.clone().read::<u8>(),
+                    Some(operator::CALL_LOCAL_SUBROUTINE) | Some(operator::CALL_GLOBAL_SUBROUTINE)
+                );
+                return Ok((Token::Operand(n), !(self.flatten && is_index)));
+            }
+            operator::HORIZONTAL_STEM
+            | operator::VERTICAL_STEM
+            | operator::HORIZONTAL_STEM_HINT_MASK
+            | operator::VERTICAL_STEM_HINT_MASK => {
+                // The width, if any, is ignored by the division.
+                self.stems_len += stack.len() as u32 >> 1;
+                stack.clear();
+                match op {
+                    operator::HORIZONTAL_STEM => Operator::HorizontalStem,
+                    operator::VERTICAL_STEM => Operator::VerticalStem,
+                    operator::HORIZONTAL_STEM_HINT_MASK => Operator::HorizontalStemHintMask,
+                    _ => Operator::VerticalStemHintMask,
+                }
+            }
+            operator::HINT_MASK | operator::COUNTER_MASK => {
+                // Values left on the stack are implicit `vstem` hints.
+                self.stems_len += stack.len() as u32 >> 1;
+                stack.clear();
+
+                let mask_len = usize::num_from((self.stems_len + 7) >> 3);
+                let mask = s.read_bytes(mask_len).ok_or(CFFError::ReadOutOfBounds)?;
+                if op == operator::HINT_MASK {
+                    Operator::HintMask(mask)
+                } else {
+                    Operator::CounterMask(mask)
+                }
+            }
+            operator::CALL_LOCAL_SUBROUTINE | operator::CALL_GLOBAL_SUBROUTINE => {
+                if stack.is_empty() {
+                    return Err(CFFError::InvalidArgumentsStackLength);
+                }
+
+                if self.depth == STACK_LIMIT {
+                    return Err(CFFError::NestingLimitReached);
+                }
+
+                let subrs = if op == operator::CALL_LOCAL_SUBROUTINE {
+                    self.local_subrs.ok_or(CFFError::NoLocalSubroutines)?
+                } else {
+                    self.global_subrs
+                };
+
+                let subroutine_bias = calc_subroutine_bias(subrs.len());
+                let index = conv_subroutine_index(stack.pop(), subroutine_bias)?;
+                let char_string = subrs.get(index).ok_or(CFFError::InvalidSubroutineIndex)?;
+
+                self.depth += 1;
+                self.frames[usize::from(self.depth)] = (char_string, 0);
+
+                let operator = if op == operator::CALL_LOCAL_SUBROUTINE {
+                    Operator::CallLocalSubroutine
+                } else {
+                    Operator::CallGlobalSubroutine
+                };
+                return Ok((Token::Operator(operator), !self.flatten));
+            }
+            operator::RETURN if !self.is_cff2 => {
+                if self.depth == 0 {
+                    self.finished = true;
+                    Operator::Return
+                } else {
+                    self.depth -= 1;
+                    return Ok((Token::Operator(Operator::Return), false));
+                }
+            }
+            operator::ENDCHAR if !self.is_cff2 => {
+                if !s.at_end() {
+                    return Err(CFFError::DataAfterEndChar);
+                }
+
+                stack.clear();
+                self.finished = true;
+                Operator::EndChar
+            }
+            #[cfg(feature = "variable-fonts")]
+            operator::VS_INDEX if self.is_cff2 => {
+                if stack.len() != 1 {
+                    return Err(CFFError::InvalidArgumentsStackLength);
+                }
+
+                let index = u16::try_num_from(stack.pop())
+                    .ok_or(CFFError::InvalidItemVariationDataIndex)?;
+                self.regions_len = self
+                    .item_variation_store
+                    .region_indices(index)
+                    .ok_or(CFFError::InvalidItemVariationDataIndex)?
+                    .len();
+                Operator::VariationStoreIndex
+            }
+            #[cfg(feature = "variable-fonts")]
+            operator::BLEND if self.is_cff2 => {
+                if stack.is_empty() {
+                    return Err(CFFError::InvalidArgumentsStackLength);
+                }
+
+                let n =
+                    u16::try_num_from(stack.pop()).ok_or(CFFError::InvalidNumberOfBlendOperands)?;
+                let n = usize::from(n);
+                let k = usize::from(self.regions_len);
+                if stack.len() < n * (k + 1) {
+                    return Err(CFFError::InvalidArgumentsStackLength);
+                }
+
+                // Only default values are left on the stack.
+                stack.len -= n * k;
+                self.last_blend = (stack.len() - n, n);
+                Operator::Blend
+            }
+            TWO_BYTE_OPERATOR_MARK => {
+                let op2 = s.read::<u8>().ok_or(CFFError::ReadOutOfBounds)?;
+                let operator = match op2 {
+                    operator::HFLEX => Operator::HFlex,
+                    operator::FLEX => Operator::Flex,
+                    operator::HFLEX1 => Operator::HFlex1,
+                    operator::FLEX1 => Operator::Flex1,
+                    _ => return Err(CFFError::UnsupportedOperator),
+                };
+                stack.clear();
+                operator
+            }
+            operator::VERTICAL_MOVE_TO
+            | operator::LINE_TO
+            | operator::HORIZONTAL_LINE_TO
+            | operator::VERTICAL_LINE_TO
+            | operator::CURVE_TO
+            | operator::MOVE_TO
+            | operator::HORIZONTAL_MOVE_TO
+            | operator::CURVE_LINE
+            | operator::LINE_CURVE
+            | operator::VV_CURVE_TO
+            | operator::HH_CURVE_TO
+            | operator::VH_CURVE_TO
+            | operator::HV_CURVE_TO => {
+                stack.clear();
+                match op {
+                    operator::VERTICAL_MOVE_TO => Operator::VerticalMoveTo,
+                    operator::LINE_TO => Operator::LineTo,
+                    operator::HORIZONTAL_LINE_TO => Operator::HorizontalLineTo,
+                    operator::VERTICAL_LINE_TO => Operator::VerticalLineTo,
+                    operator::CURVE_TO => Operator::CurveTo,
+                    operator::MOVE_TO => Operator::MoveTo,
+                    operator::HORIZONTAL_MOVE_TO => Operator::HorizontalMoveTo,
+                    operator::CURVE_LINE => Operator::CurveLine,
+                    operator::LINE_CURVE => Operator::LineCurve,
+                    operator::VV_CURVE_TO => Operator::VVCurveTo,
+                    operator::HH_CURVE_TO => Operator::HHCurveTo,
+                    operator::VH_CURVE_TO => Operator::VHCurveTo,
+                    _ => Operator::HVCurveTo,
+                }
+            }
+            _ => {
+                // Reserved or not allowed in this format.
+                return Err(CFFError::InvalidOperator);
+            }
+        };
+
+        Ok((Token::Operator(operator), true))
+    }
+}
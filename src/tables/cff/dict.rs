@@ -106,6 +106,12 @@ impl<'a> DictionaryParser<'a> {
         Some(())
     }
 
+    /// Returns the offset of the current operator operands.
+    #[inline]
+    pub fn operands_offset(&self) -> usize {
+        self.operands_offset
+    }
+
     #[inline]
     pub fn operands(&self) -> &[f64] {
         &self.operands[..usize::from(self.operands_len)]
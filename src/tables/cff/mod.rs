@@ -4,6 +4,7 @@ pub mod cff1;
 pub mod cff2;
 mod charset;
 mod charstring;
+mod decompiler;
 mod dict;
 mod encoding;
 mod index;
@@ -15,6 +16,44 @@ use core::convert::TryFrom;
 use crate::parser::{FromData, TryNumFrom};
 use crate::{OutlineBuilder, RectF};
 
+/// Enumerates CFF and CFF2 charstring operators defined in the Adobe Technical Note #5177
+/// and the CFF2 specification.
+mod operator {
+    pub const HORIZONTAL_STEM: u8 = 1;
+    pub const VERTICAL_STEM: u8 = 3;
+    pub const VERTICAL_MOVE_TO: u8 = 4;
+    pub const LINE_TO: u8 = 5;
+    pub const HORIZONTAL_LINE_TO: u8 = 6;
+    pub const VERTICAL_LINE_TO: u8 = 7;
+    pub const CURVE_TO: u8 = 8;
+    pub const CALL_LOCAL_SUBROUTINE: u8 = 10;
+    pub const RETURN: u8 = 11;
+    pub const ENDCHAR: u8 = 14;
+    #[cfg(feature = "variable-fonts")]
+    pub const VS_INDEX: u8 = 15;
+    #[cfg(feature = "variable-fonts")]
+    pub const BLEND: u8 = 16;
+    pub const HORIZONTAL_STEM_HINT_MASK: u8 = 18;
+    pub const HINT_MASK: u8 = 19;
+    pub const COUNTER_MASK: u8 = 20;
+    pub const MOVE_TO: u8 = 21;
+    pub const HORIZONTAL_MOVE_TO: u8 = 22;
+    pub const VERTICAL_STEM_HINT_MASK: u8 = 23;
+    pub const CURVE_LINE: u8 = 24;
+    pub const LINE_CURVE: u8 = 25;
+    pub const VV_CURVE_TO: u8 = 26;
+    pub const HH_CURVE_TO: u8 = 27;
+    pub const SHORT_INT: u8 = 28;
+    pub const CALL_GLOBAL_SUBROUTINE: u8 = 29;
+    pub const VH_CURVE_TO: u8 = 30;
+    pub const HV_CURVE_TO: u8 = 31;
+    pub const HFLEX: u8 = 34;
+    pub const FLEX: u8 = 35;
+    pub const HFLEX1: u8 = 36;
+    pub const FLEX1: u8 = 37;
+    pub const FIXED_16_16: u8 = 255;
+}
+
 /// A list of errors that can occur during a CFF glyph outlining.
 #[allow(missing_docs)]
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
@@ -42,8 +81,77 @@ pub enum CFFError {
     BlendRegionsLimitReached,
 }
 
+/// An [`OutlineBuilder`] that also receives CFF hints.
+///
+/// Hints are reported in the charstring order, interleaved with outline segments.
+/// All values are in font units.
+pub trait CFFHintsBuilder: OutlineBuilder {
+    /// Appends a horizontal stem hint.
+    ///
+    /// `y` is the bottom edge and `dy` is the stem height.
+    /// Ghost hints have a negative height.
+    fn hstem(&mut self, y: f32, dy: f32);
+
+    /// Appends a vertical stem hint.
+    ///
+    /// `x` is the left edge and `dx` is the stem width.
+    fn vstem(&mut self, x: f32, dx: f32);
+
+    /// Sets the active stem hints for the following segments.
+    ///
+    /// Each bit corresponds to a stem in the declaration order, horizontal stems first,
+    /// starting from the most significant bit of the first byte.
+    fn hint_mask(&mut self, mask: &[u8]);
+
+    /// Sets a group of stems that should be controlled together.
+    ///
+    /// Uses the same bit layout as `hint_mask`.
+    fn counter_mask(&mut self, mask: &[u8]);
+}
+
+/// A `CFFHintsBuilder` that ignores hints.
+pub(crate) struct IgnoreHints<'a>(pub &'a mut dyn OutlineBuilder);
+
+impl OutlineBuilder for IgnoreHints<'_> {
+    #[inline]
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.0.move_to(x, y);
+    }
+
+    #[inline]
+    fn line_to(&mut self, x: f32, y: f32) {
+        self.0.line_to(x, y);
+    }
+
+    #[inline]
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        self.0.quad_to(x1, y1, x, y);
+    }
+
+    #[inline]
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        self.0.curve_to(x1, y1, x2, y2, x, y);
+    }
+
+    #[inline]
+    fn close(&mut self) {
+        self.0.close();
+    }
+}
+
+impl CFFHintsBuilder for IgnoreHints<'_> {
+    #[inline]
+    fn hstem(&mut self, _: f32, _: f32) {}
+    #[inline]
+    fn vstem(&mut self, _: f32, _: f32) {}
+    #[inline]
+    fn hint_mask(&mut self, _: &[u8]) {}
+    #[inline]
+    fn counter_mask(&mut self, _: &[u8]) {}
+}
+
 pub(crate) struct Builder<'a> {
-    builder: &'a mut dyn OutlineBuilder,
+    builder: &'a mut dyn CFFHintsBuilder,
     bbox: RectF,
 }
 
@@ -72,6 +180,31 @@ impl<'a> Builder<'a> {
     fn close(&mut self) {
         self.builder.close();
     }
+
+    /// Reports stems from a `y dy {dya dyb}*` or `x dx {dxa dxb}*` list.
+    ///
+    /// Each stem edge is relative to the previous one.
+    fn stems(&mut self, values: &[f32], is_horizontal: bool) {
+        let mut pos = 0.0;
+        for pair in values.chunks_exact(2) {
+            pos += pair[0];
+            if is_horizontal {
+                self.builder.hstem(pos, pair[1]);
+            } else {
+                self.builder.vstem(pos, pair[1]);
+            }
+            pos += pair[1];
+        }
+    }
+
+    #[inline]
+    fn mask(&mut self, mask: &[u8], is_counter: bool) {
+        if is_counter {
+            self.builder.counter_mask(mask);
+        } else {
+            self.builder.hint_mask(mask);
+        }
+    }
 }
 
 /// A type-safe wrapper for string ID.
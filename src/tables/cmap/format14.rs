@@ -130,6 +130,43 @@ impl<'a> Subtable14<'a> {
 
         None
     }
+
+    /// Returns an iterator over all variation sequences defined in this table.
+    ///
+    /// Sequences are grouped by a variation selector. For each selector,
+    /// default sequences are listed first, followed by non-default ones.
+    pub fn variation_sequences(&self) -> VariationSequences<'a> {
+        VariationSequences {
+            subtable: *self,
+            record_index: 0,
+            ranges: LazyArray32::default(),
+            range_index: 0,
+            range_offset: 0,
+            mappings: LazyArray32::default(),
+            mapping_index: 0,
+            variation: 0,
+        }
+    }
+
+    fn default_ranges(
+        &self,
+        offset: Option<Offset32>,
+    ) -> Option<LazyArray32<'a, UnicodeRangeRecord>> {
+        let data = self.data.get(offset?.to_usize()..)?;
+        let mut s = Stream::new(data);
+        let count = s.read::<u32>()?;
+        s.read_array32::<UnicodeRangeRecord>(count)
+    }
+
+    fn non_default_mappings(
+        &self,
+        offset: Option<Offset32>,
+    ) -> Option<LazyArray32<'a, UVSMappingRecord>> {
+        let data = self.data.get(offset?.to_usize()..)?;
+        let mut s = Stream::new(data);
+        let count = s.read::<u32>()?;
+        s.read_array32::<UVSMappingRecord>(count)
+    }
 }
 
 impl core::fmt::Debug for Subtable14<'_> {
@@ -137,3 +174,78 @@ impl core::fmt::Debug for Subtable14<'_> {
         write!(f, "Subtable14 {{ ... }}")
     }
 }
+
+/// A Unicode variation sequence.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct VariationSequence {
+    /// A base code point.
+    pub code_point: u32,
+    /// A variation selector code point.
+    pub variation: u32,
+    /// A glyph mapping for this sequence.
+    pub glyph: GlyphVariationResult,
+}
+
+/// An iterator over Unicode variation sequences.
+///
+/// Created by [`Subtable14::variation_sequences`].
+#[allow(missing_debug_implementations)]
+#[derive(Clone)]
+pub struct VariationSequences<'a> {
+    subtable: Subtable14<'a>,
+    record_index: u32,
+    ranges: LazyArray32<'a, UnicodeRangeRecord>,
+    range_index: u32,
+    range_offset: u16,
+    mappings: LazyArray32<'a, UVSMappingRecord>,
+    mapping_index: u32,
+    variation: u32,
+}
+
+impl<'a> Iterator for VariationSequences<'a> {
+    type Item = VariationSequence;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(range) = self.ranges.get(self.range_index) {
+                let code_point = range.start_unicode_value + u32::from(self.range_offset);
+                if self.range_offset < u16::from(range.additional_count) {
+                    self.range_offset += 1;
+                } else {
+                    self.range_index += 1;
+                    self.range_offset = 0;
+                }
+
+                return Some(VariationSequence {
+                    code_point,
+                    variation: self.variation,
+                    glyph: GlyphVariationResult::UseDefault,
+                });
+            }
+
+            if let Some(mapping) = self.mappings.get(self.mapping_index) {
+                self.mapping_index += 1;
+                return Some(VariationSequence {
+                    code_point: mapping.unicode_value,
+                    variation: self.variation,
+                    glyph: GlyphVariationResult::Found(mapping.glyph_id),
+                });
+            }
+
+            let record = self.subtable.records.get(self.record_index)?;
+            self.record_index += 1;
+            self.variation = record.var_selector;
+            self.ranges = self
+                .subtable
+                .default_ranges(record.default_uvs_offset)
+                .unwrap_or_default();
+            self.range_index = 0;
+            self.range_offset = 0;
+            self.mappings = self
+                .subtable
+                .non_default_mappings(record.non_default_uvs_offset)
+                .unwrap_or_default();
+            self.mapping_index = 0;
+        }
+    }
+}
@@ -0,0 +1,103 @@
+use core::convert::TryFrom;
+
+use crate::parser::{LazyArray32, Stream};
+use crate::GlyphId;
+
+use super::format12::SequentialMapGroup;
+
+/// A [format 8](https://docs.microsoft.com/en-us/typography/opentype/spec/cmap#format-8-mixed-16-bit-and-32-bit-coverage)
+/// subtable.
+///
+/// 32-bit character codes are stored as a concatenation of a high and low
+/// UTF-16 surrogates. Supplementary-plane code points are converted into this
+/// form during lookup and back during enumeration.
+#[derive(Clone, Copy)]
+pub struct Subtable8<'a> {
+    is32: &'a [u8],
+    groups: LazyArray32<'a, SequentialMapGroup>,
+}
+
+impl<'a> Subtable8<'a> {
+    /// Parses a subtable from raw data.
+    pub fn parse(data: &'a [u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+        s.skip::<u16>(); // format
+        s.skip::<u16>(); // reserved
+        s.skip::<u32>(); // length
+        s.skip::<u32>(); // language
+        let is32 = s.read_bytes(8192)?;
+        let count = s.read::<u32>()?;
+        let groups = s.read_array32::<SequentialMapGroup>(count)?;
+        Some(Self { is32, groups })
+    }
+
+    /// Checks that a 16-bit value is the first half of a 32-bit character code.
+    #[inline]
+    pub fn is_32bit_start(&self, value: u16) -> bool {
+        let byte = self.is32.get(usize::from(value / 8)).copied().unwrap_or(0);
+        byte & (0x80 >> (value % 8)) != 0
+    }
+
+    /// Returns a glyph index for a code point.
+    ///
+    /// Accepts both Unicode code points and already concatenated
+    /// surrogate pairs.
+    pub fn glyph_index(&self, code_point: u32) -> Option<GlyphId> {
+        let code_point = encode_surrogates(code_point);
+        let (_, group) = self.groups.binary_search_by(|range| {
+            use core::cmp::Ordering;
+
+            if range.start_char_code > code_point {
+                Ordering::Greater
+            } else if range.end_char_code < code_point {
+                Ordering::Less
+            } else {
+                Ordering::Equal
+            }
+        })?;
+
+        let id = group
+            .start_glyph_id
+            .checked_add(code_point)?
+            .checked_sub(group.start_char_code)?;
+        u16::try_from(id).ok().map(GlyphId)
+    }
+
+    /// Calls `f` for each codepoint defined in this table.
+    ///
+    /// Concatenated surrogate pairs are reported as Unicode code points.
+    pub fn codepoints(&self, mut f: impl FnMut(u32)) {
+        for group in self.groups {
+            for code_point in group.start_char_code..=group.end_char_code {
+                f(decode_surrogates(code_point));
+            }
+        }
+    }
+}
+
+impl core::fmt::Debug for Subtable8<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "Subtable8 {{ ... }}")
+    }
+}
+
+fn encode_surrogates(code_point: u32) -> u32 {
+    if (0x10000..=0x10FFFF).contains(&code_point) {
+        let c = code_point - 0x10000;
+        let high = 0xD800 + (c >> 10);
+        let low = 0xDC00 + (c & 0x3FF);
+        (high << 16) | low
+    } else {
+        code_point
+    }
+}
+
+fn decode_surrogates(code: u32) -> u32 {
+    let high = code >> 16;
+    let low = code & 0xFFFF;
+    if (0xD800..=0xDBFF).contains(&high) && (0xDC00..=0xDFFF).contains(&low) {
+        0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00)
+    } else {
+        code
+    }
+}
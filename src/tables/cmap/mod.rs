@@ -19,15 +19,17 @@ mod format14;
 mod format2;
 mod format4;
 mod format6;
+mod format8;
 
 pub use format0::Subtable0;
 pub use format10::Subtable10;
 pub use format12::Subtable12;
 pub use format13::Subtable13;
-pub use format14::{GlyphVariationResult, Subtable14};
+pub use format14::{GlyphVariationResult, Subtable14, VariationSequence, VariationSequences};
 pub use format2::Subtable2;
 pub use format4::Subtable4;
 pub use format6::Subtable6;
+pub use format8::Subtable8;
 
 /// A character encoding subtable variant.
 #[allow(missing_docs)]
@@ -37,7 +39,7 @@ pub enum Format<'a> {
     HighByteMappingThroughTable(Subtable2<'a>),
     SegmentMappingToDeltaValues(Subtable4<'a>),
     TrimmedTableMapping(Subtable6<'a>),
-    MixedCoverage, // unsupported
+    MixedCoverage(Subtable8<'a>),
     TrimmedArray(Subtable10<'a>),
     SegmentedCoverage(Subtable12<'a>),
     ManyToOneRangeMappings(Subtable13<'a>),
@@ -92,7 +94,6 @@ impl<'a> Subtable<'a> {
     ///
     /// Returns `None`:
     /// - when glyph ID is `0`.
-    /// - when format is `MixedCoverage`, since it's not supported.
     /// - when format is `UnicodeVariationSequences`. Use `glyph_variation_index` instead.
     #[inline]
     pub fn glyph_index(&self, code_point: u32) -> Option<GlyphId> {
@@ -101,7 +102,7 @@ impl<'a> Subtable<'a> {
             Format::HighByteMappingThroughTable(ref subtable) => subtable.glyph_index(code_point),
             Format::SegmentMappingToDeltaValues(ref subtable) => subtable.glyph_index(code_point),
             Format::TrimmedTableMapping(ref subtable) => subtable.glyph_index(code_point),
-            Format::MixedCoverage => None,
+            Format::MixedCoverage(ref subtable) => subtable.glyph_index(code_point),
             Format::TrimmedArray(ref subtable) => subtable.glyph_index(code_point),
             Format::SegmentedCoverage(ref subtable) => subtable.glyph_index(code_point),
             Format::ManyToOneRangeMappings(ref subtable) => subtable.glyph_index(code_point),
@@ -140,15 +141,15 @@ impl<'a> Subtable<'a> {
     /// this subtable. The subtable may still map them to glyph ID `0`.
     ///
     /// Returns without doing anything:
-    /// - when format is `MixedCoverage`, since it's not supported.
-    /// - when format is `UnicodeVariationSequences`, since it's not supported.
+    /// - when format is `UnicodeVariationSequences`.
+    ///   Use `Subtable14::variation_sequences` instead.
     pub fn codepoints<F: FnMut(u32)>(&self, f: F) {
         match self.format {
             Format::ByteEncodingTable(ref subtable) => subtable.codepoints(f),
             Format::HighByteMappingThroughTable(ref subtable) => subtable.codepoints(f),
             Format::SegmentMappingToDeltaValues(ref subtable) => subtable.codepoints(f),
             Format::TrimmedTableMapping(ref subtable) => subtable.codepoints(f),
-            Format::MixedCoverage => {} // unsupported
+            Format::MixedCoverage(ref subtable) => subtable.codepoints(f),
             Format::TrimmedArray(ref subtable) => subtable.codepoints(f),
             Format::SegmentedCoverage(ref subtable) => subtable.codepoints(f),
             Format::ManyToOneRangeMappings(ref subtable) => subtable.codepoints(f),
@@ -201,7 +202,7 @@ impl<'a> Subtables<'a> {
             2 => Format::HighByteMappingThroughTable(Subtable2::parse(data)?),
             4 => Format::SegmentMappingToDeltaValues(Subtable4::parse(data)?),
             6 => Format::TrimmedTableMapping(Subtable6::parse(data)?),
-            8 => Format::MixedCoverage, // unsupported
+            8 => Format::MixedCoverage(Subtable8::parse(data)?),
             10 => Format::TrimmedArray(Subtable10::parse(data)?),
             12 => Format::SegmentedCoverage(Subtable12::parse(data)?),
             13 => Format::ManyToOneRangeMappings(Subtable13::parse(data)?),
@@ -0,0 +1,111 @@
+//! A [CVT Variations Table](
+//! https://docs.microsoft.com/en-us/typography/opentype/spec/cvar) implementation.
+
+#![allow(clippy::neg_cmp_op_on_partial_ord)]
+
+use core::convert::TryFrom;
+
+use crate::gvar::{
+    parse_tuple_variation_header, PackedPointsIter, PackedSingleDeltasIter,
+    TupleVariationStoreHeader,
+};
+use crate::parser::{LazyArray16, Stream};
+use crate::NormalizedCoordinate;
+
+/// A [CVT Variations Table](
+/// https://docs.microsoft.com/en-us/typography/opentype/spec/cvar).
+#[derive(Clone, Copy)]
+pub struct Table<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Table<'a> {
+    /// Parses a table from raw data.
+    pub fn parse(data: &'a [u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+        let version = s.read::<u32>()?;
+        if version != 0x00010000 {
+            return None;
+        }
+
+        Some(Table { data })
+    }
+
+    /// Applies variation deltas to control values.
+    ///
+    /// `values` must be already filled with the `cvt ` table values.
+    /// Values without deltas are left untouched.
+    ///
+    /// Deltas are not rounded.
+    pub fn apply(&self, coordinates: &[NormalizedCoordinate], values: &mut [f32]) -> Option<()> {
+        // The tuple variation store header follows the version.
+        let TupleVariationStoreHeader {
+            tuple_variation_count,
+            shared_point_numbers,
+            mut main_s,
+            mut serialized_s,
+        } = TupleVariationStoreHeader::parse(self.data, 4)?;
+
+        // `cvar` doesn't store the number of axes, so it's taken from `fvar` via coordinates.
+        let axis_count = u16::try_from(coordinates.len()).ok()?;
+
+        // `cvar` has no shared tuples, therefore all tuples have embedded peaks.
+        let shared_tuple_records = LazyArray16::default();
+        for _ in 0..tuple_variation_count {
+            let header = parse_tuple_variation_header(
+                axis_count,
+                coordinates,
+                &shared_tuple_records,
+                &mut main_s,
+            )?;
+            if !(header.scalar > 0.0) {
+                // Serialized data for headers with non-positive scalar should be skipped.
+                serialized_s.advance(usize::from(header.serialized_data_len));
+                continue;
+            }
+
+            let serialized_data_start = serialized_s.offset();
+
+            // Resolve point numbers source.
+            let point_numbers = if header.has_private_point_numbers {
+                PackedPointsIter::new(&mut serialized_s)?
+            } else {
+                shared_point_numbers
+            };
+
+            // Use `checked_sub` in case we went over the `serialized_data_len`.
+            let left = usize::from(header.serialized_data_len)
+                .checked_sub(serialized_s.offset() - serialized_data_start)?;
+            let deltas_data = serialized_s.read_bytes(left)?;
+            let deltas = PackedSingleDeltasIter::new(header.scalar, deltas_data);
+
+            if let Some(point_numbers) = point_numbers {
+                // Point numbers are stored as differences from the previous one.
+                let mut index = 0u16;
+                for (point_delta, delta) in point_numbers.zip(deltas) {
+                    index = match index.checked_add(point_delta) {
+                        Some(v) => v,
+                        None => break,
+                    };
+
+                    if let Some(value) = values.get_mut(usize::from(index)) {
+                        *value += delta;
+                    }
+                }
+            } else {
+                // No point numbers means that all values have deltas.
+                for (value, delta) in values.iter_mut().zip(deltas) {
+                    *value += delta;
+                }
+            }
+        }
+
+        Some(())
+    }
+}
+
+impl core::fmt::Debug for Table<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Table {{ ... }}")
+    }
+}
@@ -0,0 +1,27 @@
+//! A [Control Value Table](
+//! https://docs.microsoft.com/en-us/typography/opentype/spec/cvt) implementation.
+
+use core::convert::TryFrom;
+
+use crate::parser::{LazyArray16, Stream};
+
+/// A [Control Value Table](https://docs.microsoft.com/en-us/typography/opentype/spec/cvt).
+#[derive(Clone, Copy, Debug)]
+pub struct Table<'a> {
+    /// A list of `FWORD` values, i.e. in font units.
+    ///
+    /// Referenced by TrueType instructions.
+    pub values: LazyArray16<'a, i16>,
+}
+
+impl<'a> Table<'a> {
+    /// Parses a table from raw data.
+    ///
+    /// Values past the 65535th one are ignored.
+    pub fn parse(data: &'a [u8]) -> Option<Self> {
+        let count = u16::try_from(data.len() / 2).unwrap_or(u16::MAX);
+        let mut s = Stream::new(data);
+        let values = s.read_array16::<i16>(count)?;
+        Some(Table { values })
+    }
+}
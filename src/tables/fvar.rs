@@ -62,12 +62,116 @@ impl VariationAxis {
     }
 }
 
+/// A [named instance](
+/// https://docs.microsoft.com/en-us/typography/opentype/spec/fvar#instancerecord).
+#[derive(Clone, Copy, Debug)]
+pub struct NamedInstance<'a> {
+    /// An instance subfamily name in the `name` table.
+    pub subfamily_name_id: u16,
+    /// An instance PostScript name in the `name` table.
+    pub post_script_name_id: Option<u16>,
+    /// User-space coordinates, one for each axis in the `fvar` axes order.
+    pub coordinates: LazyArray16<'a, Fixed>,
+}
+
+/// A list of [named instances](
+/// https://docs.microsoft.com/en-us/typography/opentype/spec/fvar#instancerecord).
+#[derive(Clone, Copy, Default)]
+pub struct NamedInstances<'a> {
+    data: &'a [u8],
+    count: u16,
+    record_size: u16,
+    axis_count: u16,
+}
+
+impl<'a> NamedInstances<'a> {
+    /// Returns an instance at `index`.
+    pub fn get(&self, index: u16) -> Option<NamedInstance<'a>> {
+        if index >= self.count {
+            return None;
+        }
+
+        let start = usize::from(index) * usize::from(self.record_size);
+        let data = self
+            .data
+            .get(start..start + usize::from(self.record_size))?;
+        let mut s = Stream::new(data);
+        let subfamily_name_id = s.read::<u16>()?;
+        s.skip::<u16>(); // flags
+        let coordinates = s.read_array16::<Fixed>(self.axis_count)?;
+        // The PostScript name ID is present only when the record is large enough.
+        // 0xFFFF indicates that there is no PostScript name.
+        let post_script_name_id = s.read::<u16>().filter(|id| *id != 0xFFFF);
+
+        Some(NamedInstance {
+            subfamily_name_id,
+            post_script_name_id,
+            coordinates,
+        })
+    }
+
+    /// Returns the number of instances.
+    #[inline]
+    pub fn len(&self) -> u16 {
+        self.count
+    }
+
+    /// Checks if there are any instances.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+}
+
+impl core::fmt::Debug for NamedInstances<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_list().entries(*self).finish()
+    }
+}
+
+impl<'a> IntoIterator for NamedInstances<'a> {
+    type Item = NamedInstance<'a>;
+    type IntoIter = NamedInstancesIter<'a>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        NamedInstancesIter {
+            instances: self,
+            index: 0,
+        }
+    }
+}
+
+/// An iterator over named instances.
+#[derive(Clone, Copy)]
+#[allow(missing_debug_implementations)]
+pub struct NamedInstancesIter<'a> {
+    instances: NamedInstances<'a>,
+    index: u16,
+}
+
+impl<'a> Iterator for NamedInstancesIter<'a> {
+    type Item = NamedInstance<'a>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index < self.instances.len() {
+            self.index += 1;
+            self.instances.get(self.index - 1)
+        } else {
+            None
+        }
+    }
+}
+
 /// A [Font Variations Table](
 /// https://docs.microsoft.com/en-us/typography/opentype/spec/fvar).
 #[derive(Clone, Copy, Debug)]
 pub struct Table<'a> {
     /// A list of variation axes.
     pub axes: LazyArray16<'a, VariationAxis>,
+    /// A list of named instances.
+    pub instances: NamedInstances<'a>,
 }
 
 impl<'a> Table<'a> {
@@ -82,6 +186,9 @@ impl<'a> Table<'a> {
         let axes_array_offset = s.read::<Offset16>()?;
         s.skip::<u16>(); // reserved
         let axis_count = s.read::<u16>()?;
+        s.skip::<u16>(); // axisSize
+        let instance_count = s.read::<u16>()?;
+        let instance_size = s.read::<u16>()?;
 
         // 'If axisCount is zero, then the font is not functional as a variable font,
         // and must be treated as a non-variable font;
@@ -91,6 +198,20 @@ impl<'a> Table<'a> {
         let mut s = Stream::new_at(data, axes_array_offset.to_usize())?;
         let axes = s.read_array16::<VariationAxis>(axis_count.get())?;
 
-        Some(Table { axes })
+        // Instance records immediately follow the axes array.
+        // Malformed records are ignored instead of rejecting the whole table.
+        let min_instance_size = usize::from(axis_count.get()) * Fixed::SIZE + 4;
+        let instances = s
+            .read_bytes(usize::from(instance_count) * usize::from(instance_size))
+            .filter(|_| usize::from(instance_size) >= min_instance_size)
+            .map(|data| NamedInstances {
+                data,
+                count: instance_count,
+                record_size: instance_size,
+                axis_count: axis_count.get(),
+            })
+            .unwrap_or_default();
+
+        Some(Table { axes, instances })
     }
 }
@@ -0,0 +1,97 @@
+//! A [Grid-fitting and Scan-conversion Procedure Table](
+//! https://docs.microsoft.com/en-us/typography/opentype/spec/gasp) implementation.
+
+use crate::parser::{FromData, LazyArray16, Stream};
+
+/// A [rendering behavior](
+/// https://docs.microsoft.com/en-us/typography/opentype/spec/gasp#gasp-range-record-format).
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub struct Behavior(pub u16);
+
+impl Behavior {
+    /// Use gridfitting.
+    #[inline]
+    pub fn gridfit(self) -> bool {
+        self.0 & 0x0001 != 0
+    }
+
+    /// Use grayscale rendering.
+    #[inline]
+    pub fn do_gray(self) -> bool {
+        self.0 & 0x0002 != 0
+    }
+
+    /// Use gridfitting with ClearType symmetric smoothing.
+    ///
+    /// Only supported in version 1 `gasp`.
+    #[inline]
+    pub fn symmetric_gridfit(self) -> bool {
+        self.0 & 0x0004 != 0
+    }
+
+    /// Use smoothing along multiple axes with ClearType.
+    ///
+    /// Only supported in version 1 `gasp`.
+    #[inline]
+    pub fn symmetric_smoothing(self) -> bool {
+        self.0 & 0x0008 != 0
+    }
+}
+
+/// A gasp range record.
+#[derive(Clone, Copy, Debug)]
+pub struct Range {
+    /// Upper limit of range, in PPEM.
+    pub max_ppem: u16,
+    /// Behavior for the range.
+    pub behavior: Behavior,
+}
+
+impl FromData for Range {
+    const SIZE: usize = 4;
+
+    #[inline]
+    fn parse(data: &[u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+        Some(Range {
+            max_ppem: s.read::<u16>()?,
+            behavior: Behavior(s.read::<u16>()?),
+        })
+    }
+}
+
+/// A [Grid-fitting and Scan-conversion Procedure Table](
+/// https://docs.microsoft.com/en-us/typography/opentype/spec/gasp).
+#[derive(Clone, Copy, Debug)]
+pub struct Table<'a> {
+    /// A list of ranges.
+    ///
+    /// Sorted by `max_ppem`.
+    pub ranges: LazyArray16<'a, Range>,
+}
+
+impl<'a> Table<'a> {
+    /// Parses a table from raw data.
+    pub fn parse(data: &'a [u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+        let version = s.read::<u16>()?;
+        if version > 1 {
+            return None;
+        }
+
+        let count = s.read::<u16>()?;
+        let ranges = s.read_array16::<Range>(count)?;
+
+        Some(Table { ranges })
+    }
+
+    /// Returns a rendering behavior for the specified pixels-per-em.
+    ///
+    /// Returns `None` when `ppem` is greater than the last range limit.
+    pub fn behavior(&self, ppem: u16) -> Option<Behavior> {
+        self.ranges
+            .into_iter()
+            .find(|range| ppem <= range.max_ppem)
+            .map(|range| range.behavior)
+    }
+}
@@ -1,6 +1,7 @@
 //! A [Glyph Definition Table](
 //! https://docs.microsoft.com/en-us/typography/opentype/spec/gdef) implementation.
 
+use crate::gpos::Device;
 use crate::opentype_layout::{Class, ClassDefinition, Coverage};
 use crate::parser::{FromSlice, LazyArray16, Offset, Offset16, Offset32, Stream};
 use crate::GlyphId;
@@ -20,11 +21,159 @@ pub enum GlyphClass {
     Component = 4,
 }
 
+/// A [Caret Value Table](https://docs.microsoft.com/en-us/typography/opentype/spec/gdef#caret-value-tables).
+#[derive(Clone, Copy, Debug)]
+pub struct CaretValue<'a> {
+    /// X or Y value, in design units, depending on the text direction.
+    ///
+    /// Always zero for the *Caret Value Format 2*.
+    pub coordinate: i16,
+    /// An index to a glyph contour point.
+    ///
+    /// Set only for the *Caret Value Format 2*.
+    pub contour_point: Option<u16>,
+    /// A [`Device`] table with coordinate adjustment.
+    ///
+    /// Set only for the *Caret Value Format 3*.
+    pub device: Option<Device<'a>>,
+}
+
+impl<'a> CaretValue<'a> {
+    fn parse(data: &'a [u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+        let format = s.read::<u16>()?;
+        match format {
+            1 => Some(CaretValue {
+                coordinate: s.read::<i16>()?,
+                contour_point: None,
+                device: None,
+            }),
+            2 => Some(CaretValue {
+                coordinate: 0,
+                contour_point: Some(s.read::<u16>()?),
+                device: None,
+            }),
+            3 => Some(CaretValue {
+                coordinate: s.read::<i16>()?,
+                contour_point: None,
+                device: s
+                    .read::<Option<Offset16>>()?
+                    .and_then(|offset| data.get(offset.to_usize()..))
+                    .and_then(Device::parse),
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// A list of ligature caret values.
+#[derive(Clone, Copy)]
+pub struct LigatureCarets<'a> {
+    data: &'a [u8],
+    offsets: LazyArray16<'a, Offset16>,
+}
+
+impl<'a> LigatureCarets<'a> {
+    fn parse(data: &'a [u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+        let count = s.read::<u16>()?;
+        let offsets = s.read_array16(count)?;
+        Some(Self { data, offsets })
+    }
+
+    /// Returns a [`CaretValue`] at index.
+    pub fn get(&self, index: u16) -> Option<CaretValue<'a>> {
+        let offset = self.offsets.get(index)?.to_usize();
+        self.data.get(offset..).and_then(CaretValue::parse)
+    }
+
+    /// Returns the number of carets.
+    pub fn len(&self) -> u16 {
+        self.offsets.len()
+    }
+
+    /// Checks if there are any carets.
+    pub fn is_empty(&self) -> bool {
+        self.offsets.is_empty()
+    }
+}
+
+impl core::fmt::Debug for LigatureCarets<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_list().entries(*self).finish()
+    }
+}
+
+impl<'a> IntoIterator for LigatureCarets<'a> {
+    type Item = CaretValue<'a>;
+    type IntoIter = LigatureCaretsIter<'a>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        LigatureCaretsIter {
+            carets: self,
+            index: 0,
+        }
+    }
+}
+
+/// An iterator over [`LigatureCarets`].
+#[allow(missing_debug_implementations)]
+pub struct LigatureCaretsIter<'a> {
+    carets: LigatureCarets<'a>,
+    index: u16,
+}
+
+impl<'a> Iterator for LigatureCaretsIter<'a> {
+    type Item = CaretValue<'a>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index < self.carets.len() {
+            self.index += 1;
+            self.carets.get(self.index - 1)
+        } else {
+            None
+        }
+    }
+}
+
+// A list of offsets to per-glyph tables, indexed by coverage.
+// Used by both `AttachList` and `LigCaretList`.
+#[derive(Clone, Copy)]
+struct CoverageOffsets<'a> {
+    data: &'a [u8],
+    coverage: Coverage<'a>,
+    offsets: LazyArray16<'a, Offset16>,
+}
+
+impl<'a> CoverageOffsets<'a> {
+    fn parse(data: &'a [u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+        let coverage = Coverage::parse(s.read_at_offset16(data)?)?;
+        let count = s.read::<u16>()?;
+        let offsets = s.read_array16(count)?;
+        Some(Self {
+            data,
+            coverage,
+            offsets,
+        })
+    }
+
+    fn get(&self, glyph_id: GlyphId) -> Option<&'a [u8]> {
+        let index = self.coverage.get(glyph_id)?;
+        let offset = self.offsets.get(index)?;
+        self.data.get(offset.to_usize()..)
+    }
+}
+
 /// A [Glyph Definition Table](https://docs.microsoft.com/en-us/typography/opentype/spec/gdef).
 #[allow(missing_debug_implementations)]
 #[derive(Clone, Copy, Default)]
 pub struct Table<'a> {
     glyph_classes: Option<ClassDefinition<'a>>,
+    attach_list: Option<CoverageOffsets<'a>>,
+    lig_caret_list: Option<CoverageOffsets<'a>>,
     mark_attach_classes: Option<ClassDefinition<'a>>,
     mark_glyph_coverage_offsets: Option<(&'a [u8], LazyArray16<'a, Offset32>)>,
     #[cfg(feature = "variable-fonts")]
@@ -41,8 +190,8 @@ impl<'a> Table<'a> {
         }
 
         let glyph_class_def_offset = s.read::<Option<Offset16>>()?;
-        s.skip::<Offset16>(); // attachListOffset
-        s.skip::<Offset16>(); // ligCaretListOffset
+        let attach_list_offset = s.read::<Option<Offset16>>()?;
+        let lig_caret_list_offset = s.read::<Option<Offset16>>()?;
         let mark_attach_class_def_offset = s.read::<Option<Offset16>>()?;
 
         let mut mark_glyph_sets_def_offset: Option<Offset16> = None;
@@ -69,6 +218,18 @@ impl<'a> Table<'a> {
             }
         }
 
+        if let Some(offset) = attach_list_offset {
+            if let Some(subdata) = data.get(offset.to_usize()..) {
+                table.attach_list = CoverageOffsets::parse(subdata);
+            }
+        }
+
+        if let Some(offset) = lig_caret_list_offset {
+            if let Some(subdata) = data.get(offset.to_usize()..) {
+                table.lig_caret_list = CoverageOffsets::parse(subdata);
+            }
+        }
+
         if let Some(offset) = mark_attach_class_def_offset {
             if let Some(subdata) = data.get(offset.to_usize()..) {
                 table.mark_attach_classes = ClassDefinition::parse(subdata);
@@ -150,6 +311,28 @@ impl<'a> Table<'a> {
         is_mark_glyph_impl(self, glyph_id, set_index).is_some()
     }
 
+    /// Returns glyph's attachment points according to
+    /// [Attachment Point List Table](
+    /// https://docs.microsoft.com/en-us/typography/opentype/spec/gdef#attachment-point-list-table).
+    ///
+    /// Each value is an index to a glyph contour point.
+    #[inline]
+    pub fn glyph_attachment_points(&self, glyph_id: GlyphId) -> Option<LazyArray16<'a, u16>> {
+        let data = self.attach_list?.get(glyph_id)?;
+        let mut s = Stream::new(data);
+        let count = s.read::<u16>()?;
+        s.read_array16(count)
+    }
+
+    /// Returns ligature glyph's caret values according to
+    /// [Ligature Caret List Table](
+    /// https://docs.microsoft.com/en-us/typography/opentype/spec/gdef#ligature-caret-list-table).
+    #[inline]
+    pub fn glyph_ligature_carets(&self, glyph_id: GlyphId) -> Option<LigatureCarets<'a>> {
+        let data = self.lig_caret_list?.get(glyph_id)?;
+        LigatureCarets::parse(data)
+    }
+
     /// Returns glyph's variation delta at a specified index according to
     /// [Item Variation Store Table](
     /// https://docs.microsoft.com/en-us/typography/opentype/spec/gdef#item-variation-store-table).
@@ -4,7 +4,7 @@
 use core::num::NonZeroU16;
 
 use crate::parser::{LazyArray16, NumFrom, Stream, F2DOT14};
-use crate::{loca, GlyphId, OutlineBuilder, Rect, RectF, Transform};
+use crate::{loca, GlyphId, OutlineBuilder, PointF, Rect, RectF, Transform};
 
 pub(crate) struct Builder<'a> {
     pub builder: &'a mut dyn OutlineBuilder,
@@ -137,22 +137,30 @@ impl<'a> Builder<'a> {
     }
 }
 
+/// A composite glyph component.
 #[derive(Clone, Copy, Debug)]
-pub(crate) struct CompositeGlyphInfo {
+pub struct CompositeGlyphInfo {
+    /// Component glyph ID.
     pub glyph_id: GlyphId,
+    /// Component transform.
+    ///
+    /// The offset is stored in `e` and `f` and is zero when `matching_points` is set.
     pub transform: Transform,
-    #[allow(dead_code)]
+    /// Parent and child points to align, when offsets are not stored as xy values.
+    pub matching_points: Option<(u16, u16)>,
+    /// Component flags.
     pub flags: CompositeGlyphFlags,
 }
 
-#[derive(Clone)]
-pub(crate) struct CompositeGlyphIter<'a> {
+/// An iterator over composite glyph components.
+#[derive(Clone, Debug)]
+pub struct CompositeGlyphIter<'a> {
     stream: Stream<'a>,
 }
 
 impl<'a> CompositeGlyphIter<'a> {
     #[inline]
-    pub fn new(data: &'a [u8]) -> Self {
+    pub(crate) fn new(data: &'a [u8]) -> Self {
         CompositeGlyphIter {
             stream: Stream::new(data),
         }
@@ -168,6 +176,7 @@ impl<'a> Iterator for CompositeGlyphIter<'a> {
         let glyph_id = self.stream.read::<GlyphId>()?;
 
         let mut ts = Transform::default();
+        let mut matching_points = None;
 
         if flags.args_are_xy_values() {
             if flags.arg_1_and_2_are_words() {
@@ -177,6 +186,13 @@ impl<'a> Iterator for CompositeGlyphIter<'a> {
                 ts.e = f32::from(self.stream.read::<i8>()?);
                 ts.f = f32::from(self.stream.read::<i8>()?);
             }
+        } else if flags.arg_1_and_2_are_words() {
+            matching_points = Some((self.stream.read::<u16>()?, self.stream.read::<u16>()?));
+        } else {
+            matching_points = Some((
+                u16::from(self.stream.read::<u8>()?),
+                u16::from(self.stream.read::<u8>()?),
+            ));
         }
 
         if flags.we_have_a_two_by_two() {
@@ -200,6 +216,7 @@ impl<'a> Iterator for CompositeGlyphIter<'a> {
         Some(CompositeGlyphInfo {
             glyph_id,
             transform: ts,
+            matching_points,
             flags,
         })
     }
@@ -209,33 +226,50 @@ impl<'a> Iterator for CompositeGlyphIter<'a> {
 // makes the code ~10% slower. At least on my machine.
 // I guess it's due to the fact that with i16 the struct
 // fits into the machine word.
-#[derive(Clone, Copy, Debug)]
-pub(crate) struct GlyphPoint {
+/// A simple glyph point.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct GlyphPoint {
+    /// X coordinate.
     pub x: i16,
+    /// Y coordinate.
     pub y: i16,
     /// Indicates that a point is a point on curve
     /// and not a control point.
     pub on_curve_point: bool,
+    /// Indicates that a point is the last point of a contour.
     pub last_point: bool,
 }
 
+/// An iterator over simple glyph points.
 #[derive(Clone, Default)]
-pub(crate) struct GlyphPointsIter<'a> {
+pub struct GlyphPointsIter<'a> {
     endpoints: EndpointsIter<'a>,
     flags: FlagsIter<'a>,
     x_coords: CoordsIter<'a>,
     y_coords: CoordsIter<'a>,
-    pub points_left: u16, // Number of points left in the glyph.
+    pub(crate) points_left: u16, // Number of points left in the glyph.
 }
 
-#[cfg(feature = "variable-fonts")]
 impl GlyphPointsIter<'_> {
+    /// Returns the number of points left.
+    #[inline]
+    pub fn points_left(&self) -> u16 {
+        self.points_left
+    }
+
+    #[cfg(feature = "variable-fonts")]
     #[inline]
-    pub fn current_contour(&self) -> u16 {
+    pub(crate) fn current_contour(&self) -> u16 {
         self.endpoints.index - 1
     }
 }
 
+impl core::fmt::Debug for GlyphPointsIter<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "GlyphPointsIter {{ ... }}")
+    }
+}
+
 impl<'a> Iterator for GlyphPointsIter<'a> {
     type Item = GlyphPoint;
 
@@ -410,18 +444,37 @@ impl SimpleGlyphFlags {
     #[inline] fn y_is_same_or_positive_short(self) -> bool { self.0 & 0x20 != 0 }
 }
 
-// https://docs.microsoft.com/en-us/typography/opentype/spec/glyf#composite-glyph-description
-#[derive(Clone, Copy, Debug)]
-pub(crate) struct CompositeGlyphFlags(u16);
+/// [Composite glyph flags](
+/// https://docs.microsoft.com/en-us/typography/opentype/spec/glyf#composite-glyph-description).
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct CompositeGlyphFlags(pub u16);
 
 #[rustfmt::skip]
 impl CompositeGlyphFlags {
+    /// Arguments are 16-bit.
     #[inline] pub fn arg_1_and_2_are_words(self) -> bool { self.0 & 0x0001 != 0 }
+    /// Arguments are offsets and not point numbers.
     #[inline] pub fn args_are_xy_values(self) -> bool { self.0 & 0x0002 != 0 }
+    /// Offsets should be rounded to the grid.
+    #[inline] pub fn round_xy_to_grid(self) -> bool { self.0 & 0x0004 != 0 }
+    /// The component has a single scale.
     #[inline] pub fn we_have_a_scale(self) -> bool { self.0 & 0x0008 != 0 }
+    /// More components follow this one.
     #[inline] pub fn more_components(self) -> bool { self.0 & 0x0020 != 0 }
+    /// The component has separate x and y scales.
     #[inline] pub fn we_have_an_x_and_y_scale(self) -> bool { self.0 & 0x0040 != 0 }
+    /// The component has a 2 by 2 transformation.
     #[inline] pub fn we_have_a_two_by_two(self) -> bool { self.0 & 0x0080 != 0 }
+    /// Instructions follow the last component.
+    #[inline] pub fn we_have_instructions(self) -> bool { self.0 & 0x0100 != 0 }
+    /// The composite glyph uses metrics of this component.
+    #[inline] pub fn use_my_metrics(self) -> bool { self.0 & 0x0200 != 0 }
+    /// Components of the composite glyph overlap.
+    #[inline] pub fn overlap_compound(self) -> bool { self.0 & 0x0400 != 0 }
+    /// The component offset is scaled.
+    #[inline] pub fn scaled_component_offset(self) -> bool { self.0 & 0x0800 != 0 }
+    /// The component offset is not scaled.
+    #[inline] pub fn unscaled_component_offset(self) -> bool { self.0 & 0x1000 != 0 }
 }
 
 // It's not defined in the spec, so we are using our own value.
@@ -480,22 +533,83 @@ fn outline_impl(
     Some(builder.bbox.to_rect())
 }
 
+// Returns `Some(None)` when the point is not present in this glyph.
+// In which case `index` is decremented by the number of glyph points.
+fn contour_point_impl(
+    glyf_table: &Table,
+    data: &[u8],
+    index: &mut u16,
+    transform: Transform,
+    depth: u8,
+) -> Option<Option<PointF>> {
+    if depth >= MAX_COMPONENTS {
+        return None;
+    }
+
+    let mut s = Stream::new(data);
+    let number_of_contours = s.read::<i16>()?;
+    s.advance(8); // Skip bbox.
+
+    if number_of_contours > 0 {
+        // Simple glyph.
+        let number_of_contours = NonZeroU16::new(number_of_contours as u16)?;
+        let mut points = parse_simple_outline(s.tail()?, number_of_contours)?;
+        if *index >= points.points_left {
+            *index -= points.points_left;
+            return Some(None);
+        }
+
+        let point = points.nth(usize::from(*index))?;
+        let mut x = f32::from(point.x);
+        let mut y = f32::from(point.y);
+        transform.apply_to(&mut x, &mut y);
+        Some(Some(PointF { x, y }))
+    } else if number_of_contours < 0 {
+        // Composite glyph.
+        for comp in CompositeGlyphIter::new(s.tail()?) {
+            if let Some(glyph_data) = glyf_table.get(comp.glyph_id) {
+                let transform = Transform::combine(transform, comp.transform);
+                let point =
+                    contour_point_impl(glyf_table, glyph_data, index, transform, depth + 1)?;
+                if point.is_some() {
+                    return Some(point);
+                }
+            }
+        }
+
+        Some(None)
+    } else {
+        // An empty glyph.
+        Some(None)
+    }
+}
+
 #[inline]
 pub(crate) fn parse_simple_outline(
     glyph_data: &[u8],
     number_of_contours: NonZeroU16,
 ) -> Option<GlyphPointsIter> {
-    let mut s = Stream::new(glyph_data);
-    let endpoints = s.read_array16::<u16>(number_of_contours.get())?;
-
-    let points_total = endpoints.last()?.checked_add(1)?;
+    let points = parse_simple_points(glyph_data, number_of_contours)?;
 
     // Contours with a single point should be ignored.
     // But this is not an error, so we should return an "empty" iterator.
-    if points_total == 1 {
+    if points.points_left == 1 {
         return Some(GlyphPointsIter::default());
     }
 
+    Some(points)
+}
+
+/// Parses all simple glyph points, unlike `parse_simple_outline`.
+fn parse_simple_points(
+    glyph_data: &[u8],
+    number_of_contours: NonZeroU16,
+) -> Option<GlyphPointsIter<'_>> {
+    let mut s = Stream::new(glyph_data);
+    let endpoints = s.read_array16::<u16>(number_of_contours.get())?;
+
+    let points_total = endpoints.last()?.checked_add(1)?;
+
     // Skip instructions byte code.
     let instructions_len = s.read::<u16>()?;
     s.advance(usize::from(instructions_len));
@@ -515,6 +629,49 @@ pub(crate) fn parse_simple_outline(
     })
 }
 
+/// Returns glyph's instructions byte code.
+///
+/// `data` must include the glyph header.
+pub(crate) fn glyph_instructions(data: &[u8]) -> Option<&[u8]> {
+    let mut s = Stream::new(data);
+    let number_of_contours = s.read::<i16>()?;
+    s.advance(8); // Skip bbox.
+
+    if number_of_contours > 0 {
+        // Simple glyph.
+        s.advance(usize::from(number_of_contours as u16) * 2); // Skip endpoints.
+    } else if number_of_contours < 0 {
+        // Composite glyph.
+        // Instructions are stored after the last component.
+        loop {
+            let flags = CompositeGlyphFlags(s.read::<u16>()?);
+            s.skip::<GlyphId>();
+            s.advance(if flags.arg_1_and_2_are_words() { 4 } else { 2 });
+            if flags.we_have_a_two_by_two() {
+                s.advance(8);
+            } else if flags.we_have_an_x_and_y_scale() {
+                s.advance(4);
+            } else if flags.we_have_a_scale() {
+                s.advance(2);
+            }
+
+            if !flags.more_components() {
+                if !flags.we_have_instructions() {
+                    return None;
+                }
+
+                break;
+            }
+        }
+    } else {
+        // An empty glyph.
+        return None;
+    }
+
+    let len = s.read::<u16>()?;
+    s.read_bytes(usize::from(len))
+}
+
 /// Resolves coordinate arrays length.
 ///
 /// The length depends on *Simple Glyph Flags*, so we have to process them all to find it.
@@ -618,12 +775,83 @@ impl<'a> Table<'a> {
         })
     }
 
+    /// Returns glyph's TrueType instructions.
+    ///
+    /// Both simple and composite glyphs can have instructions.
+    /// Instructions of composite glyph components are not included.
+    ///
+    /// Returns `None` when the glyph is empty or has no instructions.
+    /// Use `truetype_instructions::Instructions` to decode them.
+    #[inline]
+    pub fn glyph_instructions(&self, glyph_id: GlyphId) -> Option<&'a [u8]> {
+        let data = self.get(glyph_id)?;
+        glyph_instructions(data).filter(|data| !data.is_empty())
+    }
+
+    /// Returns simple glyph points.
+    ///
+    /// Points are not transformed or filtered, so contours with a single point are included.
+    ///
+    /// Returns `None` when the glyph is empty or composite.
+    #[inline]
+    pub fn glyph_points(&self, glyph_id: GlyphId) -> Option<GlyphPointsIter<'a>> {
+        let (number_of_contours, data) = self.simple_glyph(glyph_id)?;
+        parse_simple_points(data, number_of_contours)
+    }
+
+    /// Returns simple glyph contours end point indices.
+    ///
+    /// Returns `None` when the glyph is empty or composite.
+    #[inline]
+    pub fn glyph_contour_end_points(&self, glyph_id: GlyphId) -> Option<LazyArray16<'a, u16>> {
+        let (number_of_contours, data) = self.simple_glyph(glyph_id)?;
+        Stream::new(data).read_array16::<u16>(number_of_contours.get())
+    }
+
+    /// Returns composite glyph components.
+    ///
+    /// Components are not resolved recursively.
+    ///
+    /// Returns `None` when the glyph is empty or simple.
+    #[inline]
+    pub fn glyph_components(&self, glyph_id: GlyphId) -> Option<CompositeGlyphIter<'a>> {
+        let mut s = Stream::new(self.get(glyph_id)?);
+        let number_of_contours = s.read::<i16>()?;
+        s.advance(8); // Skip bbox.
+        if number_of_contours < 0 {
+            Some(CompositeGlyphIter::new(s.tail()?))
+        } else {
+            None
+        }
+    }
+
+    fn simple_glyph(&self, glyph_id: GlyphId) -> Option<(NonZeroU16, &'a [u8])> {
+        let mut s = Stream::new(self.get(glyph_id)?);
+        let number_of_contours = s.read::<i16>()?;
+        s.advance(8); // Skip bbox.
+        if number_of_contours > 0 {
+            Some((NonZeroU16::new(number_of_contours as u16)?, s.tail()?))
+        } else {
+            None
+        }
+    }
+
     #[inline]
     pub(crate) fn get(&self, glyph_id: GlyphId) -> Option<&'a [u8]> {
         let range = self.loca_table.glyph_range(glyph_id)?;
         self.data.get(range)
     }
 
+    /// Returns a glyph point at the specified index.
+    ///
+    /// Composite glyph points are indexed in the order of their components,
+    /// after applying components transforms.
+    pub(crate) fn contour_point(&self, glyph_id: GlyphId, index: u16) -> Option<PointF> {
+        let data = self.get(glyph_id)?;
+        let mut index = index;
+        contour_point_impl(self, data, &mut index, Transform::default(), 0)?
+    }
+
     /// Returns the number of points in this outline.
     pub(crate) fn outline_points(&self, glyph_id: GlyphId) -> u16 {
         self.outline_points_impl(glyph_id).unwrap_or(0)
@@ -834,13 +834,18 @@ impl core::fmt::Debug for MarkArray<'_> {
 
 /// An [Anchor Table](https://docs.microsoft.com/en-us/typography/opentype/spec/gpos#anchor-tables).
 ///
-/// The *Anchor Table Format 2: Design Units Plus Contour Point* is not supported.
+/// Use [`Face::glyph_anchor`](../struct.Face.html#method.glyph_anchor) to resolve
+/// the actual anchor position.
 #[derive(Clone, Copy, Debug)]
 pub struct Anchor<'a> {
     /// Horizontal value, in design units.
     pub x: i16,
     /// Vertical value, in design units.
     pub y: i16,
+    /// An index to a glyph contour point.
+    ///
+    /// Set only for the *Anchor Table Format 2: Design Units Plus Contour Point*.
+    pub contour_point: Option<u16>,
     /// A [`Device`] table with horizontal value.
     pub x_device: Option<Device<'a>>,
     /// A [`Device`] table with vertical value.
@@ -858,14 +863,14 @@ impl<'a> Anchor<'a> {
         let mut table = Anchor {
             x: s.read::<i16>()?,
             y: s.read::<i16>()?,
+            contour_point: None,
             x_device: None,
             y_device: None,
         };
 
-        // Note: Format 2 is not handled since there is currently no way to
-        // get a glyph contour point by index.
-
-        if format == 3 {
+        if format == 2 {
+            table.contour_point = Some(s.read::<u16>()?);
+        } else if format == 3 {
             table.x_device = s
                 .read::<Option<Offset16>>()?
                 .and_then(|offset| data.get(offset.to_usize()..))
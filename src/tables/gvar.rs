@@ -12,6 +12,7 @@ use core::convert::TryFrom;
 use core::num::NonZeroU16;
 
 use crate::parser::{LazyArray16, Offset, Offset16, Offset32, Stream, F2DOT14};
+use crate::var_store::RegionAxisCoordinates;
 use crate::{glyf, PhantomPoints, PointF};
 use crate::{GlyphId, NormalizedCoordinate, OutlineBuilder, Rect, RectF, Transform};
 
@@ -262,18 +263,66 @@ impl<'a> VariationTuples<'a> {
     }
 }
 
+// https://docs.microsoft.com/en-us/typography/opentype/spec/otvarcommonformats#tuple-variation-store-header
+#[derive(Clone)]
+pub(crate) struct TupleVariationStoreHeader<'a> {
+    pub tuple_variation_count: u16,
+    pub shared_point_numbers: Option<PackedPointsIter<'a>>,
+    // Tuple variation headers.
+    pub main_s: Stream<'a>,
+    // Serialized data after shared point numbers.
+    pub serialized_s: Stream<'a>,
+}
+
+impl<'a> TupleVariationStoreHeader<'a> {
+    // Parses a header at `offset`. Serialized data offset is relative to the start of `data`.
+    pub fn parse(data: &'a [u8], offset: usize) -> Option<Self> {
+        const SHARED_POINT_NUMBERS_FLAG: u16 = 0x8000;
+        const COUNT_MASK: u16 = 0x0FFF;
+
+        let mut main_s = Stream::new_at(data, offset)?;
+        let tuple_variation_count = main_s.read::<u16>()?;
+        let data_offset = main_s.read::<Offset16>()?;
+
+        // A variation data consists of three parts: header + variation tuples + serialized data.
+        // Each tuple has it's own chunk in the serialized data.
+        // Because of that, we are using two parsing streams: one for tuples and one for serialized data.
+        // So we can parse them in parallel and avoid needless allocations.
+        let mut serialized_s = Stream::new_at(data, data_offset.to_usize())?;
+
+        // 'The high 4 bits are flags, and the low 12 bits
+        // are the number of tuple variation tables.'
+        //
+        // All tuples in the variation data can reference the same point numbers,
+        // which are defined at the start of the serialized data.
+        let shared_point_numbers = if tuple_variation_count & SHARED_POINT_NUMBERS_FLAG != 0 {
+            PackedPointsIter::new(&mut serialized_s)?
+        } else {
+            None
+        };
+
+        Some(TupleVariationStoreHeader {
+            tuple_variation_count: tuple_variation_count & COUNT_MASK,
+            shared_point_numbers,
+            main_s,
+            serialized_s,
+        })
+    }
+}
+
 #[derive(Clone, Copy, Default, Debug)]
-struct TupleVariationHeaderData {
-    scalar: f32,
-    has_private_point_numbers: bool,
-    serialized_data_len: u16,
+pub(crate) struct TupleVariationHeaderData {
+    pub scalar: f32,
+    pub has_private_point_numbers: bool,
+    pub serialized_data_len: u16,
 }
 
 // https://docs.microsoft.com/en-us/typography/opentype/spec/otvarcommonformats#tuplevariationheader
 fn parse_variation_tuples<'a>(
     count: u16,
+    axis_count: u16,
     coordinates: &[NormalizedCoordinate],
-    shared_tuple_records: &LazyArray16<F2DOT14>,
+    shared_tuple_records: &LazyArray16<'a, F2DOT14>,
     shared_point_numbers: Option<PackedPointsIter<'a>>,
     points_len: u16,
     mut main_s: Stream<'a>,
@@ -284,7 +333,12 @@ fn parse_variation_tuples<'a>(
 
     // `TupleVariationHeader` has a variable size, so we cannot use a `LazyArray`.
     for _ in 0..count {
-        let header = parse_tuple_variation_header(coordinates, shared_tuple_records, &mut main_s)?;
+        let header = parse_tuple_variation_header(
+            axis_count,
+            coordinates,
+            shared_tuple_records,
+            &mut main_s,
+        )?;
         if !(header.scalar > 0.0) {
             // Serialized data for headers with non-positive scalar should be skipped.
             serialized_s.advance(usize::from(header.serialized_data_len));
@@ -334,94 +388,320 @@ fn parse_variation_tuples<'a>(
 }
 
 // https://docs.microsoft.com/en-us/typography/opentype/spec/otvarcommonformats#tuplevariationheader
-fn parse_tuple_variation_header(
+pub(crate) fn parse_tuple_variation_header<'a>(
+    axis_count: u16,
     coordinates: &[NormalizedCoordinate],
-    shared_tuple_records: &LazyArray16<F2DOT14>,
-    s: &mut Stream,
+    shared_tuple_records: &LazyArray16<'a, F2DOT14>,
+    s: &mut Stream<'a>,
 ) -> Option<TupleVariationHeaderData> {
-    const EMBEDDED_PEAK_TUPLE_FLAG: u16 = 0x8000;
-    const INTERMEDIATE_REGION_FLAG: u16 = 0x4000;
-    const PRIVATE_POINT_NUMBERS_FLAG: u16 = 0x2000;
-    const TUPLE_INDEX_MASK: u16 = 0x0FFF;
-
-    let serialized_data_size = s.read::<u16>()?;
-    let tuple_index = s.read::<u16>()?;
-
-    let has_embedded_peak_tuple = tuple_index & EMBEDDED_PEAK_TUPLE_FLAG != 0;
-    let has_intermediate_region = tuple_index & INTERMEDIATE_REGION_FLAG != 0;
-    let has_private_point_numbers = tuple_index & PRIVATE_POINT_NUMBERS_FLAG != 0;
-    let tuple_index = tuple_index & TUPLE_INDEX_MASK;
+    let header = TupleVariationHeader::parse(axis_count, shared_tuple_records, s)?;
+    Some(TupleVariationHeaderData {
+        scalar: header.scalar(coordinates)?,
+        has_private_point_numbers: header.has_private_point_numbers,
+        serialized_data_len: header.serialized_data_len,
+    })
+}
 
-    let axis_count = coordinates.len() as u16;
+#[derive(Clone, Copy)]
+struct TupleVariationHeader<'a> {
+    peak_tuple: LazyArray16<'a, F2DOT14>,
+    start_tuple: LazyArray16<'a, F2DOT14>,
+    end_tuple: LazyArray16<'a, F2DOT14>,
+    has_intermediate_region: bool,
+    has_private_point_numbers: bool,
+    serialized_data_len: u16,
+}
 
-    let peak_tuple = if has_embedded_peak_tuple {
-        s.read_array16::<F2DOT14>(axis_count)?
-    } else {
-        // Use shared tuples.
-        let start = tuple_index.checked_mul(axis_count)?;
-        let end = start.checked_add(axis_count)?;
-        shared_tuple_records.slice(start..end)?
-    };
+impl<'a> TupleVariationHeader<'a> {
+    fn parse(
+        axis_count: u16,
+        shared_tuple_records: &LazyArray16<'a, F2DOT14>,
+        s: &mut Stream<'a>,
+    ) -> Option<Self> {
+        const EMBEDDED_PEAK_TUPLE_FLAG: u16 = 0x8000;
+        const INTERMEDIATE_REGION_FLAG: u16 = 0x4000;
+        const PRIVATE_POINT_NUMBERS_FLAG: u16 = 0x2000;
+        const TUPLE_INDEX_MASK: u16 = 0x0FFF;
+
+        let serialized_data_size = s.read::<u16>()?;
+        let tuple_index = s.read::<u16>()?;
+
+        let has_embedded_peak_tuple = tuple_index & EMBEDDED_PEAK_TUPLE_FLAG != 0;
+        let has_intermediate_region = tuple_index & INTERMEDIATE_REGION_FLAG != 0;
+        let has_private_point_numbers = tuple_index & PRIVATE_POINT_NUMBERS_FLAG != 0;
+        let tuple_index = tuple_index & TUPLE_INDEX_MASK;
+
+        let peak_tuple = if has_embedded_peak_tuple {
+            s.read_array16::<F2DOT14>(axis_count)?
+        } else {
+            // Use shared tuples.
+            let start = tuple_index.checked_mul(axis_count)?;
+            let end = start.checked_add(axis_count)?;
+            shared_tuple_records.slice(start..end)?
+        };
 
-    let (start_tuple, end_tuple) = if has_intermediate_region {
-        (
-            s.read_array16::<F2DOT14>(axis_count)?,
-            s.read_array16::<F2DOT14>(axis_count)?,
-        )
-    } else {
-        (
-            LazyArray16::<F2DOT14>::default(),
-            LazyArray16::<F2DOT14>::default(),
-        )
-    };
+        let (start_tuple, end_tuple) = if has_intermediate_region {
+            (
+                s.read_array16::<F2DOT14>(axis_count)?,
+                s.read_array16::<F2DOT14>(axis_count)?,
+            )
+        } else {
+            (
+                LazyArray16::<F2DOT14>::default(),
+                LazyArray16::<F2DOT14>::default(),
+            )
+        };
 
-    let mut header = TupleVariationHeaderData {
-        scalar: 0.0,
-        has_private_point_numbers,
-        serialized_data_len: serialized_data_size,
-    };
+        Some(TupleVariationHeader {
+            peak_tuple,
+            start_tuple,
+            end_tuple,
+            has_intermediate_region,
+            has_private_point_numbers,
+            serialized_data_len: serialized_data_size,
+        })
+    }
 
     // Calculate the scalar value according to the pseudo-code described at:
     // https://docs.microsoft.com/en-us/typography/opentype/spec/otvaroverview#algorithm-for-interpolation-of-instance-values
-    let mut scalar = 1.0;
-    for i in 0..axis_count {
-        let v = coordinates[usize::from(i)].get();
-        let peak = peak_tuple.get(i)?.0;
-        if peak == 0 || v == peak {
-            continue;
+    fn scalar(&self, coordinates: &[NormalizedCoordinate]) -> Option<f32> {
+        // Peak tuples always have `axis_count` values.
+        if coordinates.len() != usize::from(self.peak_tuple.len()) {
+            return None;
         }
 
-        if has_intermediate_region {
-            let start = start_tuple.get(i)?.0;
-            let end = end_tuple.get(i)?.0;
-            if start > peak || peak > end || (start < 0 && end > 0 && peak != 0) {
+        let mut scalar = 1.0;
+        for i in 0..coordinates.len() as u16 {
+            let v = coordinates[usize::from(i)].get();
+            let peak = self.peak_tuple.get(i)?.0;
+            if peak == 0 || v == peak {
                 continue;
             }
 
-            if v < start || v > end {
-                return Some(header);
-            }
+            if self.has_intermediate_region {
+                let start = self.start_tuple.get(i)?.0;
+                let end = self.end_tuple.get(i)?.0;
+                if start > peak || peak > end || (start < 0 && end > 0 && peak != 0) {
+                    continue;
+                }
 
-            if v < peak {
-                if peak != start {
-                    scalar *= f32::from(v - start) / f32::from(peak - start);
+                if v < start || v > end {
+                    return Some(0.0);
                 }
-            } else {
-                if peak != end {
-                    scalar *= f32::from(end - v) / f32::from(end - peak);
+
+                if v < peak {
+                    if peak != start {
+                        scalar *= f32::from(v - start) / f32::from(peak - start);
+                    }
+                } else {
+                    if peak != end {
+                        scalar *= f32::from(end - v) / f32::from(end - peak);
+                    }
                 }
+            } else if v == 0 || v < cmp::min(0, peak) || v > cmp::max(0, peak) {
+                // 'If the instance coordinate is out of range for some axis, then the
+                // region and its associated deltas are not applicable.'
+                return Some(0.0);
+            } else {
+                scalar *= f32::from(v) / f32::from(peak);
             }
-        } else if v == 0 || v < cmp::min(0, peak) || v > cmp::max(0, peak) {
-            // 'If the instance coordinate is out of range for some axis, then the
-            // region and its associated deltas are not applicable.'
-            return Some(header);
+        }
+
+        Some(scalar)
+    }
+}
+
+/// A glyph tuple variation.
+///
+/// Deltas are not scaled and unreferenced points deltas are not inferred.
+#[derive(Clone, Copy)]
+pub struct TupleVariation<'a> {
+    header: TupleVariationHeader<'a>,
+    point_numbers: Option<PackedPointsIter<'a>>,
+    deltas: PackedDeltasIter<'a>,
+    deltas_count: u16,
+}
+
+impl<'a> TupleVariation<'a> {
+    /// Checks that the tuple has an intermediate region.
+    #[inline]
+    pub fn has_intermediate_region(&self) -> bool {
+        self.header.has_intermediate_region
+    }
+
+    /// Returns the tuple region for the specified axis.
+    ///
+    /// When the tuple has no intermediate region, the region is implied by the peak
+    /// and spans from zero to the peak.
+    pub fn axis_region(&self, axis_index: u16) -> Option<RegionAxisCoordinates> {
+        let peak_coord = self.header.peak_tuple.get(axis_index)?.0;
+        if self.header.has_intermediate_region {
+            Some(RegionAxisCoordinates {
+                start_coord: self.header.start_tuple.get(axis_index)?.0,
+                peak_coord,
+                end_coord: self.header.end_tuple.get(axis_index)?.0,
+            })
         } else {
-            scalar *= f32::from(v) / f32::from(peak);
+            Some(RegionAxisCoordinates {
+                start_coord: cmp::min(0, peak_coord),
+                peak_coord,
+                end_coord: cmp::max(0, peak_coord),
+            })
         }
     }
 
-    header.scalar = scalar;
-    Some(header)
+    /// Returns the tuple scalar for the specified coordinates.
+    ///
+    /// Returns `None` when the number of coordinates is not equal to the number of axes.
+    #[inline]
+    pub fn scalar(&self, coordinates: &[NormalizedCoordinate]) -> Option<f32> {
+        self.header.scalar(coordinates)
+    }
+
+    /// Checks that the tuple has deltas for all glyph points.
+    #[inline]
+    pub fn has_all_points(&self) -> bool {
+        self.point_numbers.is_none()
+    }
+
+    /// Returns explicit point deltas.
+    #[inline]
+    pub fn deltas(&self) -> TupleDeltasIter<'a> {
+        TupleDeltasIter {
+            point_numbers: self.point_numbers,
+            point_index: 0,
+            deltas: self.deltas,
+            left: self.deltas_count,
+        }
+    }
+}
+
+impl core::fmt::Debug for TupleVariation<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "TupleVariation {{ ... }}")
+    }
+}
+
+/// An explicit point delta.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct PointDelta {
+    /// Point index.
+    ///
+    /// Composite glyphs have a single point per component.
+    /// Points are followed by four phantom points.
+    pub point_index: u16,
+    /// X delta.
+    pub x: f32,
+    /// Y delta.
+    pub y: f32,
+}
+
+/// An iterator over tuple explicit point deltas.
+///
+/// A point can be referenced multiple times. In which case deltas should be summed.
+#[derive(Clone, Copy)]
+pub struct TupleDeltasIter<'a> {
+    point_numbers: Option<PackedPointsIter<'a>>,
+    point_index: u16,
+    deltas: PackedDeltasIter<'a>,
+    left: u16,
+}
+
+impl Iterator for TupleDeltasIter<'_> {
+    type Item = PointDelta;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.left = self.left.checked_sub(1)?;
+
+        let point_index = if let Some(ref mut point_numbers) = self.point_numbers {
+            // Point numbers are stored as differences from the previous one.
+            self.point_index = self.point_index.wrapping_add(point_numbers.next()?);
+            self.point_index
+        } else {
+            let index = self.point_index;
+            self.point_index = self.point_index.wrapping_add(1);
+            index
+        };
+
+        let (x, y) = self.deltas.next()?;
+        Some(PointDelta { point_index, x, y })
+    }
+}
+
+impl core::fmt::Debug for TupleDeltasIter<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "TupleDeltasIter {{ ... }}")
+    }
+}
+
+/// An iterator over glyph tuple variations.
+#[derive(Clone)]
+pub struct TupleVariationsIter<'a> {
+    axis_count: u16,
+    shared_tuple_records: LazyArray16<'a, F2DOT14>,
+    shared_point_numbers: Option<PackedPointsIter<'a>>,
+    points_len: u16,
+    main_s: Stream<'a>,
+    serialized_s: Stream<'a>,
+    left: u16,
+}
+
+impl<'a> Iterator for TupleVariationsIter<'a> {
+    type Item = TupleVariation<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.left = self.left.checked_sub(1)?;
+
+        let tuple = self.parse_next();
+        if tuple.is_none() {
+            // Stop on malformed data.
+            self.left = 0;
+        }
+
+        tuple
+    }
+}
+
+impl<'a> TupleVariationsIter<'a> {
+    fn parse_next(&mut self) -> Option<TupleVariation<'a>> {
+        let header = TupleVariationHeader::parse(
+            self.axis_count,
+            &self.shared_tuple_records,
+            &mut self.main_s,
+        )?;
+
+        let serialized_data_start = self.serialized_s.offset();
+
+        let point_numbers = if header.has_private_point_numbers {
+            PackedPointsIter::new(&mut self.serialized_s)?
+        } else {
+            self.shared_point_numbers
+        };
+
+        let deltas_count = if let Some(point_numbers) = point_numbers {
+            u16::try_from(point_numbers.count()).ok()?
+        } else {
+            self.points_len
+        };
+
+        // Use `checked_sub` in case we went over the `serialized_data_len`.
+        let left = usize::from(header.serialized_data_len)
+            .checked_sub(self.serialized_s.offset() - serialized_data_start)?;
+        let deltas_data = self.serialized_s.read_bytes(left)?;
+
+        Some(TupleVariation {
+            header,
+            point_numbers,
+            deltas: PackedDeltasIter::new(1.0, deltas_count, deltas_data),
+            deltas_count,
+        })
+    }
+}
+
+impl core::fmt::Debug for TupleVariationsIter<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "TupleVariationsIter {{ ... }}")
+    }
 }
 
 // https://docs.microsoft.com/en-us/typography/opentype/spec/otvarcommonformats#packed-point-numbers
@@ -892,6 +1172,7 @@ mod packed_points {
     }
 }
 
+pub(crate) use packed_points::PackedPointsIter;
 use packed_points::*;
 
 // https://docs.microsoft.com/en-us/typography/opentype/spec/otvarcommonformats#packed-deltas
@@ -1045,6 +1326,34 @@ mod packed_deltas {
         }
     }
 
+    /// Like `PackedDeltasIter`, but for data with a single delta per point,
+    /// like in the `cvar` table.
+    #[derive(Clone, Copy, Default)]
+    pub struct PackedSingleDeltasIter<'a> {
+        data: &'a [u8],
+        run: RunState,
+        scalar: f32,
+    }
+
+    impl<'a> PackedSingleDeltasIter<'a> {
+        pub fn new(scalar: f32, data: &'a [u8]) -> Self {
+            PackedSingleDeltasIter {
+                data,
+                run: RunState::default(),
+                scalar,
+            }
+        }
+    }
+
+    impl Iterator for PackedSingleDeltasIter<'_> {
+        type Item = f32;
+
+        #[inline]
+        fn next(&mut self) -> Option<Self::Item> {
+            self.run.next(self.data, self.scalar)
+        }
+    }
+
     #[cfg(test)]
     mod tests {
         use super::*;
@@ -1433,6 +1742,7 @@ mod packed_deltas {
 }
 
 use packed_deltas::PackedDeltasIter;
+pub(crate) use packed_deltas::PackedSingleDeltasIter;
 
 /// Infer unreferenced deltas.
 ///
@@ -1703,6 +2013,25 @@ impl<'a> Table<'a> {
             return None;
         }
 
+        let data = self.glyph_variation_data(glyph_id)?;
+
+        // Ignore empty data.
+        if data.is_empty() {
+            return Some(());
+        }
+
+        parse_variation_data(
+            self.axis_count.get(),
+            coordinates,
+            &self.shared_tuple_records,
+            points_len,
+            data,
+            tuples,
+        )
+    }
+
+    #[inline]
+    fn glyph_variation_data(&self, glyph_id: GlyphId) -> Option<&'a [u8]> {
         let next_glyph_id = glyph_id.0.checked_add(1)?;
 
         let (start, end) = match self.offsets {
@@ -1720,19 +2049,7 @@ impl<'a> Table<'a> {
             ),
         };
 
-        // Ignore empty data.
-        if start == end {
-            return Some(());
-        }
-
-        let data = self.glyphs_variation_data.get(start..end)?;
-        parse_variation_data(
-            coordinates,
-            &self.shared_tuple_records,
-            points_len,
-            data,
-            tuples,
-        )
+        self.glyphs_variation_data.get(start..end)
     }
 
     /// Outlines a glyph.
@@ -1743,18 +2060,45 @@ impl<'a> Table<'a> {
         glyph_id: GlyphId,
         builder: &mut dyn OutlineBuilder,
     ) -> Option<Rect> {
-        let mut b = glyf::Builder::new(Transform::default(), RectF::new(), builder);
+        let mut sink = OutlineSink(glyf::Builder::new(
+            Transform::default(),
+            RectF::new(),
+            builder,
+        ));
         let glyph_data = glyf_table.get(glyph_id)?;
-        outline_var_impl(
+        resolve_var_points(
             glyf_table,
             self,
             glyph_id,
             glyph_data,
             coordinates,
+            Transform::default(),
             0,
-            &mut b,
+            &mut sink,
         );
-        b.bbox.to_rect()
+        sink.0.bbox.to_rect()
+    }
+
+    pub(crate) fn contour_point(
+        &self,
+        glyf_table: glyf::Table,
+        coordinates: &[NormalizedCoordinate],
+        glyph_id: GlyphId,
+        index: u16,
+    ) -> Option<PointF> {
+        let glyph_data = glyf_table.get(glyph_id)?;
+        let mut sink = ContourPointSink { index, point: None };
+        resolve_var_points(
+            glyf_table,
+            self,
+            glyph_id,
+            glyph_data,
+            coordinates,
+            Transform::default(),
+            0,
+            &mut sink,
+        )?;
+        sink.point
     }
 
     pub(crate) fn phantom_points(
@@ -1779,6 +2123,92 @@ impl<'a> Table<'a> {
             bottom: tuples.apply_null()?,
         })
     }
+
+    /// Returns glyph tuple variations.
+    ///
+    /// `glyf_table` is required to resolve the number of glyph points.
+    ///
+    /// Returns `None` when the glyph has no variations.
+    pub fn glyph_tuple_variations(
+        &self,
+        glyf_table: glyf::Table,
+        glyph_id: GlyphId,
+    ) -> Option<TupleVariationsIter<'a>> {
+        let data = self.glyph_variation_data(glyph_id)?;
+        let header = TupleVariationStoreHeader::parse(data, 0)?;
+
+        let points_len = if let Some(points) = glyf_table.glyph_points(glyph_id) {
+            points.points_left()
+        } else if let Some(components) = glyf_table.glyph_components(glyph_id) {
+            components.count() as u16
+        } else {
+            0
+        };
+
+        Some(TupleVariationsIter {
+            axis_count: self.axis_count.get(),
+            shared_tuple_records: self.shared_tuple_records,
+            shared_point_numbers: header.shared_point_numbers,
+            points_len: points_len.checked_add(PHANTOM_POINTS_LEN as u16)?,
+            main_s: header.main_s,
+            serialized_s: header.serialized_s,
+            left: header.tuple_variation_count,
+        })
+    }
+
+    /// Calls `f` with a delta for each glyph point, followed by four phantom points deltas.
+    ///
+    /// Deltas of all tuples are scaled and summed,
+    /// and deltas of unreferenced points are inferred.
+    ///
+    /// Composite glyphs have a single point per component.
+    pub fn glyph_deltas(
+        &self,
+        glyf_table: glyf::Table,
+        coordinates: &[NormalizedCoordinate],
+        glyph_id: GlyphId,
+        mut f: impl FnMut(PointF),
+    ) -> Option<()> {
+        let mut s = Stream::new(glyf_table.get(glyph_id)?);
+        let number_of_contours = s.read::<i16>()?;
+        s.advance(8); // Skip bbox.
+
+        let mut tuples = VariationTuples::default();
+        if number_of_contours > 0 {
+            // Simple glyph.
+            let number_of_contours = NonZeroU16::new(number_of_contours as u16)?;
+            let mut glyph_points = glyf::parse_simple_outline(s.tail()?, number_of_contours)?;
+            let all_glyph_points = glyph_points.clone();
+            let points_len = glyph_points.points_left;
+            self.parse_variation_data(glyph_id, coordinates, points_len, &mut tuples)?;
+
+            while let Some(point) = glyph_points.next() {
+                let p = tuples.apply(all_glyph_points.clone(), glyph_points.clone(), point)?;
+                f(PointF {
+                    x: p.x - f32::from(point.x),
+                    y: p.y - f32::from(point.y),
+                });
+            }
+        } else {
+            // Composite or empty glyph.
+            let components_count = if number_of_contours < 0 {
+                glyf::CompositeGlyphIter::new(s.tail()?).count() as u16
+            } else {
+                0
+            };
+
+            self.parse_variation_data(glyph_id, coordinates, components_count, &mut tuples)?;
+            for _ in 0..components_count {
+                f(tuples.apply_null()?);
+            }
+        }
+
+        for _ in 0..PHANTOM_POINTS_LEN {
+            f(tuples.apply_null()?);
+        }
+
+        Some(())
+    }
 }
 
 impl core::fmt::Debug for Table<'_> {
@@ -1787,16 +2217,80 @@ impl core::fmt::Debug for Table<'_> {
     }
 }
 
-#[allow(clippy::comparison_chain)]
-fn outline_var_impl(
+// Receives variable glyph points resolved by `resolve_var_points`.
+trait VarPointsSink {
+    // Called before resolving simple glyph points.
+    //
+    // Returning `true` skips the glyph without parsing its variation data.
+    fn skip_glyph(&mut self, _points_len: u16) -> bool {
+        false
+    }
+
+    // Called with each resolved point and a transform of the component it belongs to.
+    //
+    // Returning `false` stops the processing.
+    fn push_point(&mut self, p: PointF, point: glyf::GlyphPoint, transform: Transform) -> bool;
+}
+
+struct OutlineSink<'a>(glyf::Builder<'a>);
+
+impl VarPointsSink for OutlineSink<'_> {
+    #[inline]
+    fn push_point(&mut self, mut p: PointF, point: glyf::GlyphPoint, transform: Transform) -> bool {
+        if !transform.is_default() {
+            transform.apply_to(&mut p.x, &mut p.y);
+        }
+
+        self.0
+            .push_point(p.x, p.y, point.on_curve_point, point.last_point);
+        true
+    }
+}
+
+// Looks for a point by its index across all the glyph points.
+struct ContourPointSink {
+    index: u16,
+    point: Option<PointF>,
+}
+
+impl VarPointsSink for ContourPointSink {
+    #[inline]
+    fn skip_glyph(&mut self, points_len: u16) -> bool {
+        if self.index >= points_len {
+            self.index -= points_len;
+            true
+        } else {
+            false
+        }
+    }
+
+    #[inline]
+    fn push_point(&mut self, mut p: PointF, _: glyf::GlyphPoint, transform: Transform) -> bool {
+        if self.index == 0 {
+            transform.apply_to(&mut p.x, &mut p.y);
+            self.point = Some(p);
+            false
+        } else {
+            self.index -= 1;
+            true
+        }
+    }
+}
+
+// Resolves variable glyph points, including points of composite glyph components.
+//
+// Returns `Some(false)` when the sink has stopped the processing.
+#[allow(clippy::too_many_arguments)]
+fn resolve_var_points(
     glyf_table: glyf::Table,
     gvar_table: &Table,
     glyph_id: GlyphId,
     data: &[u8],
     coordinates: &[NormalizedCoordinate],
+    transform: Transform,
     depth: u8,
-    builder: &mut glyf::Builder,
-) -> Option<()> {
+    sink: &mut dyn VarPointsSink,
+) -> Option<bool> {
     if depth >= glyf::MAX_COMPONENTS {
         return None;
     }
@@ -1822,14 +2316,21 @@ fn outline_var_impl(
         let mut glyph_points = glyf::parse_simple_outline(s.tail()?, number_of_contours)?;
         let all_glyph_points = glyph_points.clone();
         let points_len = glyph_points.points_left;
+        if sink.skip_glyph(points_len) {
+            return Some(true);
+        }
+
         gvar_table.parse_variation_data(glyph_id, coordinates, points_len, &mut tuples)?;
 
+        // Deltas are stored sequentially, so we have to process all the preceding points.
         while let Some(point) = glyph_points.next() {
             let p = tuples.apply(all_glyph_points.clone(), glyph_points.clone(), point)?;
-            builder.push_point(p.x, p.y, point.on_curve_point, point.last_point);
+            if !sink.push_point(p, point, transform) {
+                return Some(false);
+            }
         }
 
-        Some(())
+        Some(true)
     } else if number_of_contours < 0 {
         // Composite glyph.
 
@@ -1848,93 +2349,74 @@ fn outline_var_impl(
         for component in components {
             let t = tuples.apply_null()?;
 
-            let mut transform = builder.transform;
+            let mut component_transform = transform;
 
             // Variation component offset should be applied only when
             // the ARGS_ARE_XY_VALUES flag is set.
             if component.flags.args_are_xy_values() {
-                transform = Transform::combine(transform, Transform::new_translate(t.x, t.y));
+                component_transform =
+                    Transform::combine(component_transform, Transform::new_translate(t.x, t.y));
             }
 
-            transform = Transform::combine(transform, component.transform);
+            component_transform = Transform::combine(component_transform, component.transform);
 
-            let mut b = glyf::Builder::new(transform, builder.bbox, builder.builder);
             if let Some(glyph_data) = glyf_table.get(component.glyph_id) {
-                outline_var_impl(
+                let resolved = resolve_var_points(
                     glyf_table,
                     gvar_table,
                     component.glyph_id,
                     glyph_data,
                     coordinates,
+                    component_transform,
                     depth + 1,
-                    &mut b,
+                    sink,
                 )?;
 
-                // Take updated bbox.
-                builder.bbox = b.bbox;
+                if !resolved {
+                    return Some(false);
+                }
             }
         }
 
-        Some(())
+        Some(true)
     } else {
         // An empty glyph.
-        None
+        Some(true)
     }
 }
 
 // https://docs.microsoft.com/en-us/typography/opentype/spec/otvarcommonformats#tuple-variation-store-header
 fn parse_variation_data<'a>(
+    axis_count: u16,
     coordinates: &[NormalizedCoordinate],
-    shared_tuple_records: &LazyArray16<F2DOT14>,
+    shared_tuple_records: &LazyArray16<'a, F2DOT14>,
     points_len: u16,
     data: &'a [u8],
     tuples: &mut VariationTuples<'a>,
 ) -> Option<()> {
-    const SHARED_POINT_NUMBERS_FLAG: u16 = 0x8000;
-    const COUNT_MASK: u16 = 0x0FFF;
-
-    let mut main_stream = Stream::new(data);
-    let tuple_variation_count = main_stream.read::<u16>()?;
-    let data_offset = main_stream.read::<Offset16>()?;
-
-    // 'The high 4 bits are flags, and the low 12 bits
-    // are the number of tuple variation tables for this glyph.'
-    let has_shared_point_numbers = tuple_variation_count & SHARED_POINT_NUMBERS_FLAG != 0;
-    let tuple_variation_count = tuple_variation_count & COUNT_MASK;
+    let header = TupleVariationStoreHeader::parse(data, 0)?;
 
     // 'The number of tuple variation tables can be any number between 1 and 4095.'
     // No need to check for 4095, because this is 0x0FFF that we masked before.
-    if tuple_variation_count == 0 {
+    if header.tuple_variation_count == 0 {
         return None;
     }
 
     // Attempt to reserve space for the tuples we're about to parse.
     // If it fails, bail out.
-    if !tuples.reserve(tuple_variation_count) {
+    if !tuples.reserve(header.tuple_variation_count) {
         return None;
     }
 
-    // A glyph variation data consists of three parts: header + variation tuples + serialized data.
-    // Each tuple has it's own chunk in the serialized data.
-    // Because of that, we are using two parsing streams: one for tuples and one for serialized data.
-    // So we can parse them in parallel and avoid needless allocations.
-    let mut serialized_stream = Stream::new_at(data, data_offset.to_usize())?;
-
-    // All tuples in the variation data can reference the same point numbers,
-    // which are defined at the start of the serialized data.
-    let mut shared_point_numbers = None;
-    if has_shared_point_numbers {
-        shared_point_numbers = PackedPointsIter::new(&mut serialized_stream)?;
-    }
-
     parse_variation_tuples(
-        tuple_variation_count,
+        header.tuple_variation_count,
+        axis_count,
         coordinates,
         shared_tuple_records,
-        shared_point_numbers,
+        header.shared_point_numbers,
         points_len.checked_add(PHANTOM_POINTS_LEN as u16)?,
-        main_stream,
-        serialized_stream,
+        header.main_s,
+        header.serialized_s,
         tuples,
     )
 }
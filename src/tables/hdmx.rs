@@ -0,0 +1,154 @@
+//! A [Horizontal Device Metrics Table](
+//! https://docs.microsoft.com/en-us/typography/opentype/spec/hdmx) implementation.
+
+use core::convert::TryFrom;
+use core::num::NonZeroU16;
+
+use crate::parser::{LazyArray16, Stream};
+use crate::GlyphId;
+
+/// A device record.
+#[derive(Clone, Copy, Debug)]
+pub struct DeviceRecord<'a> {
+    /// Pixel size for following widths (as ppem).
+    pub pixel_size: u8,
+    /// Maximum width.
+    pub max_width: u8,
+    /// Widths for each glyph.
+    pub widths: LazyArray16<'a, u8>,
+}
+
+impl<'a> DeviceRecord<'a> {
+    /// Returns glyph's advance width in pixels.
+    #[inline]
+    pub fn glyph_advance(&self, glyph_id: GlyphId) -> Option<u8> {
+        self.widths.get(glyph_id.0)
+    }
+}
+
+/// A list of [`DeviceRecord`]s.
+#[derive(Clone, Copy)]
+pub struct DeviceRecords<'a> {
+    data: &'a [u8],
+    count: u16,
+    record_size: usize,
+    // From the `maxp` table.
+    number_of_glyphs: u16,
+}
+
+impl<'a> DeviceRecords<'a> {
+    /// Returns a record at the index.
+    pub fn get(&self, index: u16) -> Option<DeviceRecord<'a>> {
+        if index >= self.count {
+            return None;
+        }
+
+        let offset = usize::from(index).checked_mul(self.record_size)?;
+        let mut s = Stream::new_at(self.data, offset)?;
+        Some(DeviceRecord {
+            pixel_size: s.read::<u8>()?,
+            max_width: s.read::<u8>()?,
+            widths: s.read_array16::<u8>(self.number_of_glyphs)?,
+        })
+    }
+
+    /// Returns a record with the specified pixel size.
+    pub fn find(&self, pixel_size: u8) -> Option<DeviceRecord<'a>> {
+        self.into_iter()
+            .find(|record| record.pixel_size == pixel_size)
+    }
+
+    /// Returns the number of records.
+    #[inline]
+    pub fn len(&self) -> u16 {
+        self.count
+    }
+
+    /// Checks if there are any records.
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+}
+
+impl core::fmt::Debug for DeviceRecords<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "DeviceRecords {{ ... }}")
+    }
+}
+
+impl<'a> IntoIterator for DeviceRecords<'a> {
+    type Item = DeviceRecord<'a>;
+    type IntoIter = DeviceRecordsIter<'a>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        DeviceRecordsIter {
+            records: self,
+            index: 0,
+        }
+    }
+}
+
+/// An iterator over [`DeviceRecords`].
+#[allow(missing_debug_implementations)]
+pub struct DeviceRecordsIter<'a> {
+    records: DeviceRecords<'a>,
+    index: u16,
+}
+
+impl<'a> Iterator for DeviceRecordsIter<'a> {
+    type Item = DeviceRecord<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index < self.records.len() {
+            self.index += 1;
+            self.records.get(self.index - 1)
+        } else {
+            None
+        }
+    }
+}
+
+/// A [Horizontal Device Metrics Table](
+/// https://docs.microsoft.com/en-us/typography/opentype/spec/hdmx).
+#[derive(Clone, Copy, Debug)]
+pub struct Table<'a> {
+    /// A list of [`DeviceRecord`]s. Sorted by pixel size.
+    pub records: DeviceRecords<'a>,
+}
+
+impl<'a> Table<'a> {
+    /// Parses a table from raw data.
+    ///
+    /// - `number_of_glyphs` is from the `maxp` table.
+    pub fn parse(number_of_glyphs: NonZeroU16, data: &'a [u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+
+        let version = s.read::<u16>()?;
+        if version != 0 {
+            return None;
+        }
+
+        let count = u16::try_from(s.read::<i16>()?).ok()?;
+        let record_size = usize::try_from(s.read::<i32>()?).ok()?;
+        let number_of_glyphs = number_of_glyphs.get();
+        // Each record must be able to hold a width for each glyph.
+        if record_size < usize::from(number_of_glyphs) + 2 {
+            return None;
+        }
+
+        Some(Table {
+            records: DeviceRecords {
+                data: s.tail()?,
+                count,
+                record_size,
+                number_of_glyphs,
+            },
+        })
+    }
+
+    /// Returns glyph's advance width in pixels for the specified pixels-per-em.
+    pub fn glyph_advance(&self, glyph_id: GlyphId, pixels_per_em: u8) -> Option<u8> {
+        self.records.find(pixels_per_em)?.glyph_advance(glyph_id)
+    }
+}
@@ -0,0 +1,166 @@
+//! A [Justification Table](https://docs.microsoft.com/en-us/typography/opentype/spec/jstf)
+//! implementation.
+
+use crate::opentype_layout::{LookupIndex, LookupList, RecordList, RecordListItem};
+use crate::parser::{FromSlice, LazyArray16, LazyOffsetArray16, Offset, Offset16, Stream};
+use crate::{GlyphId, Tag};
+
+/// A set of justification modifications.
+///
+/// Used for both shrinkage and extension.
+#[derive(Clone, Copy, Default, Debug)]
+pub struct Modifications<'a> {
+    /// `GSUB` lookups to enable.
+    pub gsub_enable: LazyArray16<'a, LookupIndex>,
+    /// `GSUB` lookups to disable.
+    pub gsub_disable: LazyArray16<'a, LookupIndex>,
+    /// `GPOS` lookups to enable.
+    pub gpos_enable: LazyArray16<'a, LookupIndex>,
+    /// `GPOS` lookups to disable.
+    pub gpos_disable: LazyArray16<'a, LookupIndex>,
+    /// `GPOS` lookups that define the maximum adjustment.
+    ///
+    /// Use [`gpos::PositioningSubtable`](crate::gpos::PositioningSubtable)
+    /// to parse lookup subtables.
+    pub max: LookupList<'a>,
+}
+
+/// A [Justification Priority Table](
+/// https://docs.microsoft.com/en-us/typography/opentype/spec/jstf#jstfpriority-table).
+#[derive(Clone, Copy, Debug)]
+pub struct Priority<'a> {
+    /// Modifications applied when a line is too long.
+    pub shrinkage: Modifications<'a>,
+    /// Modifications applied when a line is too short.
+    pub extension: Modifications<'a>,
+}
+
+impl<'a> FromSlice<'a> for Priority<'a> {
+    fn parse(data: &'a [u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+        let shrinkage = Modifications {
+            gsub_enable: parse_lookup_indices(data, s.read()?),
+            gsub_disable: parse_lookup_indices(data, s.read()?),
+            gpos_enable: parse_lookup_indices(data, s.read()?),
+            gpos_disable: parse_lookup_indices(data, s.read()?),
+            max: parse_lookup_list(data, s.read()?),
+        };
+        let extension = Modifications {
+            gsub_enable: parse_lookup_indices(data, s.read()?),
+            gsub_disable: parse_lookup_indices(data, s.read()?),
+            gpos_enable: parse_lookup_indices(data, s.read()?),
+            gpos_disable: parse_lookup_indices(data, s.read()?),
+            max: parse_lookup_list(data, s.read()?),
+        };
+        Some(Priority {
+            shrinkage,
+            extension,
+        })
+    }
+}
+
+/// A [Justification Language System Table](
+/// https://docs.microsoft.com/en-us/typography/opentype/spec/jstf#jstflangsys-table).
+#[derive(Clone, Copy, Debug)]
+pub struct LanguageSystem<'a> {
+    /// Language tag.
+    pub tag: Tag,
+    /// Justification priorities. From the highest to the lowest one.
+    pub priorities: LazyOffsetArray16<'a, Priority<'a>>,
+}
+
+impl<'a> RecordListItem<'a> for LanguageSystem<'a> {
+    fn parse(tag: Tag, data: &'a [u8]) -> Option<Self> {
+        Some(LanguageSystem {
+            tag,
+            priorities: LazyOffsetArray16::parse(data)?,
+        })
+    }
+}
+
+/// A list of [`LanguageSystem`] records.
+pub type LanguageSystemList<'a> = RecordList<'a, LanguageSystem<'a>>;
+
+/// A [Justification Script Table](
+/// https://docs.microsoft.com/en-us/typography/opentype/spec/jstf#jstfscript-table).
+#[derive(Clone, Copy, Debug)]
+pub struct Script<'a> {
+    /// Script tag.
+    pub tag: Tag,
+    /// Glyphs that can be inserted to extend a line, like kashidas.
+    pub extender_glyphs: LazyArray16<'a, GlyphId>,
+    /// Default language.
+    pub default_language: Option<LanguageSystem<'a>>,
+    /// List of supported languages, excluding the default one. Listed alphabetically.
+    pub languages: LanguageSystemList<'a>,
+}
+
+impl<'a> RecordListItem<'a> for Script<'a> {
+    fn parse(tag: Tag, data: &'a [u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+        let extender_glyphs = match s.read::<Option<Offset16>>()? {
+            Some(offset) => {
+                let mut s = Stream::new_at(data, offset.to_usize())?;
+                let count = s.read::<u16>()?;
+                s.read_array16::<GlyphId>(count)?
+            }
+            None => LazyArray16::default(),
+        };
+        let default_language = s
+            .read::<Option<Offset16>>()?
+            .and_then(|offset| data.get(offset.to_usize()..))
+            .and_then(|data| LanguageSystem::parse(Tag::from_bytes(b"dflt"), data));
+        let mut languages = RecordList::parse(s.tail()?)?;
+        // Offsets are relative to this table.
+        languages.data = data;
+        Some(Script {
+            tag,
+            extender_glyphs,
+            default_language,
+            languages,
+        })
+    }
+}
+
+/// A list of [`Script`] records.
+pub type ScriptList<'a> = RecordList<'a, Script<'a>>;
+
+/// A [Justification Table](https://docs.microsoft.com/en-us/typography/opentype/spec/jstf).
+#[derive(Clone, Copy, Debug)]
+pub struct Table<'a> {
+    /// A list of scripts. Listed alphabetically.
+    pub scripts: ScriptList<'a>,
+}
+
+impl<'a> Table<'a> {
+    /// Parses a table from raw data.
+    pub fn parse(data: &'a [u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+        let major_version = s.read::<u16>()?;
+        s.skip::<u16>(); // minor version
+        if major_version != 1 {
+            return None;
+        }
+
+        let mut scripts = RecordList::parse(s.tail()?)?;
+        // Offsets are relative to this table.
+        scripts.data = data;
+        Some(Table { scripts })
+    }
+}
+
+fn parse_lookup_indices(data: &[u8], offset: Option<Offset16>) -> LazyArray16<'_, LookupIndex> {
+    let parse = || {
+        let mut s = Stream::new_at(data, offset?.to_usize())?;
+        let count = s.read::<u16>()?;
+        s.read_array16::<LookupIndex>(count)
+    };
+    parse().unwrap_or_default()
+}
+
+fn parse_lookup_list(data: &[u8], offset: Option<Offset16>) -> LookupList<'_> {
+    offset
+        .and_then(|offset| data.get(offset.to_usize()..))
+        .and_then(LookupList::parse)
+        .unwrap_or_default()
+}
@@ -0,0 +1,37 @@
+//! A [Linear Threshold Table](
+//! https://docs.microsoft.com/en-us/typography/opentype/spec/ltsh) implementation.
+
+use crate::parser::{LazyArray16, Stream};
+use crate::GlyphId;
+
+/// A [Linear Threshold Table](https://docs.microsoft.com/en-us/typography/opentype/spec/ltsh).
+#[derive(Clone, Copy, Debug)]
+pub struct Table<'a> {
+    /// The vertical pel height at which each glyph can be assumed to scale linearly.
+    pub y_pels: LazyArray16<'a, u8>,
+}
+
+impl<'a> Table<'a> {
+    /// Parses a table from raw data.
+    pub fn parse(data: &'a [u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+
+        let version = s.read::<u16>()?;
+        if version != 0 {
+            return None;
+        }
+
+        let count = s.read::<u16>()?;
+        let y_pels = s.read_array16::<u8>(count)?;
+
+        Some(Table { y_pels })
+    }
+
+    /// Returns the pixels-per-em starting from which glyph's advance scales linearly.
+    ///
+    /// A value of 1 means that the glyph always scales linearly.
+    #[inline]
+    pub fn glyph_threshold(&self, glyph_id: GlyphId) -> Option<u8> {
+        self.y_pels.get(glyph_id.0)
+    }
+}
@@ -6,10 +6,39 @@ use core::num::NonZeroU16;
 use crate::parser::Stream;
 
 /// A [Maximum Profile Table](https://docs.microsoft.com/en-us/typography/opentype/spec/maxp).
+///
+/// All fields except `number_of_glyphs` are present only in version 1.0 tables
+/// and are set to 0 otherwise.
 #[derive(Clone, Copy, Debug)]
 pub struct Table {
     /// The total number of glyphs in the face.
     pub number_of_glyphs: NonZeroU16,
+    /// Maximum points in a non-composite glyph.
+    pub max_points: u16,
+    /// Maximum contours in a non-composite glyph.
+    pub max_contours: u16,
+    /// Maximum points in a composite glyph.
+    pub max_composite_points: u16,
+    /// Maximum contours in a composite glyph.
+    pub max_composite_contours: u16,
+    /// 1 if instructions do not use the twilight zone, 2 otherwise.
+    pub max_zones: u16,
+    /// Maximum points used in the twilight zone.
+    pub max_twilight_points: u16,
+    /// Number of Storage Area locations.
+    pub max_storage: u16,
+    /// Number of function definitions.
+    pub max_function_defs: u16,
+    /// Number of instruction definitions.
+    pub max_instruction_defs: u16,
+    /// Maximum stack depth across all programs.
+    pub max_stack_elements: u16,
+    /// Maximum byte count for glyph instructions.
+    pub max_size_of_instructions: u16,
+    /// Maximum number of components referenced at the top level of a composite glyph.
+    pub max_component_elements: u16,
+    /// Maximum levels of recursion.
+    pub max_component_depth: u16,
 }
 
 impl Table {
@@ -23,6 +52,32 @@ impl Table {
 
         let n = s.read::<u16>()?;
         let number_of_glyphs = NonZeroU16::new(n)?;
-        Some(Table { number_of_glyphs })
+
+        // Version 1.0 fields are optional, since we don't really care
+        // about them outside of hinting.
+        let mut next = || {
+            if version == 0x00010000 {
+                s.read::<u16>().unwrap_or(0)
+            } else {
+                0
+            }
+        };
+
+        Some(Table {
+            number_of_glyphs,
+            max_points: next(),
+            max_contours: next(),
+            max_composite_points: next(),
+            max_composite_contours: next(),
+            max_zones: next(),
+            max_twilight_points: next(),
+            max_storage: next(),
+            max_function_defs: next(),
+            max_instruction_defs: next(),
+            max_stack_elements: next(),
+            max_size_of_instructions: next(),
+            max_component_elements: next(),
+            max_component_depth: next(),
+        })
     }
 }
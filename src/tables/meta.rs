@@ -0,0 +1,200 @@
+//! A [Metadata Table](
+//! https://docs.microsoft.com/en-us/typography/opentype/spec/meta) implementation.
+
+use core::convert::TryFrom;
+
+use crate::parser::{FromData, LazyArray32, Offset, Offset32, Stream};
+use crate::Tag;
+
+#[derive(Clone, Copy)]
+struct DataMapRecord {
+    tag: Tag,
+    offset: Offset32,
+    length: u32,
+}
+
+impl FromData for DataMapRecord {
+    const SIZE: usize = 12;
+
+    #[inline]
+    fn parse(data: &[u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+        Some(DataMapRecord {
+            tag: s.read::<Tag>()?,
+            offset: s.read::<Offset32>()?,
+            length: s.read::<u32>()?,
+        })
+    }
+}
+
+/// A metadata entry.
+#[derive(Clone, Copy, Debug)]
+pub struct DataMap<'a> {
+    /// Metadata tag.
+    pub tag: Tag,
+    /// Raw metadata.
+    pub data: &'a [u8],
+}
+
+impl<'a> DataMap<'a> {
+    /// Parses metadata as a comma-separated list of ScriptLangTags.
+    ///
+    /// Used by the `dlng` and `slng` entries.
+    /// Returns `None` when metadata is not a valid UTF-8 string.
+    pub fn script_lang_tags(&self) -> Option<ScriptLangTags<'a>> {
+        core::str::from_utf8(self.data).ok().map(ScriptLangTags)
+    }
+}
+
+/// A list of metadata entries.
+#[derive(Clone, Copy, Default)]
+pub struct DataMaps<'a> {
+    data: &'a [u8],
+    records: LazyArray32<'a, DataMapRecord>,
+}
+
+impl<'a> DataMaps<'a> {
+    /// Returns an entry at index.
+    pub fn get(&self, index: u32) -> Option<DataMap<'a>> {
+        let record = self.records.get(index)?;
+        let start = record.offset.to_usize();
+        let end = start.checked_add(usize::try_from(record.length).ok()?)?;
+        Some(DataMap {
+            tag: record.tag,
+            data: self.data.get(start..end)?,
+        })
+    }
+
+    /// Returns the first entry with the specified tag.
+    pub fn find(&self, tag: Tag) -> Option<DataMap<'a>> {
+        self.into_iter().find(|map| map.tag == tag)
+    }
+
+    /// Returns a number of entries.
+    pub fn len(&self) -> u32 {
+        self.records.len()
+    }
+
+    /// Checks if there are any entries.
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+}
+
+impl core::fmt::Debug for DataMaps<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_list().entries(*self).finish()
+    }
+}
+
+impl<'a> IntoIterator for DataMaps<'a> {
+    type Item = DataMap<'a>;
+    type IntoIter = DataMapsIter<'a>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        DataMapsIter {
+            maps: self,
+            index: 0,
+        }
+    }
+}
+
+/// An iterator over [`DataMaps`].
+///
+/// Entries with invalid data ranges are skipped.
+#[derive(Clone, Copy)]
+#[allow(missing_debug_implementations)]
+pub struct DataMapsIter<'a> {
+    maps: DataMaps<'a>,
+    index: u32,
+}
+
+impl<'a> Iterator for DataMapsIter<'a> {
+    type Item = DataMap<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.index < self.maps.len() {
+            self.index += 1;
+            if let Some(map) = self.maps.get(self.index - 1) {
+                return Some(map);
+            }
+        }
+
+        None
+    }
+}
+
+/// An iterator over ScriptLangTags.
+///
+/// Each tag is a BCP 47-like language tag, a script subtag
+/// or a combination of both. Like `en-Latn`, `Hant` or `ja`.
+/// Surrounding whitespace is trimmed and empty tags are skipped.
+#[derive(Clone, Copy, Debug)]
+pub struct ScriptLangTags<'a>(&'a str);
+
+impl<'a> Iterator for ScriptLangTags<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.0.is_empty() {
+            let (tag, tail) = match self.0.find(',') {
+                Some(idx) => (&self.0[..idx], &self.0[idx + 1..]),
+                None => (self.0, ""),
+            };
+            self.0 = tail;
+
+            let tag = tag.trim();
+            if !tag.is_empty() {
+                return Some(tag);
+            }
+        }
+
+        None
+    }
+}
+
+/// A [Metadata Table](https://docs.microsoft.com/en-us/typography/opentype/spec/meta).
+#[derive(Clone, Copy, Debug)]
+pub struct Table<'a> {
+    /// A list of metadata entries.
+    pub data_maps: DataMaps<'a>,
+}
+
+impl<'a> Table<'a> {
+    /// Parses a table from raw data.
+    pub fn parse(data: &'a [u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+        let version = s.read::<u32>()?;
+        if version != 1 {
+            return None;
+        }
+
+        s.skip::<u32>(); // flags
+        s.skip::<u32>(); // reserved
+        let count = s.read::<u32>()?;
+        let records = s.read_array32::<DataMapRecord>(count)?;
+
+        Some(Table {
+            data_maps: DataMaps { data, records },
+        })
+    }
+
+    /// Returns languages the font was primarily designed for.
+    ///
+    /// Parsed from the `dlng` entry.
+    pub fn design_languages(&self) -> Option<ScriptLangTags<'a>> {
+        self.data_maps
+            .find(Tag::from_bytes(b"dlng"))?
+            .script_lang_tags()
+    }
+
+    /// Returns languages the font is capable of supporting.
+    ///
+    /// Parsed from the `slng` entry.
+    pub fn supported_languages(&self) -> Option<ScriptLangTags<'a>> {
+        self.data_maps
+            .find(Tag::from_bytes(b"slng"))?
+            .script_lang_tags()
+    }
+}
@@ -4,21 +4,31 @@ mod cff;
 pub mod cmap;
 pub mod colr;
 pub mod cpal;
+pub mod cvt;
+pub mod fpgm;
+pub mod gasp;
 pub mod glyf;
+pub mod hdmx;
 pub mod head;
 pub mod hhea;
 pub mod hmtx;
 pub mod kern;
 pub mod loca;
+pub mod ltsh;
 pub mod maxp;
+pub mod meta;
 pub mod name;
 pub mod os2;
 pub mod post;
+pub mod prep;
 pub mod sbix;
 pub mod svg;
+pub mod vdmx;
 pub mod vhea;
 pub mod vorg;
 
+#[cfg(feature = "opentype-layout")]
+pub mod base;
 #[cfg(feature = "opentype-layout")]
 pub mod gdef;
 #[cfg(feature = "opentype-layout")]
@@ -26,6 +36,8 @@ pub mod gpos;
 #[cfg(feature = "opentype-layout")]
 pub mod gsub;
 #[cfg(feature = "opentype-layout")]
+pub mod jstf;
+#[cfg(feature = "opentype-layout")]
 pub mod math;
 
 #[cfg(feature = "apple-layout")]
@@ -42,6 +54,8 @@ pub mod trak;
 #[cfg(feature = "variable-fonts")]
 pub mod avar;
 #[cfg(feature = "variable-fonts")]
+pub mod cvar;
+#[cfg(feature = "variable-fonts")]
 pub mod fvar;
 #[cfg(feature = "variable-fonts")]
 pub mod gvar;
@@ -50,9 +64,11 @@ pub mod hvar;
 #[cfg(feature = "variable-fonts")]
 pub mod mvar;
 #[cfg(feature = "variable-fonts")]
+pub mod stat;
+#[cfg(feature = "variable-fonts")]
 pub mod vvar;
 
 pub use cff::cff1;
 #[cfg(feature = "variable-fonts")]
 pub use cff::cff2;
-pub use cff::CFFError;
+pub use cff::{CFFError, CFFHintsBuilder};
@@ -243,6 +243,24 @@ impl<'a> Names<'a> {
     pub fn is_empty(&self) -> bool {
         self.records.is_empty()
     }
+
+    /// Returns the most suitable name with the specified ID.
+    ///
+    /// Prefers Unicode names in English, then any Unicode names,
+    /// then any other names.
+    #[cfg(feature = "variable-fonts")]
+    pub(crate) fn find_preferred(&self, name_id: u16) -> Option<Name<'a>> {
+        let names = self.into_iter().filter(|name| name.name_id == name_id);
+        names
+            .clone()
+            .find(|name| {
+                name.is_unicode()
+                    && (name.platform_id == PlatformId::Unicode
+                        || name.language() == Language::English_UnitedStates)
+            })
+            .or_else(|| names.clone().find(|name| name.is_unicode()))
+            .or_else(|| names.clone().next())
+    }
 }
 
 impl core::fmt::Debug for Names<'_> {
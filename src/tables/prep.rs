@@ -0,0 +1,26 @@
+//! A [Control Value Program](
+//! https://docs.microsoft.com/en-us/typography/opentype/spec/prep) implementation.
+
+use crate::truetype_instructions::Instructions;
+
+/// A [Control Value Program](https://docs.microsoft.com/en-us/typography/opentype/spec/prep).
+#[derive(Clone, Copy, Debug)]
+pub struct Table<'a> {
+    /// Raw TrueType instructions.
+    ///
+    /// Executed each time the point size or the transformation changes.
+    pub instructions: &'a [u8],
+}
+
+impl<'a> Table<'a> {
+    /// Parses a table from raw data.
+    pub fn parse(data: &'a [u8]) -> Option<Self> {
+        Some(Table { instructions: data })
+    }
+
+    /// Returns an iterator over decoded instructions.
+    #[inline]
+    pub fn decode(&self) -> Instructions<'a> {
+        Instructions::new(self.instructions)
+    }
+}
@@ -0,0 +1,564 @@
+//! A [Style Attributes Table](
+//! https://docs.microsoft.com/en-us/typography/opentype/spec/stat) implementation.
+
+use crate::parser::{
+    Fixed, FromData, FromSlice, LazyArray16, LazyOffsetArray16, Offset, Offset32, Stream,
+};
+use crate::{fvar, name, NormalizedCoordinate, Tag};
+
+/// A [design axis record](
+/// https://docs.microsoft.com/en-us/typography/opentype/spec/stat#axis-records).
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct AxisRecord {
+    /// An axis tag.
+    pub tag: Tag,
+    /// An axis name in the `name` table.
+    pub name_id: u16,
+    /// A value that applications can use to determine primary sorting
+    /// of face names, or for ordering of labels when composing family or face names.
+    pub ordering: u16,
+}
+
+impl FromData for AxisRecord {
+    const SIZE: usize = 8;
+
+    #[inline]
+    fn parse(data: &[u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+        Some(AxisRecord {
+            tag: s.read::<Tag>()?,
+            name_id: s.read::<u16>()?,
+            ordering: s.read::<u16>()?,
+        })
+    }
+}
+
+/// A list of design axis records.
+///
+/// Unlike [`LazyArray16`], records can be larger than [`AxisRecord::SIZE`],
+/// since newer table versions are allowed to append fields to them.
+#[derive(Clone, Copy, Default)]
+pub struct AxisRecords<'a> {
+    data: &'a [u8],
+    record_size: u16,
+    count: u16,
+}
+
+impl<'a> AxisRecords<'a> {
+    /// Returns a record at `index`.
+    pub fn get(&self, index: u16) -> Option<AxisRecord> {
+        if index >= self.count {
+            return None;
+        }
+
+        let start = usize::from(index) * usize::from(self.record_size);
+        self.data.get(start..).and_then(AxisRecord::parse)
+    }
+
+    /// Returns the number of records.
+    #[inline]
+    pub fn len(&self) -> u16 {
+        self.count
+    }
+
+    /// Checks if there are any records.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+}
+
+impl core::fmt::Debug for AxisRecords<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_list().entries(*self).finish()
+    }
+}
+
+impl<'a> IntoIterator for AxisRecords<'a> {
+    type Item = AxisRecord;
+    type IntoIter = AxisRecordsIter<'a>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        AxisRecordsIter {
+            records: self,
+            index: 0,
+        }
+    }
+}
+
+/// An iterator over design axis records.
+#[derive(Clone, Copy)]
+#[allow(missing_debug_implementations)]
+pub struct AxisRecordsIter<'a> {
+    records: AxisRecords<'a>,
+    index: u16,
+}
+
+impl<'a> Iterator for AxisRecordsIter<'a> {
+    type Item = AxisRecord;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index < self.records.len() {
+            self.index += 1;
+            self.records.get(self.index - 1)
+        } else {
+            None
+        }
+    }
+}
+
+/// [Axis value table flags](
+/// https://docs.microsoft.com/en-us/typography/opentype/spec/stat#flags).
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub struct AxisValueFlags(pub u16);
+
+impl AxisValueFlags {
+    /// Indicates that the axis value represents the “Regular” value
+    /// for a font family that is older than the current one.
+    #[inline]
+    pub fn older_sibling_font_attribute(self) -> bool {
+        self.0 & 0x0001 != 0
+    }
+
+    /// Indicates that the axis value name can be omitted when composing a face name.
+    #[inline]
+    pub fn elidable(self) -> bool {
+        self.0 & 0x0002 != 0
+    }
+}
+
+/// An axis value record used by [`AxisValueKind::Multiple`].
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct AxisValueRecord {
+    /// An index into the design axes list.
+    pub axis_index: u16,
+    /// A numeric value for this record.
+    pub value: f32,
+}
+
+impl FromData for AxisValueRecord {
+    const SIZE: usize = 6;
+
+    #[inline]
+    fn parse(data: &[u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+        Some(AxisValueRecord {
+            axis_index: s.read::<u16>()?,
+            value: s.read::<Fixed>()?.0,
+        })
+    }
+}
+
+/// An axis value format-specific data.
+#[derive(Clone, Copy, Debug)]
+pub enum AxisValueKind<'a> {
+    /// A single value on a single axis.
+    ///
+    /// Format 1.
+    #[allow(missing_docs)]
+    Value { axis_index: u16, value: f32 },
+    /// A range of values on a single axis.
+    ///
+    /// Format 2.
+    #[allow(missing_docs)]
+    Range {
+        axis_index: u16,
+        nominal_value: f32,
+        range_min_value: f32,
+        range_max_value: f32,
+    },
+    /// A single value on a single axis with a linked value,
+    /// usually a bold counterpart of a regular style.
+    ///
+    /// Format 3.
+    #[allow(missing_docs)]
+    Linked {
+        axis_index: u16,
+        value: f32,
+        linked_value: f32,
+    },
+    /// A combination of values on multiple axes.
+    ///
+    /// Format 4.
+    Multiple(LazyArray16<'a, AxisValueRecord>),
+}
+
+/// An [axis value table](
+/// https://docs.microsoft.com/en-us/typography/opentype/spec/stat#axis-value-tables).
+#[derive(Clone, Copy, Debug)]
+pub struct AxisValue<'a> {
+    /// Axis value flags.
+    pub flags: AxisValueFlags,
+    /// An axis value name in the `name` table.
+    pub value_name_id: u16,
+    /// A format-specific data.
+    pub kind: AxisValueKind<'a>,
+}
+
+impl<'a> FromSlice<'a> for AxisValue<'a> {
+    fn parse(data: &'a [u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+        let format = s.read::<u16>()?;
+        let index_or_count = s.read::<u16>()?;
+        let flags = AxisValueFlags(s.read::<u16>()?);
+        let value_name_id = s.read::<u16>()?;
+        let kind = match format {
+            1 => AxisValueKind::Value {
+                axis_index: index_or_count,
+                value: s.read::<Fixed>()?.0,
+            },
+            2 => AxisValueKind::Range {
+                axis_index: index_or_count,
+                nominal_value: s.read::<Fixed>()?.0,
+                range_min_value: s.read::<Fixed>()?.0,
+                range_max_value: s.read::<Fixed>()?.0,
+            },
+            3 => AxisValueKind::Linked {
+                axis_index: index_or_count,
+                value: s.read::<Fixed>()?.0,
+                linked_value: s.read::<Fixed>()?.0,
+            },
+            4 => AxisValueKind::Multiple(s.read_array16::<AxisValueRecord>(index_or_count)?),
+            _ => return None,
+        };
+
+        Some(AxisValue {
+            flags,
+            value_name_id,
+            kind,
+        })
+    }
+}
+
+impl AxisValue<'_> {
+    /// Checks that the axis value applies to the specified design axis.
+    pub fn has_axis(&self, index: u16) -> bool {
+        match self.kind {
+            AxisValueKind::Value { axis_index, .. }
+            | AxisValueKind::Range { axis_index, .. }
+            | AxisValueKind::Linked { axis_index, .. } => axis_index == index,
+            AxisValueKind::Multiple(records) => records.into_iter().any(|r| r.axis_index == index),
+        }
+    }
+}
+
+/// A list of axis values.
+pub type AxisValues<'a> = LazyOffsetArray16<'a, AxisValue<'a>>;
+
+/// A [Style Attributes Table](
+/// https://docs.microsoft.com/en-us/typography/opentype/spec/stat).
+#[derive(Clone, Copy, Default, Debug)]
+pub struct Table<'a> {
+    /// A list of design axes.
+    ///
+    /// Can contain axes that are not present in the `fvar` table.
+    pub axes: AxisRecords<'a>,
+    /// A list of axis values.
+    pub values: AxisValues<'a>,
+    /// A name in the `name` table that should be used when all axis value names
+    /// are elided.
+    ///
+    /// Present only in table version 1.1 and newer.
+    pub elided_fallback_name_id: Option<u16>,
+}
+
+impl<'a> Table<'a> {
+    /// Parses a table from raw data.
+    pub fn parse(data: &'a [u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+        let major_version = s.read::<u16>()?;
+        let minor_version = s.read::<u16>()?;
+        if major_version != 1 {
+            return None;
+        }
+
+        let design_axis_size = s.read::<u16>()?;
+        let design_axis_count = s.read::<u16>()?;
+        let design_axes_offset = s.read::<Offset32>()?;
+        let axis_value_count = s.read::<u16>()?;
+        let axis_value_offsets_offset = s.read::<Offset32>()?;
+        let elided_fallback_name_id = if minor_version >= 1 {
+            s.read::<u16>()
+        } else {
+            None
+        };
+
+        if usize::from(design_axis_size) < AxisRecord::SIZE {
+            return None;
+        }
+
+        let axes = if design_axis_count != 0 {
+            let len = usize::from(design_axis_size) * usize::from(design_axis_count);
+            AxisRecords {
+                data: data.get(design_axes_offset.to_usize()..)?.get(..len)?,
+                record_size: design_axis_size,
+                count: design_axis_count,
+            }
+        } else {
+            AxisRecords::default()
+        };
+
+        let values = if axis_value_count != 0 {
+            let data = data.get(axis_value_offsets_offset.to_usize()..)?;
+            let offsets = Stream::new(data).read_array16(axis_value_count)?;
+            LazyOffsetArray16::new(data, offsets)
+        } else {
+            LazyOffsetArray16::default()
+        };
+
+        Some(Table {
+            axes,
+            values,
+            elided_fallback_name_id,
+        })
+    }
+}
+
+/// A resolved [`AxisValue`] name.
+#[derive(Clone, Copy, Debug)]
+pub struct StyleName<'a> {
+    /// A design axis the value belongs to.
+    ///
+    /// For [`AxisValueKind::Multiple`] values, this is the first axis
+    /// in the axis ordering covered by the value.
+    pub axis: AxisRecord,
+    /// A matched axis value.
+    pub value: AxisValue<'a>,
+    /// A name record for [`AxisValue::value_name_id`].
+    ///
+    /// Prefers Unicode English names.
+    /// `None` when the `name` table doesn't have such a record.
+    pub name: Option<name::Name<'a>>,
+}
+
+/// An iterator over axis value names matching specific variation coordinates.
+///
+/// Yields at most one name per design axis, in the design axes ordering.
+/// Created by [`Face::style_names`](crate::Face::style_names).
+#[derive(Clone)]
+#[allow(missing_debug_implementations)]
+pub struct StyleNames<'a> {
+    table: Table<'a>,
+    names: name::Names<'a>,
+    fvar_axes: LazyArray16<'a, fvar::VariationAxis>,
+    coordinates: [NormalizedCoordinate; MAX_AXES],
+    // Design axes already covered by a multiple-axes value.
+    covered: [bool; MAX_AXES],
+    // The last yielded axis as (ordering, index).
+    last: Option<(u16, u16)>,
+}
+
+// Matches the maximum number of variation coordinates stored by `Face`.
+const MAX_AXES: usize = 64;
+
+impl<'a> StyleNames<'a> {
+    pub(crate) fn new(
+        table: Table<'a>,
+        names: name::Names<'a>,
+        fvar_axes: LazyArray16<'a, fvar::VariationAxis>,
+        coordinates: &[NormalizedCoordinate],
+    ) -> Self {
+        let mut coords = [NormalizedCoordinate::default(); MAX_AXES];
+        for (c1, c2) in coords.iter_mut().zip(coordinates) {
+            *c1 = *c2;
+        }
+
+        StyleNames {
+            table,
+            names,
+            fvar_axes,
+            coordinates: coords,
+            covered: [false; MAX_AXES],
+            last: None,
+        }
+    }
+
+    // Returns the next design axis in the axis ordering.
+    fn next_axis(&self) -> Option<(u16, AxisRecord)> {
+        let mut next: Option<(u16, u16, AxisRecord)> = None;
+        for (index, axis) in self.table.axes.into_iter().enumerate() {
+            let index = index as u16;
+            let key = (axis.ordering, index);
+            if let Some(last) = self.last {
+                if key <= last {
+                    continue;
+                }
+            }
+
+            match next {
+                Some((ordering, i, _)) if (ordering, i) <= key => {}
+                _ => next = Some((axis.ordering, index, axis)),
+            }
+        }
+
+        next.map(|(_, index, axis)| (index, axis))
+    }
+
+    // Maps a user-space value on a design axis into a normalized coordinate
+    // and returns it along with the current coordinate.
+    //
+    // Both coordinates are not mapped by `avar`, so we can compare them directly.
+    //
+    // Returns `None` when the design axis is not present in `fvar`.
+    fn normalize(&self, axis_index: u16, value: f32) -> Option<(i16, i16)> {
+        let tag = self.table.axes.get(axis_index)?.tag;
+        let (fvar_index, fvar_axis) = self
+            .fvar_axes
+            .into_iter()
+            .enumerate()
+            .find(|(_, a)| a.tag == tag)?;
+
+        let coord = fvar_axis.normalized_value(value);
+        let current = self
+            .coordinates
+            .get(fvar_index)
+            .copied()
+            .unwrap_or_default();
+        Some((coord.get(), current.get()))
+    }
+
+    // Checks that a single value matches the current coordinate on a design axis.
+    //
+    // Values on axes that are not present in `fvar` always match,
+    // since such axes describe a static font property.
+    fn value_matches(&self, axis_index: u16, value: f32) -> bool {
+        let tag = match self.table.axes.get(axis_index) {
+            Some(axis) => axis.tag,
+            None => return false,
+        };
+
+        match self.fvar_axes.into_iter().find(|a| a.tag == tag) {
+            Some(axis) => {
+                // Values outside the axis range would be clamped to its bounds
+                // and match incorrectly.
+                if value < axis.min_value || value > axis.max_value {
+                    return false;
+                }
+
+                match self.normalize(axis_index, value) {
+                    Some((coord, current)) => coord == current,
+                    None => false,
+                }
+            }
+            None => true,
+        }
+    }
+
+    fn range_matches(&self, axis_index: u16, min: f32, max: f32) -> bool {
+        let (min, current) = match self.normalize(axis_index, min) {
+            Some(v) => v,
+            None => return true,
+        };
+
+        match self.normalize(axis_index, max) {
+            Some((max, _)) => min <= current && current <= max,
+            None => false,
+        }
+    }
+
+    fn find_value(&self, axis_index: u16) -> Option<AxisValue<'a>> {
+        // Values that cover multiple axes take precedence.
+        let mut best_multiple: Option<(u16, AxisValue)> = None;
+        for value in self.table.values {
+            if let AxisValueKind::Multiple(records) = value.kind {
+                if !value.has_axis(axis_index) {
+                    continue;
+                }
+
+                if records
+                    .into_iter()
+                    .all(|r| self.value_matches(r.axis_index, r.value))
+                {
+                    match best_multiple {
+                        Some((len, _)) if len >= records.len() => {}
+                        _ => best_multiple = Some((records.len(), value)),
+                    }
+                }
+            }
+        }
+
+        if let Some((_, value)) = best_multiple {
+            return Some(value);
+        }
+
+        let mut range_match = None;
+        for value in self.table.values {
+            match value.kind {
+                AxisValueKind::Value {
+                    axis_index: index,
+                    value: v,
+                }
+                | AxisValueKind::Linked {
+                    axis_index: index,
+                    value: v,
+                    ..
+                } => {
+                    if index == axis_index && self.value_matches(index, v) {
+                        return Some(value);
+                    }
+                }
+                AxisValueKind::Range {
+                    axis_index: index,
+                    nominal_value,
+                    range_min_value,
+                    range_max_value,
+                } => {
+                    if index != axis_index {
+                        continue;
+                    }
+
+                    // Adjacent ranges can share a boundary,
+                    // therefore a nominal value match is preferred.
+                    if self.value_matches(index, nominal_value) {
+                        return Some(value);
+                    }
+
+                    if range_match.is_none()
+                        && self.range_matches(index, range_min_value, range_max_value)
+                    {
+                        range_match = Some(value);
+                    }
+                }
+                AxisValueKind::Multiple(_) => {}
+            }
+        }
+
+        range_match
+    }
+}
+
+impl<'a> Iterator for StyleNames<'a> {
+    type Item = StyleName<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (index, axis) = self.next_axis()?;
+            self.last = Some((axis.ordering, index));
+
+            if self.covered.get(usize::from(index)) == Some(&true) {
+                continue;
+            }
+
+            let value = match self.find_value(index) {
+                Some(v) => v,
+                None => continue,
+            };
+
+            if let AxisValueKind::Multiple(records) = value.kind {
+                for record in records {
+                    if let Some(covered) = self.covered.get_mut(usize::from(record.axis_index)) {
+                        *covered = true;
+                    }
+                }
+            }
+
+            return Some(StyleName {
+                axis,
+                value,
+                name: self.names.find_preferred(value.value_name_id),
+            });
+        }
+    }
+}
@@ -0,0 +1,165 @@
+//! A [Vertical Device Metrics Table](
+//! https://docs.microsoft.com/en-us/typography/opentype/spec/vdmx) implementation.
+
+use core::convert::TryFrom;
+
+use crate::parser::{FromData, LazyArray16, Offset, Offset16, Stream};
+
+/// An aspect ratio range.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct RatioRange {
+    /// Character set.
+    ///
+    /// In version 1 table, 0 means all glyphs and 1 means Windows ANSI subset.
+    pub char_set: u8,
+    /// Value to use for x-Ratio.
+    pub x_ratio: u8,
+    /// Starting y-Ratio value.
+    pub y_start_ratio: u8,
+    /// Ending y-Ratio value.
+    pub y_end_ratio: u8,
+}
+
+impl RatioRange {
+    /// Checks that the range matches a device aspect ratio.
+    ///
+    /// A range with all ratios set to 0 matches any aspect ratio.
+    pub fn contains(&self, x_ratio: u16, y_ratio: u16) -> bool {
+        if self.x_ratio == 0 && self.y_start_ratio == 0 && self.y_end_ratio == 0 {
+            return true;
+        }
+
+        // Compare `y_ratio / x_ratio` with `y_start_ratio / self.x_ratio`
+        // and `y_end_ratio / self.x_ratio` without division.
+        let y = u32::from(y_ratio) * u32::from(self.x_ratio);
+        let start = u32::from(self.y_start_ratio) * u32::from(x_ratio);
+        let end = u32::from(self.y_end_ratio) * u32::from(x_ratio);
+        start <= y && y <= end
+    }
+}
+
+impl FromData for RatioRange {
+    const SIZE: usize = 4;
+
+    #[inline]
+    fn parse(data: &[u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+        Some(RatioRange {
+            char_set: s.read::<u8>()?,
+            x_ratio: s.read::<u8>()?,
+            y_start_ratio: s.read::<u8>()?,
+            y_end_ratio: s.read::<u8>()?,
+        })
+    }
+}
+
+/// Vertical extents for a specific pixels-per-em.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Record {
+    /// The ppem to which values apply.
+    pub y_pel_height: u16,
+    /// Maximum value (in pels) for this ppem.
+    pub y_max: i16,
+    /// Minimum value (in pels) for this ppem.
+    pub y_min: i16,
+}
+
+impl FromData for Record {
+    const SIZE: usize = 6;
+
+    #[inline]
+    fn parse(data: &[u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+        Some(Record {
+            y_pel_height: s.read::<u16>()?,
+            y_max: s.read::<i16>()?,
+            y_min: s.read::<i16>()?,
+        })
+    }
+}
+
+/// A group of vertical extents records.
+#[derive(Clone, Copy, Debug)]
+pub struct Group<'a> {
+    /// Starting y-pel height.
+    pub start_size: u8,
+    /// Ending y-pel height.
+    pub end_size: u8,
+    /// A list of records. Sorted by `y_pel_height`.
+    pub records: LazyArray16<'a, Record>,
+}
+
+impl<'a> Group<'a> {
+    fn parse(data: &'a [u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+        let count = s.read::<u16>()?;
+        let start_size = s.read::<u8>()?;
+        let end_size = s.read::<u8>()?;
+        let records = s.read_array16::<Record>(count)?;
+        Some(Group {
+            start_size,
+            end_size,
+            records,
+        })
+    }
+
+    /// Returns a record for the specified pixels-per-em.
+    pub fn get(&self, pixels_per_em: u16) -> Option<Record> {
+        self.records
+            .binary_search_by(|r| r.y_pel_height.cmp(&pixels_per_em))
+            .map(|(_, r)| r)
+    }
+}
+
+/// A [Vertical Device Metrics Table](
+/// https://docs.microsoft.com/en-us/typography/opentype/spec/vdmx).
+#[derive(Clone, Copy, Debug)]
+pub struct Table<'a> {
+    /// A list of aspect ratio ranges.
+    pub ratio_ranges: LazyArray16<'a, RatioRange>,
+    data: &'a [u8],
+    offsets: LazyArray16<'a, Offset16>,
+}
+
+impl<'a> Table<'a> {
+    /// Parses a table from raw data.
+    pub fn parse(data: &'a [u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+
+        let version = s.read::<u16>()?;
+        if version > 1 {
+            return None;
+        }
+
+        s.skip::<u16>(); // number of groups
+        let ratios_count = s.read::<u16>()?;
+        let ratio_ranges = s.read_array16::<RatioRange>(ratios_count)?;
+        let offsets = s.read_array16::<Offset16>(ratios_count)?;
+
+        Some(Table {
+            ratio_ranges,
+            data,
+            offsets,
+        })
+    }
+
+    /// Returns a group for the aspect ratio range at the index.
+    ///
+    /// Multiple ranges can share the same group.
+    pub fn group(&self, index: u16) -> Option<Group<'a>> {
+        let offset = self.offsets.get(index)?.to_usize();
+        Group::parse(self.data.get(offset..)?)
+    }
+
+    /// Returns vertical extents for the specified pixels-per-em and device aspect ratio.
+    ///
+    /// Uses the first matching aspect ratio range.
+    pub fn extents(&self, pixels_per_em: u16, x_ratio: u16, y_ratio: u16) -> Option<Record> {
+        let index = self
+            .ratio_ranges
+            .into_iter()
+            .position(|range| range.contains(x_ratio, y_ratio))?;
+        let index = u16::try_from(index).ok()?;
+        self.group(index)?.get(pixels_per_em)
+    }
+}
@@ -0,0 +1,247 @@
+//! A [TrueType instructions](
+//! https://docs.microsoft.com/en-us/typography/opentype/spec/tt_instructions) decoder.
+//!
+//! Instructions are stored in the `fpgm` and `prep` tables and in glyphs.
+//! This module only decodes them. See the `hinting` module for execution.
+
+use crate::parser::{LazyArray16, Stream};
+
+/// Inline values of a push instruction.
+#[derive(Clone, Copy, Debug)]
+pub enum Operands<'a> {
+    /// The instruction has no inline values.
+    None,
+    /// Values of `NPUSHB` and `PUSHB`.
+    Bytes(LazyArray16<'a, u8>),
+    /// Values of `NPUSHW` and `PUSHW`.
+    Words(LazyArray16<'a, i16>),
+}
+
+/// A decoded instruction.
+#[derive(Clone, Copy, Debug)]
+pub struct Instruction<'a> {
+    /// Instruction offset in the bytecode.
+    pub offset: usize,
+    /// Instruction opcode.
+    ///
+    /// Flags, like the rounding flag of `MDAP`, are encoded in the opcode itself.
+    pub opcode: u8,
+    /// Inline values.
+    ///
+    /// Only push instructions have them. Operands of other instructions
+    /// are taken from the stack at runtime.
+    pub operands: Operands<'a>,
+}
+
+impl Instruction<'_> {
+    /// Returns the instruction mnemonic, without flags.
+    ///
+    /// Returns `None` for undefined opcodes, which can still be defined by `IDEF`.
+    #[inline]
+    pub fn name(&self) -> Option<&'static str> {
+        opcode_name(self.opcode)
+    }
+}
+
+/// An iterator over decoded instructions.
+///
+/// Stops at the end of data or at a truncated push instruction.
+/// Control flow is not validated.
+#[derive(Clone, Copy, Default, Debug)]
+pub struct Instructions<'a> {
+    data: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> Instructions<'a> {
+    /// Creates a new iterator over bytecode.
+    #[inline]
+    pub fn new(data: &'a [u8]) -> Self {
+        Instructions { data, offset: 0 }
+    }
+
+    /// Returns the offset of the next instruction.
+    ///
+    /// After iteration, an offset smaller than the data length indicates truncated data.
+    #[inline]
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+}
+
+impl<'a> Iterator for Instructions<'a> {
+    type Item = Instruction<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let offset = self.offset;
+        let mut s = Stream::new_at(self.data, offset)?;
+        let opcode = s.read::<u8>()?;
+        let operands = match opcode {
+            // NPUSHB
+            0x40 => {
+                let count = s.read::<u8>()?;
+                Operands::Bytes(s.read_array16::<u8>(u16::from(count))?)
+            }
+            // NPUSHW
+            0x41 => {
+                let count = s.read::<u8>()?;
+                Operands::Words(s.read_array16::<i16>(u16::from(count))?)
+            }
+            // PUSHB
+            0xB0..=0xB7 => Operands::Bytes(s.read_array16::<u8>(u16::from(opcode - 0xAF))?),
+            // PUSHW
+            0xB8..=0xBF => Operands::Words(s.read_array16::<i16>(u16::from(opcode - 0xB7))?),
+            _ => Operands::None,
+        };
+
+        self.offset = s.offset();
+        Some(Instruction {
+            offset,
+            opcode,
+            operands,
+        })
+    }
+}
+
+/// Returns the offset of the instruction after the one at `pc`.
+#[cfg(feature = "hinting")]
+pub(crate) fn next_instruction(code: &[u8], pc: usize) -> Option<usize> {
+    let mut iter = Instructions {
+        data: code,
+        offset: pc,
+    };
+    iter.next()?;
+    Some(iter.offset)
+}
+
+fn opcode_name(opcode: u8) -> Option<&'static str> {
+    let name = match opcode {
+        0x00..=0x01 => "SVTCA",
+        0x02..=0x03 => "SPVTCA",
+        0x04..=0x05 => "SFVTCA",
+        0x06..=0x07 => "SPVTL",
+        0x08..=0x09 => "SFVTL",
+        0x0A => "SPVFS",
+        0x0B => "SFVFS",
+        0x0C => "GPV",
+        0x0D => "GFV",
+        0x0E => "SFVTPV",
+        0x0F => "ISECT",
+        0x10 => "SRP0",
+        0x11 => "SRP1",
+        0x12 => "SRP2",
+        0x13 => "SZP0",
+        0x14 => "SZP1",
+        0x15 => "SZP2",
+        0x16 => "SZPS",
+        0x17 => "SLOOP",
+        0x18 => "RTG",
+        0x19 => "RTHG",
+        0x1A => "SMD",
+        0x1B => "ELSE",
+        0x1C => "JMPR",
+        0x1D => "SCVTCI",
+        0x1E => "SSWCI",
+        0x1F => "SSW",
+        0x20 => "DUP",
+        0x21 => "POP",
+        0x22 => "CLEAR",
+        0x23 => "SWAP",
+        0x24 => "DEPTH",
+        0x25 => "CINDEX",
+        0x26 => "MINDEX",
+        0x27 => "ALIGNPTS",
+        0x29 => "UTP",
+        0x2A => "LOOPCALL",
+        0x2B => "CALL",
+        0x2C => "FDEF",
+        0x2D => "ENDF",
+        0x2E..=0x2F => "MDAP",
+        0x30..=0x31 => "IUP",
+        0x32..=0x33 => "SHP",
+        0x34..=0x35 => "SHC",
+        0x36..=0x37 => "SHZ",
+        0x38 => "SHPIX",
+        0x39 => "IP",
+        0x3A..=0x3B => "MSIRP",
+        0x3C => "ALIGNRP",
+        0x3D => "RTDG",
+        0x3E..=0x3F => "MIAP",
+        0x40 => "NPUSHB",
+        0x41 => "NPUSHW",
+        0x42 => "WS",
+        0x43 => "RS",
+        0x44 => "WCVTP",
+        0x45 => "RCVT",
+        0x46..=0x47 => "GC",
+        0x48 => "SCFS",
+        0x49..=0x4A => "MD",
+        0x4B => "MPPEM",
+        0x4C => "MPS",
+        0x4D => "FLIPON",
+        0x4E => "FLIPOFF",
+        0x4F => "DEBUG",
+        0x50 => "LT",
+        0x51 => "LTEQ",
+        0x52 => "GT",
+        0x53 => "GTEQ",
+        0x54 => "EQ",
+        0x55 => "NEQ",
+        0x56 => "ODD",
+        0x57 => "EVEN",
+        0x58 => "IF",
+        0x59 => "EIF",
+        0x5A => "AND",
+        0x5B => "OR",
+        0x5C => "NOT",
+        0x5D => "DELTAP1",
+        0x5E => "SDB",
+        0x5F => "SDS",
+        0x60 => "ADD",
+        0x61 => "SUB",
+        0x62 => "DIV",
+        0x63 => "MUL",
+        0x64 => "ABS",
+        0x65 => "NEG",
+        0x66 => "FLOOR",
+        0x67 => "CEILING",
+        0x68..=0x6B => "ROUND",
+        0x6C..=0x6F => "NROUND",
+        0x70 => "WCVTF",
+        0x71 => "DELTAP2",
+        0x72 => "DELTAP3",
+        0x73 => "DELTAC1",
+        0x74 => "DELTAC2",
+        0x75 => "DELTAC3",
+        0x76 => "SROUND",
+        0x77 => "S45ROUND",
+        0x78 => "JROT",
+        0x79 => "JROF",
+        0x7A => "ROFF",
+        0x7C => "RUTG",
+        0x7D => "RDTG",
+        0x7E => "SANGW",
+        0x7F => "AA",
+        0x80 => "FLIPPT",
+        0x81 => "FLIPRGON",
+        0x82 => "FLIPRGOFF",
+        0x85 => "SCANCTRL",
+        0x86..=0x87 => "SDPVTL",
+        0x88 => "GETINFO",
+        0x89 => "IDEF",
+        0x8A => "ROLL",
+        0x8B => "MAX",
+        0x8C => "MIN",
+        0x8D => "SCANTYPE",
+        0x8E => "INSTCTRL",
+        0x91 => "GETVARIATION",
+        0x92 => "GETDATA",
+        0xB0..=0xB7 => "PUSHB",
+        0xB8..=0xBF => "PUSHW",
+        0xC0..=0xDF => "MDRP",
+        0xE0..=0xFF => "MIRP",
+        _ => return None,
+    };
+
+    Some(name)
+}
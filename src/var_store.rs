@@ -49,7 +49,7 @@ impl<'a> ItemVariationStore<'a> {
             let total = count.checked_mul(axis_count)?;
             VariationRegionList {
                 axis_count,
-                regions: regions_s.read_array16::<RegionAxisCoordinatesRecord>(total)?,
+                regions: regions_s.read_array16::<RegionAxisCoordinates>(total)?,
             }
         };
 
@@ -60,7 +60,7 @@ impl<'a> ItemVariationStore<'a> {
         })
     }
 
-    pub fn region_indices(&self, index: u16) -> Option<LazyArray16<u16>> {
+    pub fn region_indices(&self, index: u16) -> Option<LazyArray16<'a, u16>> {
         // Offsets in bytes from the start of the item variation store
         // to each item variation data subtable.
         let offset = self.data_offsets.get(index)?;
@@ -133,10 +133,18 @@ impl<'a> ItemVariationStore<'a> {
 #[derive(Clone, Copy, Debug)]
 pub struct VariationRegionList<'a> {
     axis_count: u16,
-    regions: LazyArray16<'a, RegionAxisCoordinatesRecord>,
+    regions: LazyArray16<'a, RegionAxisCoordinates>,
 }
 
 impl<'a> VariationRegionList<'a> {
+    /// Returns region coordinates for each axis.
+    #[inline]
+    pub(crate) fn region(&self, index: u16) -> Option<LazyArray16<'a, RegionAxisCoordinates>> {
+        let start = index.checked_mul(self.axis_count)?;
+        self.regions
+            .slice(start..start.checked_add(self.axis_count)?)
+    }
+
     #[inline]
     pub(crate) fn evaluate_region(&self, index: u16, coordinates: &[NormalizedCoordinate]) -> f32 {
         let mut v = 1.0;
@@ -158,16 +166,22 @@ impl<'a> VariationRegionList<'a> {
     }
 }
 
-#[derive(Clone, Copy, Debug)]
-struct RegionAxisCoordinatesRecord {
-    start_coord: i16,
-    peak_coord: i16,
-    end_coord: i16,
+/// A variation region range on a single axis.
+///
+/// Coordinates are normalized and stored as F2DOT14.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct RegionAxisCoordinates {
+    /// The region start coordinate.
+    pub start_coord: i16,
+    /// The region peak coordinate.
+    pub peak_coord: i16,
+    /// The region end coordinate.
+    pub end_coord: i16,
 }
 
-impl RegionAxisCoordinatesRecord {
+impl RegionAxisCoordinates {
     #[inline]
-    pub fn evaluate_axis(&self, coord: i16) -> f32 {
+    pub(crate) fn evaluate_axis(&self, coord: i16) -> f32 {
         let start = self.start_coord;
         let peak = self.peak_coord;
         let end = self.end_coord;
@@ -196,13 +210,13 @@ impl RegionAxisCoordinatesRecord {
     }
 }
 
-impl FromData for RegionAxisCoordinatesRecord {
+impl FromData for RegionAxisCoordinates {
     const SIZE: usize = 6;
 
     #[inline]
     fn parse(data: &[u8]) -> Option<Self> {
         let mut s = Stream::new(data);
-        Some(RegionAxisCoordinatesRecord {
+        Some(RegionAxisCoordinates {
             start_coord: s.read::<i16>()?,
             peak_coord: s.read::<i16>()?,
             end_coord: s.read::<i16>()?,
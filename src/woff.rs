@@ -0,0 +1,457 @@
+//! A [WOFF 1.0](https://www.w3.org/TR/WOFF/) container implementation.
+//!
+//! WOFF is a simple wrapper around TrueType/OpenType tables,
+//! where each table can be individually compressed with zlib.
+//! We do not parse fonts from WOFF directly. Instead, tables are decompressed
+//! into a caller-provided buffer and then can be passed to [`Face::from_raw_tables`].
+//!
+//! # Example
+//!
+//! ```no_run
+//! let data = std::fs::read("font.woff").unwrap();
+//! let woff = ttf_parser::woff::Woff::parse(&data).unwrap();
+//! let mut buffer = vec![0; woff.tables_len().unwrap()];
+//! let tables = woff.decompress_tables(&mut buffer).unwrap();
+//! let face = ttf_parser::Face::from_raw_tables(tables).unwrap();
+//! ```
+//!
+//! [`Face::from_raw_tables`]: crate::Face::from_raw_tables
+
+use core::convert::TryFrom;
+
+use crate::parser::{FromData, LazyArray16, NumFrom, Stream};
+use crate::{RawFaceTables, Tag};
+
+// 'wOFF'
+const SIGNATURE: u32 = 0x774F4646;
+const HEADER_SIZE: usize = 44;
+const SFNT_HEADER_SIZE: usize = 12;
+const SFNT_TABLE_RECORD_SIZE: usize = 16;
+
+/// The maximum possible deflate compression ratio.
+///
+/// Used to reject fonts that declare impossibly large tables,
+/// since we cannot check this until the data is actually decompressed.
+const MAX_COMPRESSION_RATIO: u32 = 1032;
+
+/// A list of WOFF decoding errors.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Error {
+    /// An attempt to read out of bounds detected.
+    ///
+    /// Should occur only on malformed fonts.
+    MalformedFont,
+
+    /// Data must start with `wOFF`.
+    UnknownSignature,
+
+    /// The provided buffer is too small to hold the decompressed data.
+    BufferTooSmall,
+
+    /// The zlib stream is malformed or its size doesn't match the declared one.
+    DecompressionFailed,
+}
+
+impl core::fmt::Display for Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Error::MalformedFont => write!(f, "malformed font"),
+            Error::UnknownSignature => write!(f, "unknown signature"),
+            Error::BufferTooSmall => write!(f, "buffer is too small"),
+            Error::DecompressionFailed => write!(f, "decompression failed"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
+
+/// A [table directory entry](https://www.w3.org/TR/WOFF/#TableDirectory).
+#[derive(Clone, Copy, Debug)]
+pub struct TableRecord {
+    /// A table tag.
+    pub tag: Tag,
+    /// An offset to the table data from the beginning of the WOFF file.
+    pub offset: u32,
+    /// A length of the compressed data.
+    ///
+    /// When equal to `original_length`, the table is stored uncompressed.
+    pub compressed_length: u32,
+    /// A length of the uncompressed table.
+    pub original_length: u32,
+    /// A checksum of the uncompressed table.
+    pub original_checksum: u32,
+}
+
+impl FromData for TableRecord {
+    const SIZE: usize = 20;
+
+    #[inline]
+    fn parse(data: &[u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+        Some(TableRecord {
+            tag: s.read::<Tag>()?,
+            offset: s.read::<u32>()?,
+            compressed_length: s.read::<u32>()?,
+            original_length: s.read::<u32>()?,
+            original_checksum: s.read::<u32>()?,
+        })
+    }
+}
+
+impl TableRecord {
+    #[inline]
+    fn is_compressed(&self) -> bool {
+        self.compressed_length < self.original_length
+    }
+
+    #[inline]
+    fn padded_length(&self) -> Option<usize> {
+        padded_len(usize::num_from(self.original_length))
+    }
+}
+
+/// A [WOFF 1.0](https://www.w3.org/TR/WOFF/) font.
+#[derive(Clone, Copy)]
+pub struct Woff<'a> {
+    data: &'a [u8],
+    /// The "sfnt version" of the input font.
+    ///
+    /// Usually `0x00010000` for TrueType and `OTTO` for CFF-based fonts.
+    pub flavor: u32,
+    /// The major version of the WOFF file.
+    ///
+    /// Not the WOFF format version.
+    pub major_version: u16,
+    /// The minor version of the WOFF file.
+    pub minor_version: u16,
+    /// A list of table records.
+    ///
+    /// Sorted by tag.
+    pub tables: LazyArray16<'a, TableRecord>,
+    metadata: Option<(&'a [u8], u32)>,
+    /// A private data block.
+    pub private_data: Option<&'a [u8]>,
+}
+
+impl<'a> Woff<'a> {
+    /// Parses a WOFF header and table directory.
+    ///
+    /// No decompression is performed at this stage.
+    pub fn parse(data: &'a [u8]) -> Result<Self, Error> {
+        let mut s = Stream::new(data);
+        let signature = s.read::<u32>().ok_or(Error::UnknownSignature)?;
+        if signature != SIGNATURE {
+            return Err(Error::UnknownSignature);
+        }
+
+        let mut s = Stream::new(data.get(..HEADER_SIZE).ok_or(Error::MalformedFont)?);
+        s.skip::<u32>(); // signature
+        let flavor = s.read::<u32>().ok_or(Error::MalformedFont)?;
+        let length = s.read::<u32>().ok_or(Error::MalformedFont)?;
+        let num_tables = s.read::<u16>().ok_or(Error::MalformedFont)?;
+        let reserved = s.read::<u16>().ok_or(Error::MalformedFont)?;
+        s.skip::<u32>(); // totalSfntSize
+        let major_version = s.read::<u16>().ok_or(Error::MalformedFont)?;
+        let minor_version = s.read::<u16>().ok_or(Error::MalformedFont)?;
+        let meta_offset = s.read::<u32>().ok_or(Error::MalformedFont)?;
+        let meta_length = s.read::<u32>().ok_or(Error::MalformedFont)?;
+        let meta_orig_length = s.read::<u32>().ok_or(Error::MalformedFont)?;
+        let priv_offset = s.read::<u32>().ok_or(Error::MalformedFont)?;
+        let priv_length = s.read::<u32>().ok_or(Error::MalformedFont)?;
+
+        if reserved != 0 || usize::num_from(length) != data.len() {
+            return Err(Error::MalformedFont);
+        }
+
+        let mut s = Stream::new_at(data, HEADER_SIZE).ok_or(Error::MalformedFont)?;
+        let tables = s
+            .read_array16::<TableRecord>(num_tables)
+            .ok_or(Error::MalformedFont)?;
+
+        for table in tables {
+            let start = usize::num_from(table.offset);
+            let end = start
+                .checked_add(usize::num_from(table.compressed_length))
+                .ok_or(Error::MalformedFont)?;
+            if end > data.len() || table.compressed_length > table.original_length {
+                return Err(Error::MalformedFont);
+            }
+        }
+
+        let metadata = if meta_offset != 0 {
+            let data = slice(data, meta_offset, meta_length).ok_or(Error::MalformedFont)?;
+            Some((data, meta_orig_length))
+        } else {
+            None
+        };
+
+        let private_data = if priv_offset != 0 {
+            Some(slice(data, priv_offset, priv_length).ok_or(Error::MalformedFont)?)
+        } else {
+            None
+        };
+
+        Ok(Woff {
+            data,
+            flavor,
+            major_version,
+            minor_version,
+            tables,
+            metadata,
+            private_data,
+        })
+    }
+
+    /// Returns a table record by tag.
+    pub fn table(&self, tag: Tag) -> Option<TableRecord> {
+        self.tables
+            .binary_search_by(|record| record.tag.cmp(&tag))
+            .map(|(_, record)| record)
+    }
+
+    /// Returns the buffer size required by [`Woff::decompress_tables`].
+    ///
+    /// Returns `None` on overflow.
+    pub fn tables_len(&self) -> Option<usize> {
+        let mut len = 0usize;
+        for table in self.tables {
+            len = len.checked_add(table.padded_length()?)?;
+        }
+
+        Some(len)
+    }
+
+    /// Returns the buffer size required by [`Woff::decompress_sfnt`].
+    ///
+    /// Returns `None` on overflow.
+    pub fn sfnt_len(&self) -> Option<usize> {
+        let records_len = usize::from(self.tables.len()).checked_mul(SFNT_TABLE_RECORD_SIZE)?;
+        SFNT_HEADER_SIZE
+            .checked_add(records_len)?
+            .checked_add(self.tables_len()?)
+    }
+
+    /// Decompresses a single table into the provided buffer.
+    ///
+    /// The buffer must be at least `record.original_length` bytes long.
+    ///
+    /// Returns a slice of the buffer containing the table data.
+    pub fn decompress_table<'b>(
+        &self,
+        record: TableRecord,
+        buffer: &'b mut [u8],
+    ) -> Result<&'b [u8], Error> {
+        let input = slice(self.data, record.offset, record.compressed_length)
+            .ok_or(Error::MalformedFont)?;
+        let len = usize::num_from(record.original_length);
+        let output = buffer.get_mut(..len).ok_or(Error::BufferTooSmall)?;
+
+        if record.is_compressed() {
+            inflate(input, output)?;
+        } else {
+            output.copy_from_slice(input);
+        }
+
+        // Tables are 4-byte aligned, so zero the padding if the buffer has space for it.
+        if let Some(padded_len) = record.padded_length() {
+            if let Some(padding) = buffer.get_mut(len..padded_len) {
+                padding.iter_mut().for_each(|b| *b = 0);
+            }
+        }
+
+        Ok(&buffer[..len])
+    }
+
+    /// Decompresses all tables into the provided buffer.
+    ///
+    /// Use [`Woff::tables_len`] to get the required buffer size.
+    ///
+    /// The returned [`RawFaceTables`] can be passed to
+    /// [`Face::from_raw_tables`](crate::Face::from_raw_tables).
+    pub fn decompress_tables<'b>(&self, buffer: &'b mut [u8]) -> Result<RawFaceTables<'b>, Error> {
+        self.check_compression_ratios()?;
+
+        let mut offset = 0usize;
+        for record in self.tables {
+            let len = record.padded_length().ok_or(Error::MalformedFont)?;
+            let chunk = buffer.get_mut(offset..).ok_or(Error::BufferTooSmall)?;
+            self.decompress_table(record, chunk)?;
+            offset = offset.checked_add(len).ok_or(Error::MalformedFont)?;
+        }
+
+        // Tables are stored sequentially, so we can simply iterate over records once again
+        // to split the now immutable buffer.
+        let buffer: &'b [u8] = buffer;
+        let mut tables = RawFaceTables::default();
+        let mut offset = 0usize;
+        for record in self.tables {
+            let len = usize::num_from(record.original_length);
+            let end = offset.checked_add(len).ok_or(Error::MalformedFont)?;
+            tables.set(record.tag, buffer.get(offset..end));
+            let padded_len = record.padded_length().ok_or(Error::MalformedFont)?;
+            offset = offset.checked_add(padded_len).ok_or(Error::MalformedFont)?;
+        }
+
+        Ok(tables)
+    }
+
+    /// Decompresses a font into the provided buffer as a regular TrueType/OpenType font.
+    ///
+    /// Use [`Woff::sfnt_len`] to get the required buffer size.
+    ///
+    /// Returns the number of bytes written.
+    /// The resulting data can be passed to [`Face::parse`](crate::Face::parse).
+    pub fn decompress_sfnt(&self, buffer: &mut [u8]) -> Result<usize, Error> {
+        self.check_compression_ratios()?;
+
+        let len = self.sfnt_len().ok_or(Error::MalformedFont)?;
+        let buffer = buffer.get_mut(..len).ok_or(Error::BufferTooSmall)?;
+
+        let num_tables = self.tables.len();
+        let mut entry_selector = 0u16;
+        while num_tables >> (entry_selector + 1) != 0 {
+            entry_selector += 1;
+        }
+        let search_range = (1u16 << entry_selector).wrapping_mul(16);
+        let range_shift = num_tables.wrapping_mul(16).wrapping_sub(search_range);
+
+        let mut w = Writer::new(buffer);
+        w.write_u32(self.flavor);
+        w.write_u16(num_tables);
+        w.write_u16(search_range);
+        w.write_u16(entry_selector);
+        w.write_u16(range_shift);
+
+        let mut offset = SFNT_HEADER_SIZE + usize::from(num_tables) * SFNT_TABLE_RECORD_SIZE;
+        for record in self.tables {
+            w.write_u32(record.tag.0);
+            w.write_u32(record.original_checksum);
+            w.write_u32(u32::try_from(offset).map_err(|_| Error::MalformedFont)?);
+            w.write_u32(record.original_length);
+            offset += record.padded_length().ok_or(Error::MalformedFont)?;
+        }
+
+        let mut offset = w.offset;
+        for record in self.tables {
+            let chunk = buffer.get_mut(offset..).ok_or(Error::BufferTooSmall)?;
+            self.decompress_table(record, chunk)?;
+            offset += record.padded_length().ok_or(Error::MalformedFont)?;
+        }
+
+        Ok(len)
+    }
+
+    /// Decompresses a font into a regular TrueType/OpenType font.
+    ///
+    /// The resulting data can be passed to [`Face::parse`](crate::Face::parse).
+    #[cfg(feature = "std")]
+    pub fn to_sfnt(&self) -> Result<std::vec::Vec<u8>, Error> {
+        // Check before allocating the buffer.
+        self.check_compression_ratios()?;
+
+        let mut buffer = vec![0; self.sfnt_len().ok_or(Error::MalformedFont)?];
+        self.decompress_sfnt(&mut buffer)?;
+        Ok(buffer)
+    }
+
+    fn check_compression_ratios(&self) -> Result<(), Error> {
+        for record in self.tables {
+            if record.original_length / MAX_COMPRESSION_RATIO > record.compressed_length {
+                return Err(Error::MalformedFont);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the length of the uncompressed extended metadata.
+    pub fn metadata_len(&self) -> Option<usize> {
+        self.metadata.map(|(_, len)| usize::num_from(len))
+    }
+
+    /// Decompresses the extended metadata into the provided buffer.
+    ///
+    /// Metadata is stored as an UTF-8 XML, but we don't validate it in any way.
+    ///
+    /// Returns `Ok(None)` when metadata is not present.
+    pub fn decompress_metadata<'b>(&self, buffer: &'b mut [u8]) -> Result<Option<&'b [u8]>, Error> {
+        let (input, len) = match self.metadata {
+            Some(v) => v,
+            None => return Ok(None),
+        };
+
+        let output = buffer
+            .get_mut(..usize::num_from(len))
+            .ok_or(Error::BufferTooSmall)?;
+        inflate(input, output)?;
+        Ok(Some(output))
+    }
+}
+
+impl core::fmt::Debug for Woff<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Woff {{ ... }}")
+    }
+}
+
+/// Checks that the provided data is a WOFF font.
+#[inline]
+pub fn is_woff(data: &[u8]) -> bool {
+    Stream::read_at::<u32>(data, 0) == Some(SIGNATURE)
+}
+
+fn inflate(input: &[u8], output: &mut [u8]) -> Result<(), Error> {
+    let len = miniz_oxide::inflate::decompress_slice_iter_to_slice(
+        output,
+        core::iter::once(input),
+        true,
+        false,
+    )
+    .map_err(|_| Error::DecompressionFailed)?;
+
+    // The decompressed data must have exactly the declared size.
+    if len != output.len() {
+        return Err(Error::DecompressionFailed);
+    }
+
+    Ok(())
+}
+
+fn slice(data: &[u8], offset: u32, len: u32) -> Option<&[u8]> {
+    let start = usize::num_from(offset);
+    let end = start.checked_add(usize::num_from(len))?;
+    data.get(start..end)
+}
+
+#[inline]
+fn padded_len(len: usize) -> Option<usize> {
+    Some(len.checked_add(3)? & !3)
+}
+
+struct Writer<'a> {
+    data: &'a mut [u8],
+    offset: usize,
+}
+
+impl<'a> Writer<'a> {
+    fn new(data: &'a mut [u8]) -> Self {
+        Writer { data, offset: 0 }
+    }
+
+    fn write_u16(&mut self, n: u16) {
+        self.write_bytes(&n.to_be_bytes());
+    }
+
+    fn write_u32(&mut self, n: u32) {
+        self.write_bytes(&n.to_be_bytes());
+    }
+
+    fn write_bytes(&mut self, bytes: &[u8]) {
+        let end = self.offset + bytes.len();
+        if let Some(data) = self.data.get_mut(self.offset..end) {
+            data.copy_from_slice(bytes);
+        }
+        self.offset = end;
+    }
+}
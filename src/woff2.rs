@@ -0,0 +1,1177 @@
+//! A [WOFF 2.0](https://www.w3.org/TR/WOFF2/) container implementation.
+//!
+//! Unlike WOFF 1.0, WOFF 2.0 stores all tables in a single Brotli stream
+//! and can additionally transform `glyf`, `loca` and `hmtx` tables
+//! into a more compact representation.
+//! Therefore we cannot simply decompress tables, but have to reconstruct them,
+//! which requires heap allocations.
+//!
+//! The result is a regular TrueType/OpenType font or font collection,
+//! which can be passed to [`Face::parse`](crate::Face::parse).
+//!
+//! # Example
+//!
+//! ```no_run
+//! let data = std::fs::read("font.woff2").unwrap();
+//! let woff2 = ttf_parser::woff2::Woff2::parse(&data).unwrap();
+//! let sfnt = woff2.to_sfnt().unwrap();
+//! let face = ttf_parser::Face::parse(&sfnt, 0).unwrap();
+//! ```
+
+use core::convert::TryFrom;
+use std::vec::Vec;
+
+use crate::parser::{NumFrom, Stream};
+use crate::Tag;
+
+// 'wOF2'
+const SIGNATURE: u32 = 0x774F4632;
+// 'ttcf'
+const COLLECTION_FLAVOR: u32 = 0x74746366;
+
+const GLYF: Tag = Tag::from_bytes(b"glyf");
+const LOCA: Tag = Tag::from_bytes(b"loca");
+const HMTX: Tag = Tag::from_bytes(b"hmtx");
+const HHEA: Tag = Tag::from_bytes(b"hhea");
+const HEAD: Tag = Tag::from_bytes(b"head");
+const MAXP: Tag = Tag::from_bytes(b"maxp");
+
+/// The maximum size of the decompressed font data.
+///
+/// Brotli can have an extremely high compression ratio, so we have to limit
+/// the output size to prevent excessive memory usage.
+/// The same limit is used by the reference decoder.
+const MAX_DECOMPRESSED_SIZE: usize = 30 * 1024 * 1024;
+
+/// Table tags that can be stored as a 6-bit index in a table directory entry.
+///
+/// <https://www.w3.org/TR/WOFF2/#table_dir_format>
+const KNOWN_TAGS: [&[u8; 4]; 63] = [
+    b"cmap", b"head", b"hhea", b"hmtx", b"maxp", b"name", b"OS/2", b"post", b"cvt ", b"fpgm",
+    b"glyf", b"loca", b"prep", b"CFF ", b"VORG", b"EBDT", b"EBLC", b"gasp", b"hdmx", b"kern",
+    b"LTSH", b"PCLT", b"VDMX", b"vhea", b"vmtx", b"BASE", b"GDEF", b"GPOS", b"GSUB", b"EBSC",
+    b"JSTF", b"MATH", b"CBDT", b"CBLC", b"COLR", b"CPAL", b"SVG ", b"sbix", b"acnt", b"avar",
+    b"bdat", b"bloc", b"bsln", b"cvar", b"fdsc", b"feat", b"fmtx", b"fvar", b"gvar", b"hsty",
+    b"just", b"lcar", b"mort", b"morx", b"opbd", b"prop", b"trak", b"Zapf", b"Silf", b"Glat",
+    b"Gloc", b"Feat", b"Sill",
+];
+
+/// A list of WOFF2 decoding errors.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Error {
+    /// An attempt to read out of bounds detected.
+    ///
+    /// Should occur only on malformed fonts.
+    MalformedFont,
+
+    /// Data must start with `wOF2`.
+    UnknownSignature,
+
+    /// The Brotli stream is malformed or its size doesn't match the declared one.
+    DecompressionFailed,
+
+    /// A transformed table cannot be reconstructed.
+    MalformedTransform,
+
+    /// The decompressed font is larger than 30MiB.
+    TooLarge,
+}
+
+impl core::fmt::Display for Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Error::MalformedFont => write!(f, "malformed font"),
+            Error::UnknownSignature => write!(f, "unknown signature"),
+            Error::DecompressionFailed => write!(f, "decompression failed"),
+            Error::MalformedTransform => write!(f, "malformed table transform"),
+            Error::TooLarge => write!(f, "decompressed font is too large"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// A [table directory entry](https://www.w3.org/TR/WOFF2/#table_dir_format).
+#[derive(Clone, Copy, Debug)]
+pub struct TableRecord {
+    /// A table tag.
+    pub tag: Tag,
+    /// A preprocessing transformation version.
+    ///
+    /// Note that for `glyf` and `loca` tables, version 0 indicates a transformed table
+    /// and version 3 indicates a null transform.
+    pub transform_version: u8,
+    /// A length of the original table.
+    pub original_length: u32,
+    /// A length of the transformed table.
+    ///
+    /// Set only for transformed tables.
+    pub transform_length: Option<u32>,
+}
+
+impl TableRecord {
+    /// Checks that the table is stored in a transformed form.
+    #[inline]
+    pub fn is_transformed(&self) -> bool {
+        self.transform_length.is_some()
+    }
+
+    // A length of the table data in the decompressed stream.
+    #[inline]
+    fn stream_length(&self) -> u32 {
+        self.transform_length.unwrap_or(self.original_length)
+    }
+}
+
+/// A font in a WOFF2 font collection.
+#[derive(Clone, Debug)]
+pub struct CollectionFont {
+    /// The "sfnt version" of the font.
+    pub flavor: u32,
+    /// Indices into the [`Woff2::tables`] list.
+    pub table_indices: Vec<u16>,
+}
+
+/// A [WOFF 2.0](https://www.w3.org/TR/WOFF2/) font.
+#[derive(Clone)]
+pub struct Woff2<'a> {
+    /// The "sfnt version" of the input font.
+    ///
+    /// Usually `0x00010000` for TrueType, `OTTO` for CFF-based fonts
+    /// and `ttcf` for font collections.
+    pub flavor: u32,
+    /// The major version of the WOFF file.
+    ///
+    /// Not the WOFF format version.
+    pub major_version: u16,
+    /// The minor version of the WOFF file.
+    pub minor_version: u16,
+    /// A list of table records.
+    ///
+    /// Tables are stored in the decompressed stream in this specific order.
+    pub tables: Vec<TableRecord>,
+    /// A list of fonts in a font collection.
+    ///
+    /// Empty for a regular font.
+    pub collection_fonts: Vec<CollectionFont>,
+    /// A collection header version.
+    ///
+    /// Set only for font collections.
+    pub collection_version: u32,
+    compressed_data: &'a [u8],
+    metadata: Option<(&'a [u8], u32)>,
+    /// A private data block.
+    pub private_data: Option<&'a [u8]>,
+}
+
+impl<'a> Woff2<'a> {
+    /// Parses a WOFF2 header, table directory and collection directory.
+    ///
+    /// No decompression is performed at this stage.
+    pub fn parse(data: &'a [u8]) -> Result<Self, Error> {
+        let mut s = Stream::new(data);
+        let signature = s.read::<u32>().ok_or(Error::UnknownSignature)?;
+        if signature != SIGNATURE {
+            return Err(Error::UnknownSignature);
+        }
+
+        let flavor = s.read::<u32>().ok_or(Error::MalformedFont)?;
+        let length = s.read::<u32>().ok_or(Error::MalformedFont)?;
+        let num_tables = s.read::<u16>().ok_or(Error::MalformedFont)?;
+        let reserved = s.read::<u16>().ok_or(Error::MalformedFont)?;
+        s.skip::<u32>(); // totalSfntSize
+        let total_compressed_size = s.read::<u32>().ok_or(Error::MalformedFont)?;
+        let major_version = s.read::<u16>().ok_or(Error::MalformedFont)?;
+        let minor_version = s.read::<u16>().ok_or(Error::MalformedFont)?;
+        let meta_offset = s.read::<u32>().ok_or(Error::MalformedFont)?;
+        let meta_length = s.read::<u32>().ok_or(Error::MalformedFont)?;
+        let meta_orig_length = s.read::<u32>().ok_or(Error::MalformedFont)?;
+        let priv_offset = s.read::<u32>().ok_or(Error::MalformedFont)?;
+        let priv_length = s.read::<u32>().ok_or(Error::MalformedFont)?;
+
+        if reserved != 0 || num_tables == 0 || usize::num_from(length) != data.len() {
+            return Err(Error::MalformedFont);
+        }
+
+        let mut tables = Vec::with_capacity(usize::from(num_tables));
+        for _ in 0..num_tables {
+            tables.push(parse_table_record(&mut s).ok_or(Error::MalformedFont)?);
+        }
+
+        let mut collection_fonts = Vec::new();
+        let mut collection_version = 0;
+        if flavor == COLLECTION_FLAVOR {
+            collection_version = s.read::<u32>().ok_or(Error::MalformedFont)?;
+            let num_fonts = read_u255_u16(&mut s).ok_or(Error::MalformedFont)?;
+            if num_fonts == 0 {
+                return Err(Error::MalformedFont);
+            }
+
+            for _ in 0..num_fonts {
+                let num_tables = read_u255_u16(&mut s).ok_or(Error::MalformedFont)?;
+                let flavor = s.read::<u32>().ok_or(Error::MalformedFont)?;
+                let mut table_indices = Vec::with_capacity(usize::from(num_tables));
+                for _ in 0..num_tables {
+                    let index = read_u255_u16(&mut s).ok_or(Error::MalformedFont)?;
+                    if usize::from(index) >= tables.len() {
+                        return Err(Error::MalformedFont);
+                    }
+                    table_indices.push(index);
+                }
+
+                collection_fonts.push(CollectionFont {
+                    flavor,
+                    table_indices,
+                });
+            }
+        }
+
+        let compressed_data = s
+            .read_bytes(usize::num_from(total_compressed_size))
+            .ok_or(Error::MalformedFont)?;
+
+        let metadata = if meta_offset != 0 {
+            let data = slice(data, meta_offset, meta_length).ok_or(Error::MalformedFont)?;
+            Some((data, meta_orig_length))
+        } else {
+            None
+        };
+
+        let private_data = if priv_offset != 0 {
+            Some(slice(data, priv_offset, priv_length).ok_or(Error::MalformedFont)?)
+        } else {
+            None
+        };
+
+        Ok(Woff2 {
+            flavor,
+            major_version,
+            minor_version,
+            tables,
+            collection_fonts,
+            collection_version,
+            compressed_data,
+            metadata,
+            private_data,
+        })
+    }
+
+    /// Checks that the font is a font collection.
+    #[inline]
+    pub fn is_collection(&self) -> bool {
+        self.flavor == COLLECTION_FLAVOR
+    }
+
+    /// Decompresses and reconstructs a font.
+    ///
+    /// Returns a TrueType/OpenType font or a font collection
+    /// when [`Woff2::is_collection`] is set.
+    /// The resulting data can be passed to [`Face::parse`](crate::Face::parse).
+    pub fn to_sfnt(&self) -> Result<Vec<u8>, Error> {
+        let mut stream_len = 0usize;
+        for table in &self.tables {
+            stream_len = stream_len
+                .checked_add(usize::num_from(table.stream_length()))
+                .ok_or(Error::TooLarge)?;
+        }
+
+        if stream_len > MAX_DECOMPRESSED_SIZE {
+            return Err(Error::TooLarge);
+        }
+
+        let stream = decompress(self.compressed_data, stream_len)?;
+
+        // Split the decompressed stream into tables.
+        let mut tables_data = Vec::with_capacity(self.tables.len());
+        let mut offset = 0;
+        for table in &self.tables {
+            let len = usize::num_from(table.stream_length());
+            tables_data.push(TableData::Borrowed(&stream[offset..offset + len]));
+            offset += len;
+        }
+
+        if self.is_collection() {
+            for font in &self.collection_fonts {
+                self.reconstruct_tables(&font.table_indices, &mut tables_data)?;
+            }
+        } else {
+            let indices: Vec<u16> = (0..self.tables.len() as u16).collect();
+            self.reconstruct_tables(&indices, &mut tables_data)?;
+        }
+
+        let mut writer = SfntWriter::default();
+        if self.is_collection() {
+            let fonts: Vec<(u32, &[u16])> = self
+                .collection_fonts
+                .iter()
+                .map(|font| (font.flavor, font.table_indices.as_slice()))
+                .collect();
+            writer.write_collection(self.collection_version, &fonts, &self.tables, &tables_data)?;
+        } else {
+            let indices: Vec<u16> = (0..self.tables.len() as u16).collect();
+            writer.write_font(self.flavor, &indices, &self.tables, &tables_data)?;
+        }
+
+        Ok(writer.data)
+    }
+
+    /// Decompresses the extended metadata.
+    ///
+    /// Metadata is stored as an UTF-8 XML, but we don't validate it in any way.
+    ///
+    /// Returns `Ok(None)` when metadata is not present.
+    pub fn decompress_metadata(&self) -> Result<Option<Vec<u8>>, Error> {
+        match self.metadata {
+            Some((data, len)) => {
+                let len = usize::num_from(len);
+                if len > MAX_DECOMPRESSED_SIZE {
+                    return Err(Error::TooLarge);
+                }
+
+                decompress(data, len).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    // Replaces transformed `glyf`, `loca` and `hmtx` tables of a single font
+    // with reconstructed ones.
+    fn reconstruct_tables(
+        &self,
+        indices: &[u16],
+        tables_data: &mut [TableData],
+    ) -> Result<(), Error> {
+        let find = |tag: Tag| {
+            indices
+                .iter()
+                .map(|i| usize::from(*i))
+                .find(|i| self.tables[*i].tag == tag)
+        };
+
+        let glyf_index = find(GLYF);
+        let loca_index = find(LOCA);
+        let hmtx_index = find(HMTX);
+
+        let glyf_transformed = glyf_index.map(|i| self.tables[i].is_transformed()) == Some(true);
+        let loca_transformed = loca_index.map(|i| self.tables[i].is_transformed()) == Some(true);
+        let hmtx_transformed = hmtx_index.map(|i| self.tables[i].is_transformed()) == Some(true);
+
+        if glyf_transformed != loca_transformed {
+            return Err(Error::MalformedTransform);
+        }
+
+        if !glyf_transformed && !hmtx_transformed {
+            return Ok(());
+        }
+
+        let (glyf_index, loca_index) = match (glyf_index, loca_index) {
+            (Some(glyf), Some(loca)) if glyf_transformed => (glyf, loca),
+            // The `hmtx` transform relies on `glyf` bounding boxes.
+            _ => return Err(Error::MalformedTransform),
+        };
+
+        // Tables can be shared between fonts in a collection,
+        // in which case they were already reconstructed.
+        let x_mins = match (&tables_data[glyf_index], &tables_data[loca_index]) {
+            (TableData::Reconstructed(_, x_mins), TableData::Reconstructed(..)) => x_mins.clone(),
+            (TableData::Borrowed(data), _) => {
+                let head = find(HEAD)
+                    .and_then(|i| tables_data[i].as_slice())
+                    .ok_or(Error::MalformedTransform)?;
+                let index_to_loc_format =
+                    Stream::read_at::<u16>(head, 50).ok_or(Error::MalformedTransform)?;
+
+                let glyf = reconstruct_glyf(data).ok_or(Error::MalformedTransform)?;
+                if glyf.index_format != index_to_loc_format {
+                    return Err(Error::MalformedTransform);
+                }
+
+                let x_mins = glyf.x_mins.clone();
+                tables_data[glyf_index] = TableData::Reconstructed(glyf.glyf, glyf.x_mins);
+                tables_data[loca_index] = TableData::Reconstructed(glyf.loca, Vec::new());
+                x_mins
+            }
+            _ => return Err(Error::MalformedTransform),
+        };
+
+        if let Some(hmtx_index) = hmtx_index {
+            if let TableData::Borrowed(data) = tables_data[hmtx_index] {
+                if hmtx_transformed {
+                    let hhea = find(HHEA)
+                        .and_then(|i| tables_data[i].as_slice())
+                        .ok_or(Error::MalformedTransform)?;
+                    let maxp = find(MAXP)
+                        .and_then(|i| tables_data[i].as_slice())
+                        .ok_or(Error::MalformedTransform)?;
+                    let number_of_metrics =
+                        Stream::read_at::<u16>(hhea, 34).ok_or(Error::MalformedTransform)?;
+                    let number_of_glyphs =
+                        Stream::read_at::<u16>(maxp, 4).ok_or(Error::MalformedTransform)?;
+
+                    let hmtx = reconstruct_hmtx(data, number_of_metrics, number_of_glyphs, &x_mins)
+                        .ok_or(Error::MalformedTransform)?;
+                    tables_data[hmtx_index] = TableData::Reconstructed(hmtx, Vec::new());
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl core::fmt::Debug for Woff2<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Woff2 {{ ... }}")
+    }
+}
+
+/// Checks that the provided data is a WOFF2 font.
+#[inline]
+pub fn is_woff2(data: &[u8]) -> bool {
+    Stream::read_at::<u32>(data, 0) == Some(SIGNATURE)
+}
+
+enum TableData<'a> {
+    Borrowed(&'a [u8]),
+    // Reconstructed table data and glyphs' xMin values for the `glyf` table.
+    Reconstructed(Vec<u8>, Vec<i16>),
+}
+
+impl TableData<'_> {
+    fn as_slice(&self) -> Option<&[u8]> {
+        match self {
+            TableData::Borrowed(data) => Some(data),
+            TableData::Reconstructed(data, _) => Some(data),
+        }
+    }
+}
+
+fn parse_table_record(s: &mut Stream) -> Option<TableRecord> {
+    let flags = s.read::<u8>()?;
+    let tag_index = flags & 0x3F;
+    let transform_version = flags >> 6;
+    let tag = if tag_index == 0x3F {
+        s.read::<Tag>()?
+    } else {
+        Tag::from_bytes(KNOWN_TAGS.get(usize::from(tag_index))?)
+    };
+
+    let original_length = read_u_base128(s)?;
+
+    // `glyf` and `loca` tables use 3 as a null transform, while other tables use 0.
+    let is_transformed = if tag == GLYF || tag == LOCA {
+        match transform_version {
+            0 => true,
+            3 => false,
+            _ => return None,
+        }
+    } else if tag == HMTX {
+        match transform_version {
+            0 => false,
+            1 => true,
+            _ => return None,
+        }
+    } else {
+        if transform_version != 0 {
+            return None;
+        }
+
+        false
+    };
+
+    let transform_length = if is_transformed {
+        let len = read_u_base128(s)?;
+        // The transformed `loca` table must be empty.
+        if tag == LOCA && len != 0 {
+            return None;
+        }
+
+        Some(len)
+    } else {
+        None
+    };
+
+    Some(TableRecord {
+        tag,
+        transform_version,
+        original_length,
+        transform_length,
+    })
+}
+
+// https://www.w3.org/TR/WOFF2/#DataTypes
+fn read_u_base128(s: &mut Stream) -> Option<u32> {
+    let mut accum = 0u32;
+    for i in 0..5 {
+        let byte = s.read::<u8>()?;
+
+        // No leading zeros.
+        if i == 0 && byte == 0x80 {
+            return None;
+        }
+
+        // Check for overflow.
+        if accum & 0xFE000000 != 0 {
+            return None;
+        }
+
+        accum = (accum << 7) | u32::from(byte & 0x7F);
+
+        if byte & 0x80 == 0 {
+            return Some(accum);
+        }
+    }
+
+    // UIntBase128 sequence cannot be longer than 5 bytes.
+    None
+}
+
+// https://www.w3.org/TR/WOFF2/#DataTypes
+fn read_u255_u16(s: &mut Stream) -> Option<u16> {
+    const ONE_MORE_BYTE_CODE_1: u8 = 255;
+    const ONE_MORE_BYTE_CODE_2: u8 = 254;
+    const WORD_CODE: u8 = 253;
+    const LOWEST_U_CODE: u16 = 253;
+
+    let code = s.read::<u8>()?;
+    match code {
+        WORD_CODE => s.read::<u16>(),
+        ONE_MORE_BYTE_CODE_1 => Some(u16::from(s.read::<u8>()?) + LOWEST_U_CODE),
+        ONE_MORE_BYTE_CODE_2 => Some(u16::from(s.read::<u8>()?) + LOWEST_U_CODE * 2),
+        _ => Some(u16::from(code)),
+    }
+}
+
+fn decompress(input: &[u8], len: usize) -> Result<Vec<u8>, Error> {
+    use brotli_decompressor::BrotliResult;
+
+    let mut output = vec![0; len];
+    let info = brotli_decompressor::brotli_decode(input, &mut output);
+    match info.result {
+        BrotliResult::ResultSuccess if info.decoded_size == len => Ok(output),
+        _ => Err(Error::DecompressionFailed),
+    }
+}
+
+fn slice(data: &[u8], offset: u32, len: u32) -> Option<&[u8]> {
+    let start = usize::num_from(offset);
+    let end = start.checked_add(usize::num_from(len))?;
+    data.get(start..end)
+}
+
+struct ReconstructedGlyf {
+    glyf: Vec<u8>,
+    loca: Vec<u8>,
+    index_format: u16,
+    x_mins: Vec<i16>,
+}
+
+// https://www.w3.org/TR/WOFF2/#glyf_table_format
+fn reconstruct_glyf(data: &[u8]) -> Option<ReconstructedGlyf> {
+    let mut s = Stream::new(data);
+    s.skip::<u16>(); // reserved
+    let option_flags = s.read::<u16>()?;
+    let number_of_glyphs = s.read::<u16>()?;
+    let index_format = s.read::<u16>()?;
+    if index_format > 1 {
+        return None;
+    }
+
+    let n_contour_stream_size = s.read::<u32>()?;
+    let n_points_stream_size = s.read::<u32>()?;
+    let flag_stream_size = s.read::<u32>()?;
+    let glyph_stream_size = s.read::<u32>()?;
+    let composite_stream_size = s.read::<u32>()?;
+    let bbox_stream_size = s.read::<u32>()?;
+    let instruction_stream_size = s.read::<u32>()?;
+
+    let mut n_contour_stream = Stream::new(s.read_bytes(usize::num_from(n_contour_stream_size))?);
+    let mut n_points_stream = Stream::new(s.read_bytes(usize::num_from(n_points_stream_size))?);
+    let mut flag_stream = Stream::new(s.read_bytes(usize::num_from(flag_stream_size))?);
+    let mut glyph_stream = Stream::new(s.read_bytes(usize::num_from(glyph_stream_size))?);
+    let mut composite_stream = Stream::new(s.read_bytes(usize::num_from(composite_stream_size))?);
+    let mut bbox_stream = Stream::new(s.read_bytes(usize::num_from(bbox_stream_size))?);
+    let mut instruction_stream =
+        Stream::new(s.read_bytes(usize::num_from(instruction_stream_size))?);
+
+    let bbox_bitmap_len = ((usize::from(number_of_glyphs) + 31) >> 5) << 2;
+    let bbox_bitmap = bbox_stream.read_bytes(bbox_bitmap_len)?;
+
+    let overlap_bitmap = if option_flags & 1 != 0 {
+        Some(s.read_bytes((usize::from(number_of_glyphs) + 7) >> 3)?)
+    } else {
+        None
+    };
+
+    let mut glyf = Vec::with_capacity(data.len() * 2);
+    let mut loca = Vec::with_capacity((usize::from(number_of_glyphs) + 1) * 4);
+    let mut x_mins = Vec::with_capacity(usize::from(number_of_glyphs));
+    let mut points = Vec::new();
+
+    let write_loca = |loca: &mut Vec<u8>, offset: usize| -> Option<()> {
+        if index_format == 0 {
+            loca.extend_from_slice(&u16::try_from(offset / 2).ok()?.to_be_bytes());
+        } else {
+            loca.extend_from_slice(&u32::try_from(offset).ok()?.to_be_bytes());
+        }
+
+        Some(())
+    };
+
+    for glyph_id in 0..number_of_glyphs {
+        write_loca(&mut loca, glyf.len())?;
+
+        let has_bbox = bit_is_set(bbox_bitmap, glyph_id);
+        let number_of_contours = n_contour_stream.read::<i16>()?;
+        if number_of_contours == 0 {
+            // An empty glyph cannot have a bounding box.
+            if has_bbox {
+                return None;
+            }
+
+            x_mins.push(0);
+            continue;
+        }
+
+        let bbox_offset = glyf.len() + 2;
+        write_i16(&mut glyf, number_of_contours);
+        glyf.extend_from_slice(&[0; 8]); // bbox, will be set later
+
+        let bbox = if number_of_contours > 0 {
+            let overlap = overlap_bitmap
+                .map(|bitmap| bit_is_set(bitmap, glyph_id))
+                .unwrap_or(false);
+
+            // Simple glyph.
+            let mut total_points = 0u16;
+            for _ in 0..number_of_contours {
+                let n = read_u255_u16(&mut n_points_stream)?;
+                total_points = total_points.checked_add(n)?;
+                write_u16(&mut glyf, total_points.checked_sub(1)?);
+            }
+
+            decode_triplets(
+                total_points,
+                &mut flag_stream,
+                &mut glyph_stream,
+                &mut points,
+            )?;
+
+            let instructions_len = read_u255_u16(&mut glyph_stream)?;
+            let instructions = instruction_stream.read_bytes(usize::from(instructions_len))?;
+            write_u16(&mut glyf, instructions_len);
+            glyf.extend_from_slice(instructions);
+
+            write_simple_glyph_points(&points, overlap, &mut glyf);
+
+            if has_bbox {
+                read_bbox(&mut bbox_stream)?
+            } else {
+                calculate_bbox(&points)?
+            }
+        } else {
+            // Composite glyph.
+
+            // Composite glyphs must have an explicit bounding box.
+            if !has_bbox {
+                return None;
+            }
+
+            let have_instructions = copy_composite_glyph(&mut composite_stream, &mut glyf)?;
+            if have_instructions {
+                let instructions_len = read_u255_u16(&mut glyph_stream)?;
+                let instructions = instruction_stream.read_bytes(usize::from(instructions_len))?;
+                write_u16(&mut glyf, instructions_len);
+                glyf.extend_from_slice(instructions);
+            }
+
+            read_bbox(&mut bbox_stream)?
+        };
+
+        for (i, n) in bbox.iter().enumerate() {
+            glyf[bbox_offset + i * 2..bbox_offset + i * 2 + 2].copy_from_slice(&n.to_be_bytes());
+        }
+        x_mins.push(bbox[0]);
+
+        // Glyph records are 4-byte aligned.
+        while glyf.len() & 3 != 0 {
+            glyf.push(0);
+        }
+    }
+
+    write_loca(&mut loca, glyf.len())?;
+
+    Some(ReconstructedGlyf {
+        glyf,
+        loca,
+        index_format,
+        x_mins,
+    })
+}
+
+#[derive(Clone, Copy)]
+struct Point {
+    dx: i16,
+    dy: i16,
+    on_curve: bool,
+}
+
+// https://www.w3.org/TR/WOFF2/#triplet_decoding
+fn decode_triplets(
+    number_of_points: u16,
+    flag_stream: &mut Stream,
+    glyph_stream: &mut Stream,
+    points: &mut Vec<Point>,
+) -> Option<()> {
+    fn with_sign(flag: u8, value: i32) -> i32 {
+        if flag & 1 != 0 {
+            value
+        } else {
+            -value
+        }
+    }
+
+    points.clear();
+    for _ in 0..number_of_points {
+        let flag = flag_stream.read::<u8>()?;
+        let on_curve = flag >> 7 == 0;
+        let flag = flag & 0x7F;
+
+        let (dx, dy) = if flag < 10 {
+            let b0 = i32::from(glyph_stream.read::<u8>()?);
+            (0, with_sign(flag, (i32::from(flag & 14) << 7) + b0))
+        } else if flag < 20 {
+            let b0 = i32::from(glyph_stream.read::<u8>()?);
+            (with_sign(flag, (i32::from((flag - 10) & 14) << 7) + b0), 0)
+        } else if flag < 84 {
+            let b0 = i32::from(flag - 20);
+            let b1 = i32::from(glyph_stream.read::<u8>()?);
+            (
+                with_sign(flag, 1 + (b0 & 0x30) + (b1 >> 4)),
+                with_sign(flag >> 1, 1 + ((b0 & 0x0C) << 2) + (b1 & 0x0F)),
+            )
+        } else if flag < 120 {
+            let b0 = i32::from(flag - 84);
+            let b1 = i32::from(glyph_stream.read::<u8>()?);
+            let b2 = i32::from(glyph_stream.read::<u8>()?);
+            (
+                with_sign(flag, 1 + ((b0 / 12) << 8) + b1),
+                with_sign(flag >> 1, 1 + (((b0 % 12) >> 2) << 8) + b2),
+            )
+        } else if flag < 124 {
+            let b1 = i32::from(glyph_stream.read::<u8>()?);
+            let b2 = i32::from(glyph_stream.read::<u8>()?);
+            let b3 = i32::from(glyph_stream.read::<u8>()?);
+            (
+                with_sign(flag, (b1 << 4) + (b2 >> 4)),
+                with_sign(flag >> 1, ((b2 & 0x0F) << 8) + b3),
+            )
+        } else {
+            let b1 = i32::from(glyph_stream.read::<u8>()?);
+            let b2 = i32::from(glyph_stream.read::<u8>()?);
+            let b3 = i32::from(glyph_stream.read::<u8>()?);
+            let b4 = i32::from(glyph_stream.read::<u8>()?);
+            (
+                with_sign(flag, (b1 << 8) + b2),
+                with_sign(flag >> 1, (b3 << 8) + b4),
+            )
+        };
+
+        points.push(Point {
+            dx: i16::try_from(dx).ok()?,
+            dy: i16::try_from(dy).ok()?,
+            on_curve,
+        });
+    }
+
+    Some(())
+}
+
+// Writes flags and coordinates of a simple glyph using the most compact representation.
+fn write_simple_glyph_points(points: &[Point], overlap: bool, glyf: &mut Vec<u8>) {
+    const ON_CURVE_POINT: u8 = 0x01;
+    const X_SHORT_VECTOR: u8 = 0x02;
+    const Y_SHORT_VECTOR: u8 = 0x04;
+    const REPEAT_FLAG: u8 = 0x08;
+    const X_IS_SAME_OR_POSITIVE_X_SHORT_VECTOR: u8 = 0x10;
+    const Y_IS_SAME_OR_POSITIVE_Y_SHORT_VECTOR: u8 = 0x20;
+    const OVERLAP_SIMPLE: u8 = 0x40;
+
+    fn coordinate_flag(d: i16, short_flag: u8, same_flag: u8) -> u8 {
+        if d == 0 {
+            same_flag
+        } else if d > -256 && d < 256 {
+            short_flag | if d > 0 { same_flag } else { 0 }
+        } else {
+            0
+        }
+    }
+
+    let mut last_flag = None;
+    let mut repeat_count = 0u8;
+    for (i, point) in points.iter().enumerate() {
+        let mut flag = if point.on_curve { ON_CURVE_POINT } else { 0 };
+        if overlap && i == 0 {
+            flag |= OVERLAP_SIMPLE;
+        }
+
+        flag |= coordinate_flag(
+            point.dx,
+            X_SHORT_VECTOR,
+            X_IS_SAME_OR_POSITIVE_X_SHORT_VECTOR,
+        );
+        flag |= coordinate_flag(
+            point.dy,
+            Y_SHORT_VECTOR,
+            Y_IS_SAME_OR_POSITIVE_Y_SHORT_VECTOR,
+        );
+
+        if last_flag == Some(flag) && repeat_count != u8::MAX {
+            if repeat_count == 0 {
+                // Mark the previous flag as repeated.
+                if let Some(last) = glyf.last_mut() {
+                    *last |= REPEAT_FLAG;
+                }
+                glyf.push(1);
+            } else if let Some(last) = glyf.last_mut() {
+                *last += 1;
+            }
+
+            repeat_count += 1;
+        } else {
+            glyf.push(flag);
+            repeat_count = 0;
+        }
+
+        last_flag = Some(flag);
+    }
+
+    for point in points {
+        if point.dx == 0 {
+            // Skip.
+        } else if point.dx > -256 && point.dx < 256 {
+            glyf.push(point.dx.unsigned_abs() as u8);
+        } else {
+            write_i16(glyf, point.dx);
+        }
+    }
+
+    for point in points {
+        if point.dy == 0 {
+            // Skip.
+        } else if point.dy > -256 && point.dy < 256 {
+            glyf.push(point.dy.unsigned_abs() as u8);
+        } else {
+            write_i16(glyf, point.dy);
+        }
+    }
+}
+
+fn calculate_bbox(points: &[Point]) -> Option<[i16; 4]> {
+    if points.is_empty() {
+        return Some([0; 4]);
+    }
+
+    let mut x = 0i32;
+    let mut y = 0i32;
+    let mut bbox = [i32::MAX, i32::MAX, i32::MIN, i32::MIN];
+    for point in points {
+        x += i32::from(point.dx);
+        y += i32::from(point.dy);
+        bbox[0] = bbox[0].min(x);
+        bbox[1] = bbox[1].min(y);
+        bbox[2] = bbox[2].max(x);
+        bbox[3] = bbox[3].max(y);
+    }
+
+    Some([
+        i16::try_from(bbox[0]).ok()?,
+        i16::try_from(bbox[1]).ok()?,
+        i16::try_from(bbox[2]).ok()?,
+        i16::try_from(bbox[3]).ok()?,
+    ])
+}
+
+fn read_bbox(s: &mut Stream) -> Option<[i16; 4]> {
+    Some([
+        s.read::<i16>()?,
+        s.read::<i16>()?,
+        s.read::<i16>()?,
+        s.read::<i16>()?,
+    ])
+}
+
+// Copies composite glyph components.
+//
+// Returns `true` when the glyph has instructions.
+fn copy_composite_glyph(s: &mut Stream, glyf: &mut Vec<u8>) -> Option<bool> {
+    const ARG_1_AND_2_ARE_WORDS: u16 = 0x0001;
+    const WE_HAVE_A_SCALE: u16 = 0x0008;
+    const MORE_COMPONENTS: u16 = 0x0020;
+    const WE_HAVE_AN_X_AND_Y_SCALE: u16 = 0x0040;
+    const WE_HAVE_A_TWO_BY_TWO: u16 = 0x0080;
+    const WE_HAVE_INSTRUCTIONS: u16 = 0x0100;
+
+    let data = s.tail()?;
+    let start = s.offset();
+    let mut have_instructions = false;
+    loop {
+        let flags = s.read::<u16>()?;
+        s.skip::<u16>(); // glyph index
+
+        let mut len = if flags & ARG_1_AND_2_ARE_WORDS != 0 {
+            4
+        } else {
+            2
+        };
+
+        if flags & WE_HAVE_A_SCALE != 0 {
+            len += 2;
+        } else if flags & WE_HAVE_AN_X_AND_Y_SCALE != 0 {
+            len += 4;
+        } else if flags & WE_HAVE_A_TWO_BY_TWO != 0 {
+            len += 8;
+        }
+
+        s.advance_checked(len)?;
+
+        have_instructions |= flags & WE_HAVE_INSTRUCTIONS != 0;
+        if flags & MORE_COMPONENTS == 0 {
+            break;
+        }
+    }
+
+    glyf.extend_from_slice(data.get(..s.offset() - start)?);
+    Some(have_instructions)
+}
+
+// https://www.w3.org/TR/WOFF2/#hmtx_table_format
+fn reconstruct_hmtx(
+    data: &[u8],
+    number_of_metrics: u16,
+    number_of_glyphs: u16,
+    x_mins: &[i16],
+) -> Option<Vec<u8>> {
+    const PROPORTIONAL_LSBS_ABSENT: u8 = 0x01;
+    const MONOSPACED_LSBS_ABSENT: u8 = 0x02;
+
+    let mut s = Stream::new(data);
+    let flags = s.read::<u8>()?;
+    // At least one of the arrays must be absent and reserved bits must be unset.
+    if flags & 0x03 == 0 || flags & 0xFC != 0 {
+        return None;
+    }
+
+    if number_of_metrics == 0
+        || number_of_metrics > number_of_glyphs
+        || x_mins.len() < usize::from(number_of_glyphs)
+    {
+        return None;
+    }
+
+    let advances = s.read_array16::<u16>(number_of_metrics)?;
+
+    let proportional_lsbs = if flags & PROPORTIONAL_LSBS_ABSENT == 0 {
+        Some(s.read_array16::<i16>(number_of_metrics)?)
+    } else {
+        None
+    };
+
+    let monospaced_count = number_of_glyphs - number_of_metrics;
+    let monospaced_lsbs = if flags & MONOSPACED_LSBS_ABSENT == 0 {
+        Some(s.read_array16::<i16>(monospaced_count)?)
+    } else {
+        None
+    };
+
+    let mut hmtx =
+        Vec::with_capacity(usize::from(number_of_metrics) * 4 + usize::from(monospaced_count) * 2);
+    for i in 0..number_of_metrics {
+        let lsb = match proportional_lsbs {
+            Some(lsbs) => lsbs.get(i)?,
+            None => x_mins[usize::from(i)],
+        };
+
+        write_u16(&mut hmtx, advances.get(i)?);
+        write_i16(&mut hmtx, lsb);
+    }
+
+    for i in 0..monospaced_count {
+        let lsb = match monospaced_lsbs {
+            Some(lsbs) => lsbs.get(i)?,
+            None => x_mins[usize::from(number_of_metrics + i)],
+        };
+
+        write_i16(&mut hmtx, lsb);
+    }
+
+    Some(hmtx)
+}
+
+#[derive(Default)]
+struct SfntWriter {
+    data: Vec<u8>,
+}
+
+impl SfntWriter {
+    fn write_font(
+        &mut self,
+        flavor: u32,
+        indices: &[u16],
+        records: &[TableRecord],
+        tables: &[TableData],
+    ) -> Result<(), Error> {
+        let mut offsets = vec![None; tables.len()];
+        self.write_font_impl(flavor, indices, records, tables, &mut offsets, true)
+    }
+
+    fn write_collection(
+        &mut self,
+        version: u32,
+        fonts: &[(u32, &[u16])],
+        records: &[TableRecord],
+        tables: &[TableData],
+    ) -> Result<(), Error> {
+        write_u32(&mut self.data, COLLECTION_FLAVOR);
+        write_u32(&mut self.data, version);
+        write_u32(&mut self.data, fonts.len() as u32);
+
+        let offsets_start = self.data.len();
+        self.data.resize(self.data.len() + fonts.len() * 4, 0);
+
+        if version >= 0x00020000 {
+            // No digital signature.
+            self.data.extend_from_slice(&[0; 12]);
+        }
+
+        // Shared tables must be written only once.
+        let mut offsets = vec![None; tables.len()];
+        for (i, (flavor, indices)) in fonts.iter().enumerate() {
+            let font_offset = u32::try_from(self.data.len()).map_err(|_| Error::TooLarge)?;
+            let pos = offsets_start + i * 4;
+            self.data[pos..pos + 4].copy_from_slice(&font_offset.to_be_bytes());
+            self.write_font_impl(*flavor, indices, records, tables, &mut offsets, false)?;
+        }
+
+        Ok(())
+    }
+
+    fn write_font_impl(
+        &mut self,
+        flavor: u32,
+        indices: &[u16],
+        records: &[TableRecord],
+        tables: &[TableData],
+        offsets: &mut [Option<(u32, u32)>],
+        update_head_checksum: bool,
+    ) -> Result<(), Error> {
+        // Table records must be sorted by tag.
+        let mut indices = indices.to_vec();
+        indices.sort_by_key(|i| records[usize::from(*i)].tag);
+        indices.dedup();
+
+        let num_tables = u16::try_from(indices.len()).map_err(|_| Error::MalformedFont)?;
+        let mut entry_selector = 0u16;
+        while num_tables >> (entry_selector + 1) != 0 {
+            entry_selector += 1;
+        }
+        let search_range = (1u16 << entry_selector).wrapping_mul(16);
+        let range_shift = num_tables.wrapping_mul(16).wrapping_sub(search_range);
+
+        let font_start = self.data.len();
+        write_u32(&mut self.data, flavor);
+        write_u16(&mut self.data, num_tables);
+        write_u16(&mut self.data, search_range);
+        write_u16(&mut self.data, entry_selector);
+        write_u16(&mut self.data, range_shift);
+
+        let records_start = self.data.len();
+        self.data.resize(records_start + indices.len() * 16, 0);
+
+        let mut head_offset = None;
+        for (i, index) in indices.iter().enumerate() {
+            let index = usize::from(*index);
+            let tag = records[index].tag;
+
+            let (offset, len) = match offsets[index] {
+                Some(v) => v,
+                None => {
+                    let data = tables[index].as_slice().ok_or(Error::MalformedFont)?;
+                    let offset = u32::try_from(self.data.len()).map_err(|_| Error::TooLarge)?;
+                    let len = u32::try_from(data.len()).map_err(|_| Error::TooLarge)?;
+                    self.data.extend_from_slice(data);
+                    while self.data.len() & 3 != 0 {
+                        self.data.push(0);
+                    }
+
+                    offsets[index] = Some((offset, len));
+                    (offset, len)
+                }
+            };
+
+            let start = usize::num_from(offset);
+            let end = start + usize::num_from(len);
+            if tag == HEAD {
+                // Checksum adjustment must be zero during checksum calculation.
+                if let Some(adjustment) = self.data.get_mut(start + 8..start + 12) {
+                    adjustment.copy_from_slice(&[0; 4]);
+                }
+                head_offset = Some(start);
+            }
+
+            let check_sum = checksum(&self.data[start..end]);
+
+            let pos = records_start + i * 16;
+            self.data[pos..pos + 4].copy_from_slice(&tag.0.to_be_bytes());
+            self.data[pos + 4..pos + 8].copy_from_slice(&check_sum.to_be_bytes());
+            self.data[pos + 8..pos + 12].copy_from_slice(&offset.to_be_bytes());
+            self.data[pos + 12..pos + 16].copy_from_slice(&len.to_be_bytes());
+        }
+
+        if update_head_checksum {
+            if let Some(head_offset) = head_offset {
+                if self.data.len() >= head_offset + 12 {
+                    let adjustment = 0xB1B0AFBAu32.wrapping_sub(checksum(&self.data[font_start..]));
+                    self.data[head_offset + 8..head_offset + 12]
+                        .copy_from_slice(&adjustment.to_be_bytes());
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn checksum(data: &[u8]) -> u32 {
+    let mut sum = 0u32;
+    for chunk in data.chunks(4) {
+        let mut bytes = [0; 4];
+        bytes[..chunk.len()].copy_from_slice(chunk);
+        sum = sum.wrapping_add(u32::from_be_bytes(bytes));
+    }
+
+    sum
+}
+
+#[inline]
+fn bit_is_set(bitmap: &[u8], index: u16) -> bool {
+    bitmap
+        .get(usize::from(index >> 3))
+        .map(|b| b & (0x80 >> (index & 7)) != 0)
+        .unwrap_or(false)
+}
+
+#[inline]
+fn write_u16(data: &mut Vec<u8>, n: u16) {
+    data.extend_from_slice(&n.to_be_bytes());
+}
+
+#[inline]
+fn write_i16(data: &mut Vec<u8>, n: i16) {
+    data.extend_from_slice(&n.to_be_bytes());
+}
+
+#[inline]
+fn write_u32(data: &mut Vec<u8>, n: u32) {
+    data.extend_from_slice(&n.to_be_bytes());
+}
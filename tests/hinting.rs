@@ -0,0 +1,303 @@
+#![cfg(feature = "hinting")]
+
+use std::fmt::Write;
+
+use ttf_parser::hinting::Hinter;
+use ttf_parser::{Face, GlyphId, RawFace, RawFaceTables, Tag};
+
+static DEMO_FONT: &[u8] = include_bytes!("fonts/demo.ttf");
+
+struct Builder(String);
+
+impl ttf_parser::OutlineBuilder for Builder {
+    fn move_to(&mut self, x: f32, y: f32) {
+        write!(&mut self.0, "M {} {} ", x, y).unwrap();
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        write!(&mut self.0, "L {} {} ", x, y).unwrap();
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        write!(&mut self.0, "Q {} {} {} {} ", x1, y1, x, y).unwrap();
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        write!(&mut self.0, "C {} {} {} {} {} {} ", x1, y1, x2, y2, x, y).unwrap();
+    }
+
+    fn close(&mut self) {
+        write!(&mut self.0, "Z ").unwrap();
+    }
+}
+
+fn push_u16(data: &mut Vec<u8>, n: u16) {
+    data.extend_from_slice(&n.to_be_bytes());
+}
+
+fn push_i16(data: &mut Vec<u8>, n: i16) {
+    data.extend_from_slice(&n.to_be_bytes());
+}
+
+// Glyph [0] is a triangle with the specified instructions.
+// Glyph [1] is a composite glyph, which references the triangle with a 100 units offset.
+#[rustfmt::skip]
+fn glyf_data(instructions: &[u8]) -> (Vec<u8>, Vec<u8>) {
+    let mut glyf = Vec::new();
+    push_i16(&mut glyf, 1); // number of contours
+    for n in &[150, 150, 850, 730] { push_i16(&mut glyf, *n); } // bbox
+    push_u16(&mut glyf, 2); // end point [0]
+    push_u16(&mut glyf, instructions.len() as u16);
+    glyf.extend_from_slice(instructions);
+    glyf.extend_from_slice(&[1, 1, 1]); // flags: on curve, two byte deltas
+    for n in &[150, 700, -350] { push_i16(&mut glyf, *n); } // x deltas
+    for n in &[150, 0, 580] { push_i16(&mut glyf, *n); } // y deltas
+    if glyf.len() % 2 != 0 {
+        glyf.push(0);
+    }
+
+    let composite_offset = glyf.len();
+    push_i16(&mut glyf, -1); // number of contours
+    for n in &[250, 150, 950, 730] { push_i16(&mut glyf, *n); } // bbox
+    push_u16(&mut glyf, 0x0007); // flags: words, xy values, round to grid
+    push_u16(&mut glyf, 0); // glyph id
+    push_i16(&mut glyf, 100); // dx
+    push_i16(&mut glyf, 0); // dy
+
+    let mut loca = Vec::new();
+    for n in &[0, composite_offset, glyf.len()] { push_u16(&mut loca, (*n / 2) as u16); }
+
+    (glyf, loca)
+}
+
+#[rustfmt::skip]
+fn hhea_data() -> Vec<u8> {
+    let mut data = vec![0; 36];
+    data[0..4].copy_from_slice(&[0, 1, 0, 0]); // version
+    data[4..6].copy_from_slice(&800i16.to_be_bytes()); // ascender
+    data[6..8].copy_from_slice(&(-200i16).to_be_bytes()); // descender
+    data[34..36].copy_from_slice(&2u16.to_be_bytes()); // number of h-metrics
+    data
+}
+
+#[rustfmt::skip]
+fn maxp_data() -> Vec<u8> {
+    let mut data = vec![0, 1, 0, 0]; // version
+    for n in &[
+        2, // number of glyphs
+        3, // max points
+        1, // max contours
+        3, // max composite points
+        1, // max composite contours
+        2, // max zones
+        4, // max twilight points
+        4, // max storage
+        4, // max function defs
+        0, // max instruction defs
+        16, // max stack elements
+        16, // max size of instructions
+        1, // max component elements
+        1, // max component depth
+    ] {
+        push_u16(&mut data, *n);
+    }
+
+    data
+}
+
+#[rustfmt::skip]
+fn hmtx_data() -> Vec<u8> {
+    let mut data = Vec::new();
+    for n in &[900, 150, 900, 250] { push_u16(&mut data, *n); } // advance, side bearing
+    data
+}
+
+struct Tables {
+    glyf: Vec<u8>,
+    loca: Vec<u8>,
+    hhea: Vec<u8>,
+    maxp: Vec<u8>,
+    hmtx: Vec<u8>,
+    cvt: Vec<u8>,
+}
+
+impl Tables {
+    fn new(instructions: &[u8]) -> Self {
+        let (glyf, loca) = glyf_data(instructions);
+        let mut cvt = Vec::new();
+        push_i16(&mut cvt, 700);
+        Tables {
+            glyf,
+            loca,
+            hhea: hhea_data(),
+            maxp: maxp_data(),
+            hmtx: hmtx_data(),
+            cvt,
+        }
+    }
+
+    fn face<'a>(&'a self, fpgm: &'a [u8], prep: &'a [u8]) -> Face<'a> {
+        let raw_face = RawFace::parse(DEMO_FONT, 0).unwrap();
+        let tables = RawFaceTables {
+            head: raw_face.table(Tag::from_bytes(b"head")).unwrap(),
+            hhea: &self.hhea,
+            maxp: &self.maxp,
+            glyf: Some(&self.glyf),
+            loca: Some(&self.loca),
+            hmtx: Some(&self.hmtx),
+            cvt: Some(&self.cvt),
+            fpgm: Some(fpgm),
+            prep: Some(prep),
+            ..RawFaceTables::default()
+        };
+        Face::from_raw_tables(tables).unwrap()
+    }
+}
+
+fn outline(hinter: &mut Hinter, glyph_id: u16) -> String {
+    let mut builder = Builder(String::new());
+    hinter
+        .outline_glyph(GlyphId(glyph_id), &mut builder)
+        .unwrap();
+    builder.0
+}
+
+#[test]
+fn unhinted() {
+    let tables = Tables::new(&[]);
+    let face = tables.face(&[], &[]);
+    let mut hinter = Hinter::new(&face, 12, &[]).unwrap();
+    // Points are scaled to 26.6 without grid-fitting.
+    assert_eq!(
+        outline(&mut hinter, 0),
+        "M 1.796875 1.796875 L 10.203125 1.796875 L 6 8.765625 L 1.796875 1.796875 Z "
+    );
+}
+
+#[test]
+fn round_points() {
+    #[rustfmt::skip]
+    let instructions = &[
+        0x00, // SVTCA[y]
+        0xB1, 2, 0, // PUSHB[1] 2 0
+        0x2F, 0x2F, // MDAP[r] MDAP[r]
+        0x30, // IUP[y]
+        0x01, // SVTCA[x]
+        0xB1, 2, 0, // PUSHB[1] 2 0
+        0x2F, 0x2F, // MDAP[r] MDAP[r]
+        0x31, // IUP[x]
+    ];
+
+    let tables = Tables::new(instructions);
+    let face = tables.face(&[], &[]);
+    let mut hinter = Hinter::new(&face, 12, &[]).unwrap();
+    assert_eq!(
+        outline(&mut hinter, 0),
+        "M 2 2 L 10.203125 2 L 6 9 L 2 2 Z "
+    );
+}
+
+#[test]
+fn function_call() {
+    #[rustfmt::skip]
+    let fpgm = &[
+        0xB0, 0, // PUSHB[0] 0
+        0x2C, // FDEF
+        0x3F, // MIAP[r]
+        0x2D, // ENDF
+    ];
+
+    #[rustfmt::skip]
+    let instructions = &[
+        0x00, // SVTCA[y]
+        0xB2, 2, 0, 0, // PUSHB[2] 2 0 0
+        0x2B, // CALL
+        0x30, // IUP[y]
+    ];
+
+    let tables = Tables::new(instructions);
+    let face = tables.face(fpgm, &[]);
+    let mut hinter = Hinter::new(&face, 12, &[]).unwrap();
+    // The top point is snapped to the rounded CVT value and the rest are shifted by IUP.
+    assert_eq!(
+        outline(&mut hinter, 0),
+        "M 1.796875 1.03125 L 10.203125 1.03125 L 6 8 L 1.796875 1.03125 Z "
+    );
+}
+
+#[test]
+fn prep_disables_hinting() {
+    #[rustfmt::skip]
+    let prep = &[
+        0xB1, 1, 1, // PUSHB[1] 1 1
+        0x8E, // INSTCTRL
+    ];
+
+    let tables = Tables::new(&[0x00, 0xB1, 2, 0, 0x2F, 0x2F]);
+    let face = tables.face(&[], prep);
+    let mut hinter = Hinter::new(&face, 12, &[]).unwrap();
+    assert_eq!(
+        outline(&mut hinter, 0),
+        "M 1.796875 1.796875 L 10.203125 1.796875 L 6 8.765625 L 1.796875 1.796875 Z "
+    );
+}
+
+#[test]
+fn composite() {
+    let tables = Tables::new(&[0x00, 0xB1, 2, 0, 0x2F, 0x2F]);
+    let face = tables.face(&[], &[]);
+    let mut hinter = Hinter::new(&face, 12, &[]).unwrap();
+    // The offset is rounded to a full pixel.
+    assert_eq!(
+        outline(&mut hinter, 1),
+        "M 2.796875 2 L 11.203125 1.796875 L 7 9 L 2.796875 2 Z "
+    );
+}
+
+#[test]
+fn advance() {
+    let tables = Tables::new(&[]);
+    let face = tables.face(&[], &[]);
+    let mut hinter = Hinter::new(&face, 12, &[]).unwrap();
+    assert_eq!(hinter.glyph_hor_advance(GlyphId(0)), Some(11.0));
+    assert_eq!(hinter.glyph_hor_advance(GlyphId(2)), None);
+}
+
+#[test]
+fn endless_recursion() {
+    #[rustfmt::skip]
+    let fpgm = &[
+        0xB0, 0, // PUSHB[0] 0
+        0x2C, // FDEF
+        0xB0, 0, // PUSHB[0] 0
+        0x2B, // CALL
+        0x2D, // ENDF
+        0xB0, 0, // PUSHB[0] 0
+        0x2B, // CALL
+    ];
+
+    let tables = Tables::new(&[]);
+    let face = tables.face(fpgm, &[]);
+    assert!(Hinter::new(&face, 12, &[]).is_none());
+}
+
+#[test]
+fn endless_loop() {
+    #[rustfmt::skip]
+    let instructions = &[
+        0x00, // SVTCA[y]
+        0xB1, 2, 0, // PUSHB[1] 2 0
+        0x2F, 0x2F, // MDAP[r] MDAP[r]
+        0xB8, 0xFF, 0xFD, // PUSHW[0] -3
+        0x1C, // JMPR
+    ];
+
+    let tables = Tables::new(instructions);
+    let face = tables.face(&[], &[]);
+    let mut hinter = Hinter::new(&face, 12, &[]).unwrap();
+    // Execution is aborted, but points that were already moved are preserved.
+    assert_eq!(
+        outline(&mut hinter, 0),
+        "M 1.796875 2 L 10.203125 1.796875 L 6 9 L 1.796875 2 Z "
+    );
+}
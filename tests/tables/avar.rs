@@ -0,0 +1,165 @@
+use ttf_parser::avar::Table;
+use ttf_parser::{Face, NormalizedCoordinate, RawFaceTables, Tag};
+use crate::{convert, demo_face_tables, Unit::*};
+
+fn coords(values: &[f32]) -> Vec<NormalizedCoordinate> {
+    values.iter().map(|v| NormalizedCoordinate::from(*v)).collect()
+}
+
+fn map(table: &Table, values: &[f32]) -> Vec<NormalizedCoordinate> {
+    let mut coordinates = coords(values);
+    table.map_coordinates(&mut coordinates).unwrap();
+    coordinates
+}
+
+fn variation_store() -> Vec<u8> {
+    convert(&[
+        UInt16(1), // format
+        UInt32(12), // offset to variation region list
+        UInt16(1), // number of item variation subtables
+        UInt32(28), // offset to item variation subtable [0]
+
+        // Variation region list.
+        UInt16(2), // axis count
+        UInt16(1), // region count
+        Int16(0), Int16(16384), Int16(16384), // region [0], axis [0]
+        Int16(0), Int16(0), Int16(0), // region [0], axis [1]
+
+        // Item variation subtable.
+        UInt16(2), // item count
+        UInt16(1), // word delta count
+        UInt16(1), // region index count
+        UInt16(0), // region index [0]
+        Int16(0), // item [0]
+        Int16(8192), // item [1]
+    ])
+}
+
+fn avar_v2(axis_index_map: bool) -> Vec<u8> {
+    let mut data = convert(&[
+        UInt32(0x00020000), // version
+        UInt16(0), // reserved
+        UInt16(2), // axis count
+        UInt16(0), // axis [0] position map count
+        UInt16(0), // axis [1] position map count
+    ]);
+
+    if axis_index_map {
+        data.extend_from_slice(&convert(&[
+            UInt32(20), // offset to axis index map
+            UInt32(26), // offset to item variation store
+
+            // Axis index map.
+            UInt8(0), // format
+            UInt8(0x07), // entry format: 1 byte entries, 8 bits inner index
+            UInt16(2), // map count
+            UInt8(1), // axis [0]
+            UInt8(0), // axis [1]
+        ]));
+    } else {
+        data.extend_from_slice(&convert(&[
+            UInt32(0), // offset to axis index map
+            UInt32(20), // offset to item variation store
+        ]));
+    }
+
+    data.extend_from_slice(&variation_store());
+    data
+}
+
+fn avar_v1() -> Vec<u8> {
+    convert(&[
+        UInt32(0x00010000), // version
+        UInt16(0), // reserved
+        UInt16(2), // axis count
+
+        UInt16(3), // axis [0] position map count
+        Int16(-16384), Int16(-16384),
+        Int16(0), Int16(0),
+        Int16(16384), Int16(16384),
+
+        UInt16(3), // axis [1] position map count
+        Int16(-16384), Int16(-16384),
+        Int16(0), Int16(0),
+        Int16(8192), Int16(16384), // 0.5 -> 1.0
+    ])
+}
+
+#[test]
+fn version_1() {
+    let data = avar_v1();
+    let table = Table::parse(&data).unwrap();
+    assert_eq!(table.segment_maps.len(), 2);
+    assert_eq!(map(&table, &[0.5, 0.25]), coords(&[0.5, 0.5]));
+    assert_eq!(map(&table, &[-0.5, 0.75]), coords(&[-0.5, 1.0]));
+}
+
+#[test]
+fn version_2() {
+    let data = avar_v2(false);
+    let table = Table::parse(&data).unwrap();
+    assert_eq!(table.segment_maps.len(), 2);
+    assert_eq!(map(&table, &[0.0, 0.0]), coords(&[0.0, 0.0]));
+    assert_eq!(map(&table, &[0.5, 0.0]), coords(&[0.5, 0.25]));
+    assert_eq!(map(&table, &[1.0, 0.0]), coords(&[1.0, 0.5]));
+    // Out of range values are clamped.
+    assert_eq!(map(&table, &[1.0, 0.75]), coords(&[1.0, 1.0]));
+}
+
+#[test]
+fn version_2_with_axis_index_map() {
+    let data = avar_v2(true);
+    let table = Table::parse(&data).unwrap();
+    assert_eq!(map(&table, &[0.5, 0.0]), coords(&[0.75, 0.0]));
+    assert_eq!(map(&table, &[-0.5, 0.5]), coords(&[-0.5, 0.5]));
+}
+
+#[test]
+fn unsupported_version() {
+    let data = convert(&[
+        UInt32(0x00030000), // version
+        UInt16(0), // reserved
+        UInt16(0), // axis count
+    ]);
+
+    assert!(Table::parse(&data).is_none());
+}
+
+#[test]
+fn axis_count_mismatch() {
+    let data = avar_v2(false);
+    let table = Table::parse(&data).unwrap();
+    let mut coordinates = coords(&[0.5]);
+    assert!(table.map_coordinates(&mut coordinates).is_none());
+}
+
+#[test]
+fn set_variation_multiple_times() {
+    let fvar = convert(&[
+        UInt32(0x00010000), // version
+        UInt16(16), // offset to axes array
+        UInt16(2), // reserved
+        UInt16(2), // axis count
+        UInt16(20), // axis size
+        UInt16(0), // instance count
+        UInt16(12), // instance size
+
+        Raw(b"wght"), Fixed(100.0), Fixed(400.0), Fixed(900.0), UInt16(0), UInt16(256),
+        Raw(b"wdth"), Fixed(50.0), Fixed(100.0), Fixed(150.0), UInt16(0), UInt16(257),
+    ]);
+
+    let avar = avar_v1();
+    let tables = RawFaceTables {
+        fvar: Some(&fvar),
+        avar: Some(&avar),
+        ..demo_face_tables()
+    };
+    let mut face = Face::from_raw_tables(tables).unwrap();
+
+    face.set_variation(Tag::from_bytes(b"wdth"), 112.5).unwrap();
+    assert_eq!(face.variation_coordinates(), coords(&[0.0, 0.5]).as_slice());
+
+    // Mapping must not be applied to already mapped coordinates.
+    face.set_variation(Tag::from_bytes(b"wght"), 650.0).unwrap();
+    assert_eq!(face.variation_coordinates(), coords(&[0.5, 0.5]).as_slice());
+}
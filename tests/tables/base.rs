@@ -0,0 +1,239 @@
+use ttf_parser::base::Table;
+use ttf_parser::gpos::Device;
+use ttf_parser::{Face, GlyphId, RawFace, RawFaceTables, Tag};
+use crate::{convert, demo_face_tables, DEMO_FONT, Unit::*};
+
+fn base_data() -> Vec<u8> {
+    convert(&[
+        UInt16(1), // major version
+        UInt16(0), // minor version
+        UInt16(8), // offset to horizontal axis
+        UInt16(0), // offset to vertical axis
+
+        // Horizontal axis.
+        UInt16(4), // offset to base tag list
+        UInt16(14), // offset to base script list
+        // Base tag list.
+        UInt16(2), // count
+        Raw(b"ideo"),
+        Raw(b"romn"),
+
+        // Base script list.
+        UInt16(2), // count
+        Raw(b"DFLT"), UInt16(14), // tag, offset
+        Raw(b"latn"), UInt16(36), // tag, offset
+
+        // Base script [0].
+        UInt16(6), // offset to base values
+        UInt16(0), // offset to default min/max
+        UInt16(0), // language count
+        // Base values.
+        UInt16(1), // default baseline index
+        UInt16(2), // count
+        UInt16(8), // offset to base coord [0]
+        UInt16(12), // offset to base coord [1]
+        // Base coord [0].
+        UInt16(1), // format
+        Int16(-120), // coordinate
+        // Base coord [1].
+        UInt16(1), // format
+        Int16(0), // coordinate
+
+        // Base script [1].
+        UInt16(12), // offset to base values
+        UInt16(40), // offset to default min/max
+        UInt16(1), // language count
+        Raw(b"TRK "), UInt16(54), // tag, offset to min/max
+        // Base values.
+        UInt16(1), // default baseline index
+        UInt16(2), // count
+        UInt16(8), // offset to base coord [0]
+        UInt16(16), // offset to base coord [1]
+        // Base coord [0].
+        UInt16(2), // format
+        Int16(-100), // coordinate
+        UInt16(1), // reference glyph
+        UInt16(1), // contour point
+        // Base coord [1].
+        UInt16(3), // format
+        Int16(10), // coordinate
+        UInt16(6), // offset to device
+        // Device.
+        UInt16(0), // outer index
+        UInt16(0), // inner index
+        UInt16(0x8000), // format
+        // Default min/max.
+        UInt16(6), // offset to min coord
+        UInt16(10), // offset to max coord
+        UInt16(0), // feature count
+        UInt16(1), Int16(-200), // format, coordinate
+        UInt16(1), Int16(800), // format, coordinate
+        // Language min/max.
+        UInt16(0), // offset to min coord
+        UInt16(14), // offset to max coord
+        UInt16(1), // feature count
+        Raw(b"kern"), UInt16(0), UInt16(18), // tag, offset to min coord, offset to max coord
+        UInt16(1), Int16(900), // format, coordinate
+        UInt16(1), Int16(950), // format, coordinate
+    ])
+}
+
+#[test]
+fn parse() {
+    let data = base_data();
+    let table = Table::parse(&data).unwrap();
+    assert!(table.vertical.is_none());
+
+    let axis = table.horizontal.unwrap();
+    let tags: Vec<Tag> = axis.baseline_tags.into_iter().collect();
+    assert_eq!(tags, [Tag::from_bytes(b"ideo"), Tag::from_bytes(b"romn")]);
+    assert_eq!(axis.scripts.len(), 2);
+
+    let script = axis.scripts.find(Tag::from_bytes(b"DFLT")).unwrap();
+    assert!(script.default_min_max.is_none());
+    assert!(script.languages.is_empty());
+    let values = script.values.unwrap();
+    assert_eq!(values.default_baseline_index, 1);
+    assert_eq!(values.coordinates.get(0).unwrap().coordinate, -120);
+
+    let script = axis.scripts.find(Tag::from_bytes(b"latn")).unwrap();
+    let values = script.values.unwrap();
+    let coord = values.coordinates.get(0).unwrap();
+    assert_eq!(coord.coordinate, -100);
+    assert_eq!(coord.reference_glyph, Some(GlyphId(1)));
+    assert_eq!(coord.contour_point, Some(1));
+    let coord = values.coordinates.get(1).unwrap();
+    assert_eq!(coord.coordinate, 10);
+    assert!(matches!(coord.device, Some(Device::Variation(_))));
+
+    let min_max = script.default_min_max.unwrap();
+    assert_eq!(min_max.tag, Tag::from_bytes(b"dflt"));
+    assert_eq!(min_max.min.unwrap().coordinate, -200);
+    assert_eq!(min_max.max.unwrap().coordinate, 800);
+    assert!(min_max.features.is_empty());
+
+    let min_max = script.languages.find(Tag::from_bytes(b"TRK ")).unwrap();
+    assert!(min_max.min.is_none());
+    assert_eq!(min_max.max.unwrap().coordinate, 900);
+    let feature = min_max.features.find(Tag::from_bytes(b"kern")).unwrap();
+    assert!(feature.min.is_none());
+    assert_eq!(feature.max.unwrap().coordinate, 950);
+}
+
+#[test]
+fn baseline() {
+    let data = base_data();
+    let axis = Table::parse(&data).unwrap().horizontal.unwrap();
+    let ideo = Tag::from_bytes(b"ideo");
+    assert_eq!(axis.baseline(Tag::from_bytes(b"DFLT"), ideo).unwrap().coordinate, -120);
+    assert_eq!(axis.baseline(Tag::from_bytes(b"latn"), ideo).unwrap().coordinate, -100);
+    // Fallback to DFLT.
+    assert_eq!(axis.baseline(Tag::from_bytes(b"cyrl"), ideo).unwrap().coordinate, -120);
+    assert!(axis.baseline(Tag::from_bytes(b"latn"), Tag::from_bytes(b"hang")).is_none());
+}
+
+#[test]
+fn resolve_coordinates() {
+    let data = base_data();
+    let raw_face = RawFace::parse(DEMO_FONT, 0).unwrap();
+    let tables = RawFaceTables {
+        loca: raw_face.table(Tag::from_bytes(b"loca")),
+        glyf: raw_face.table(Tag::from_bytes(b"glyf")),
+        base: Some(&data),
+        ..demo_face_tables()
+    };
+    let face = Face::from_raw_tables(tables).unwrap();
+
+    let axis = face.tables().base.unwrap().horizontal.unwrap();
+    let values = axis.scripts.find(Tag::from_bytes(b"latn")).unwrap().values.unwrap();
+    let coords: Vec<f32> = values
+        .coordinates
+        .into_iter()
+        .map(|coord| face.base_coordinate(&coord, false))
+        .collect();
+    assert_eq!(coords, [267.0, 10.0]);
+
+    let coord = values.coordinates.get(0).unwrap();
+    assert_eq!(face.base_coordinate(&coord, true), 369.0);
+}
+
+#[test]
+fn resolve_variation_deltas() {
+    let fvar = convert(&[
+        UInt32(0x00010000), // version
+        UInt16(16), // offset to axes array
+        UInt16(2), // reserved
+        UInt16(1), // axis count
+        UInt16(20), // axis size
+        UInt16(0), // instance count
+        UInt16(8), // instance size
+        Raw(b"wght"), Fixed(100.0), Fixed(400.0), Fixed(900.0), UInt16(0), UInt16(256),
+    ]);
+
+    let base = convert(&[
+        UInt16(1), // major version
+        UInt16(1), // minor version
+        UInt16(12), // offset to horizontal axis
+        UInt16(0), // offset to vertical axis
+        UInt32(54), // offset to item variation store
+
+        // Horizontal axis.
+        UInt16(4), // offset to base tag list
+        UInt16(10), // offset to base script list
+        // Base tag list.
+        UInt16(1), // count
+        Raw(b"romn"),
+        // Base script list.
+        UInt16(1), // count
+        Raw(b"DFLT"), UInt16(8), // tag, offset
+        // Base script.
+        UInt16(6), // offset to base values
+        UInt16(0), // offset to default min/max
+        UInt16(0), // language count
+        // Base values.
+        UInt16(0), // default baseline index
+        UInt16(1), // count
+        UInt16(6), // offset to base coord [0]
+        // Base coord [0].
+        UInt16(3), // format
+        Int16(10), // coordinate
+        UInt16(6), // offset to device
+        // Device.
+        UInt16(0), // outer index
+        UInt16(0), // inner index
+        UInt16(0x8000), // format
+
+        // Item variation store.
+        UInt16(1), // format
+        UInt32(12), // offset to variation region list
+        UInt16(1), // item variation data count
+        UInt32(22), // offset to item variation data [0]
+        // Variation region list.
+        UInt16(1), // axis count
+        UInt16(1), // region count
+        Int16(0), Int16(0x4000), Int16(0x4000), // start, peak, end
+        // Item variation data [0].
+        UInt16(1), // item count
+        UInt16(0), // word delta count
+        UInt16(1), // region index count
+        UInt16(0), // region index [0]
+        Int8(40), // delta
+    ]);
+
+    let tables = RawFaceTables {
+        fvar: Some(&fvar),
+        base: Some(&base),
+        ..demo_face_tables()
+    };
+    let mut face = Face::from_raw_tables(tables).unwrap();
+
+    let axis = face.tables().base.unwrap().horizontal.unwrap();
+    let coord = axis.baseline(Tag::from_bytes(b"DFLT"), Tag::from_bytes(b"romn")).unwrap();
+    assert_eq!(face.base_coordinate(&coord, false), 10.0);
+
+    face.set_variation(Tag::from_bytes(b"wght"), 900.0).unwrap();
+    assert_eq!(face.base_coordinate(&coord, false), 50.0);
+
+    face.set_variation(Tag::from_bytes(b"wght"), 650.0).unwrap();
+    assert_eq!(face.base_coordinate(&coord, false), 30.0);
+}
@@ -27,6 +27,24 @@ impl ttf_parser::OutlineBuilder for Builder {
     }
 }
 
+impl ttf_parser::CFFHintsBuilder for Builder {
+    fn hstem(&mut self, y: f32, dy: f32) {
+        write!(&mut self.0, "HS {} {} ", y, dy).unwrap();
+    }
+
+    fn vstem(&mut self, x: f32, dx: f32) {
+        write!(&mut self.0, "VS {} {} ", x, dx).unwrap();
+    }
+
+    fn hint_mask(&mut self, mask: &[u8]) {
+        write!(&mut self.0, "HM {:?} ", mask).unwrap();
+    }
+
+    fn counter_mask(&mut self, mask: &[u8]) {
+        write!(&mut self.0, "CM {:?} ", mask).unwrap();
+    }
+}
+
 #[allow(dead_code)]
 mod operator {
     pub const HORIZONTAL_STEM: u8           = 1;
@@ -62,16 +80,29 @@ mod operator {
 
 #[allow(dead_code)]
 mod top_dict_operator {
+    pub const VERSION: u16                      = 0;
+    pub const NOTICE: u16                       = 1;
+    pub const FULL_NAME: u16                    = 2;
+    pub const FAMILY_NAME: u16                  = 3;
+    pub const WEIGHT: u16                       = 4;
     pub const CHARSET_OFFSET: u16               = 15;
     pub const CHAR_STRINGS_OFFSET: u16          = 17;
     pub const PRIVATE_DICT_SIZE_AND_OFFSET: u16 = 18;
     pub const ROS: u16                          = 1230;
+    pub const FONT_NAME: u16                    = 1238;
     pub const FD_ARRAY: u16                     = 1236;
     pub const FD_SELECT: u16                    = 1237;
 }
 
 mod private_dict_operator {
+    pub const BLUE_VALUES: u16              = 6;
+    pub const OTHER_BLUES: u16              = 7;
+    pub const STD_HW: u16                   = 10;
+    pub const STD_VW: u16                   = 11;
     pub const LOCAL_SUBROUTINES_OFFSET: u16 = 19;
+    pub const BLUE_SCALE: u16               = 1209;
+    pub const BLUE_FUZZ: u16                = 1211;
+    pub const STEM_SNAP_H: u16              = 1212;
 }
 
 #[allow(dead_code)]
@@ -303,6 +334,171 @@ fn gen_cff(
     w.data
 }
 
+fn gen_index(items: &[&[u8]]) -> Vec<u8> {
+    let mut data = convert(&[UInt16(items.len() as u16)]);
+    if items.is_empty() {
+        return data;
+    }
+
+    data.push(1); // offset size
+    let mut offset = 1;
+    data.push(offset);
+    for item in items {
+        offset += item.len() as u8;
+        data.push(offset);
+    }
+
+    for item in items {
+        data.extend_from_slice(item);
+    }
+
+    data
+}
+
+// Writes an operator.
+fn op(n: u16) -> TtfType {
+    match n {
+        0..=21 => UInt8(n as u8),
+        _ => UInt16(0x0C00 + n - 1200),
+    }
+}
+
+// Writes a number using a fixed size encoding, to simplify offsets calculation.
+fn fixed(n: u16) -> [TtfType; 2] {
+    [UInt8(28), UInt16(n)]
+}
+
+fn gen_sid_font() -> Vec<u8> {
+    let private_dict = convert(&[
+        CFFInt(-15), CFFInt(15), CFFInt(485), CFFInt(15), op(private_dict_operator::BLUE_VALUES),
+        CFFInt(-250), CFFInt(10), op(private_dict_operator::OTHER_BLUES),
+        CFFInt(50), op(private_dict_operator::STD_HW),
+        CFFInt(80), op(private_dict_operator::STD_VW),
+        CFFInt(50), CFFInt(10), op(private_dict_operator::STEM_SNAP_H),
+        UInt8(30), UInt8(0x0A), UInt8(0x05), UInt8(0xFF), op(private_dict_operator::BLUE_SCALE), // 0.05
+        CFFInt(0), op(private_dict_operator::BLUE_FUZZ),
+    ]);
+    let char_strings = gen_index(&[&[operator::ENDCHAR]]);
+    let strings = gen_index(&[b"1.000", b"Copyright", b"Test Regular", b"Test"]);
+
+    let top_dict = |char_strings_offset: u16, private_dict_offset: u16| {
+        let [b0, b1] = fixed(char_strings_offset);
+        let [b2, b3] = fixed(private_dict_offset);
+        convert(&[
+            CFFInt(391), op(top_dict_operator::VERSION),
+            CFFInt(392), op(top_dict_operator::NOTICE),
+            CFFInt(393), op(top_dict_operator::FULL_NAME),
+            CFFInt(394), op(top_dict_operator::FAMILY_NAME),
+            CFFInt(388), op(top_dict_operator::WEIGHT), // Regular
+            b0, b1, op(top_dict_operator::CHAR_STRINGS_OFFSET),
+            CFFInt(private_dict.len() as i32), b2, b3,
+            op(top_dict_operator::PRIVATE_DICT_SIZE_AND_OFFSET),
+        ])
+    };
+
+    let header = convert(&[
+        UInt8(1), // major version
+        UInt8(0), // minor version
+        UInt8(4), // header size
+        UInt8(0), // absolute offset
+        UInt16(0), // Name INDEX
+    ]);
+    let global_subrs = gen_index(&[]);
+    let top_dict_len = gen_index(&[&top_dict(0, 0)]).len();
+    let char_strings_offset = header.len() + top_dict_len + strings.len() + global_subrs.len();
+    let private_dict_offset = char_strings_offset + char_strings.len();
+    let top_dict = top_dict(char_strings_offset as u16, private_dict_offset as u16);
+
+    let mut data = header;
+    data.extend_from_slice(&gen_index(&[&top_dict]));
+    data.extend_from_slice(&strings);
+    data.extend_from_slice(&global_subrs);
+    data.extend_from_slice(&char_strings);
+    data.extend_from_slice(&private_dict);
+    data
+}
+
+fn gen_cid_font() -> Vec<u8> {
+    let private_dicts = [
+        convert(&[CFFInt(70), op(private_dict_operator::STD_VW)]),
+        convert(&[CFFInt(90), op(private_dict_operator::STD_VW)]),
+    ];
+    let char_strings = gen_index(&[&[operator::ENDCHAR], &[operator::ENDCHAR], &[operator::ENDCHAR]]);
+    let strings = gen_index(&[b"Adobe", b"Identity", b"Latin", b"Symbols"]);
+    let charset = convert(&[
+        UInt8(0), // format
+        UInt16(5), // CID of glyph 1
+        UInt16(7), // CID of glyph 2
+    ]);
+    let fd_select = convert(&[
+        UInt8(0), // format
+        UInt8(0), UInt8(1), UInt8(1), // font dict indices
+    ]);
+
+    let font_dict = |name: u16, private_dict: &[u8], private_dict_offset: u16| {
+        let [b0, b1] = fixed(private_dict_offset);
+        convert(&[
+            CFFInt(name as i32), op(top_dict_operator::FONT_NAME),
+            CFFInt(private_dict.len() as i32), b0, b1,
+            op(top_dict_operator::PRIVATE_DICT_SIZE_AND_OFFSET),
+        ])
+    };
+
+    let fd_array = |private_dict_offset: u16| {
+        let dict0 = font_dict(393, &private_dicts[0], private_dict_offset);
+        let offset = private_dict_offset + private_dicts[0].len() as u16;
+        let dict1 = font_dict(394, &private_dicts[1], offset);
+        gen_index(&[&dict0, &dict1])
+    };
+
+    let top_dict = |charset: u16, fd_select: u16, fd_array: u16, char_strings: u16| {
+        let [b0, b1] = fixed(charset);
+        let [b2, b3] = fixed(fd_select);
+        let [b4, b5] = fixed(fd_array);
+        let [b6, b7] = fixed(char_strings);
+        convert(&[
+            CFFInt(391), CFFInt(392), CFFInt(0), op(top_dict_operator::ROS),
+            b0, b1, op(top_dict_operator::CHARSET_OFFSET),
+            b2, b3, op(top_dict_operator::FD_SELECT),
+            b4, b5, op(top_dict_operator::FD_ARRAY),
+            b6, b7, op(top_dict_operator::CHAR_STRINGS_OFFSET),
+        ])
+    };
+
+    let header = convert(&[
+        UInt8(1), // major version
+        UInt8(0), // minor version
+        UInt8(4), // header size
+        UInt8(0), // absolute offset
+        UInt16(0), // Name INDEX
+    ]);
+    let global_subrs = gen_index(&[]);
+    let top_dict_len = gen_index(&[&top_dict(0, 0, 0, 0)]).len();
+    let charset_offset = header.len() + top_dict_len + strings.len() + global_subrs.len();
+    let fd_select_offset = charset_offset + charset.len();
+    let fd_array_offset = fd_select_offset + fd_select.len();
+    let char_strings_offset = fd_array_offset + fd_array(0).len();
+    let private_dict_offset = char_strings_offset + char_strings.len();
+    let top_dict = top_dict(
+        charset_offset as u16,
+        fd_select_offset as u16,
+        fd_array_offset as u16,
+        char_strings_offset as u16,
+    );
+
+    let mut data = header;
+    data.extend_from_slice(&gen_index(&[&top_dict]));
+    data.extend_from_slice(&strings);
+    data.extend_from_slice(&global_subrs);
+    data.extend_from_slice(&charset);
+    data.extend_from_slice(&fd_select);
+    data.extend_from_slice(&fd_array(private_dict_offset as u16));
+    data.extend_from_slice(&char_strings);
+    data.extend_from_slice(&private_dicts[0]);
+    data.extend_from_slice(&private_dicts[1]);
+    data
+}
+
 #[test]
 fn unsupported_version() {
     let data = convert(&[
@@ -929,6 +1125,54 @@ fn recursive_mixed_subr() {
     assert_eq!(res.unwrap_err(), CFFError::NestingLimitReached);
 }
 
+#[test]
+fn stem_hints() {
+    let data = gen_cff(&[], &[], &[
+        CFFInt(5), // width
+        CFFInt(10), CFFInt(20), CFFInt(30), CFFInt(-21), UInt8(operator::HORIZONTAL_STEM),
+        CFFInt(100), CFFInt(10), UInt8(operator::VERTICAL_STEM),
+        CFFInt(10), CFFInt(20), UInt8(operator::MOVE_TO),
+        CFFInt(30), CFFInt(40), UInt8(operator::LINE_TO),
+        UInt8(operator::ENDCHAR),
+    ]);
+    let table = cff::Table::parse(&data).unwrap();
+
+    let mut builder = Builder(String::new());
+    let rect = table.outline_with_hints(GlyphId(0), &mut builder).unwrap();
+    assert_eq!(builder.0, "HS 10 20 HS 60 -21 VS 100 10 M 10 20 L 40 60 Z ");
+    assert_eq!(rect, Rect { x_min: 10, y_min: 20, x_max: 40, y_max: 60 });
+    assert_eq!(table.glyph_width(GlyphId(0)), Some(5));
+
+    // Regular outlining ignores hints.
+    let mut builder = Builder(String::new());
+    table.outline(GlyphId(0), &mut builder).unwrap();
+    assert_eq!(builder.0, "M 10 20 L 40 60 Z ");
+}
+
+#[test]
+fn hint_masks() {
+    let data = gen_cff(&[], &[], &[
+        CFFInt(0), CFFInt(10), CFFInt(20), CFFInt(10),
+        UInt8(operator::HORIZONTAL_STEM_HINT_MASK),
+        CFFInt(5), CFFInt(10), UInt8(operator::VERTICAL_STEM_HINT_MASK),
+        // Implicit vstem.
+        CFFInt(50), CFFInt(10), UInt8(operator::HINT_MASK), UInt8(0b1110_0000),
+        CFFInt(10), CFFInt(20), UInt8(operator::MOVE_TO),
+        UInt8(operator::COUNTER_MASK), UInt8(0b0011_0000),
+        UInt8(operator::HINT_MASK), UInt8(0b1000_0000),
+        CFFInt(30), CFFInt(40), UInt8(operator::LINE_TO),
+        UInt8(operator::ENDCHAR),
+    ]);
+    let table = cff::Table::parse(&data).unwrap();
+
+    let mut builder = Builder(String::new());
+    table.outline_with_hints(GlyphId(0), &mut builder).unwrap();
+    assert_eq!(
+        builder.0,
+        "HS 0 10 HS 30 10 VS 5 10 VS 50 10 HM [224] M 10 20 CM [48] HM [128] L 40 60 Z "
+    );
+}
+
 #[test]
 fn zero_char_string_offset() {
     let data = convert(&[
@@ -981,16 +1225,194 @@ fn invalid_char_string_offset() {
     assert!(cff::Table::parse(&data).is_none());
 }
 
+#[test]
+fn top_dict_strings() {
+    let data = gen_sid_font();
+    let table = cff::Table::parse(&data).unwrap();
+    assert_eq!(table.version(), Some("1.000"));
+    assert_eq!(table.notice(), Some("Copyright"));
+    assert_eq!(table.full_name(), Some("Test Regular"));
+    assert_eq!(table.family_name(), Some("Test"));
+    assert_eq!(table.weight(), Some("Regular"));
+    assert_eq!(table.ros(), None);
+}
+
+#[test]
+fn private_dict_hints() {
+    let data = gen_sid_font();
+    let table = cff::Table::parse(&data).unwrap();
+    assert_eq!(table.number_of_font_dicts(), 1);
+    assert!(table.private_dict_hints(1).is_none());
+
+    let hints = table.private_dict_hints(0).unwrap();
+    assert_eq!(hints.blue_values.collect::<Vec<_>>(), [-15.0, 0.0, 485.0, 500.0]);
+    assert_eq!(hints.other_blues.collect::<Vec<_>>(), [-250.0, -240.0]);
+    assert_eq!(hints.family_blues.count(), 0);
+    assert_eq!(hints.family_other_blues.count(), 0);
+    assert_eq!(hints.std_hw, Some(50.0));
+    assert_eq!(hints.std_vw, Some(80.0));
+    assert_eq!(hints.stem_snap_h.collect::<Vec<_>>(), [50.0, 60.0]);
+    assert_eq!(hints.stem_snap_v.count(), 0);
+    assert_eq!(hints.blue_scale, 0.05);
+    assert_eq!(hints.blue_shift, 7.0);
+    assert_eq!(hints.blue_fuzz, 0.0);
+}
+
+#[test]
+fn cid_font_dicts() {
+    let data = gen_cid_font();
+    let table = cff::Table::parse(&data).unwrap();
+    let ros = table.ros().unwrap();
+    assert_eq!(ros.registry, "Adobe");
+    assert_eq!(ros.ordering, "Identity");
+    assert_eq!(ros.supplement, 0);
+    assert_eq!(table.full_name(), None);
+
+    assert_eq!(table.number_of_font_dicts(), 2);
+    assert_eq!(table.private_dict_hints(0).unwrap().std_vw, Some(70.0));
+    assert_eq!(table.private_dict_hints(1).unwrap().std_vw, Some(90.0));
+    assert_eq!(table.private_dict_hints(1).unwrap().blue_scale, 0.039625);
+    assert!(table.private_dict_hints(2).is_none());
+}
+
+#[test]
+fn cid_mapping() {
+    let data = gen_cid_font();
+    let table = cff::Table::parse(&data).unwrap();
+    assert_eq!(table.glyph_cid(GlyphId(1)), Some(5));
+    assert_eq!(table.glyph_index_by_cid(0), Some(GlyphId(0)));
+    assert_eq!(table.glyph_index_by_cid(5), Some(GlyphId(1)));
+    assert_eq!(table.glyph_index_by_cid(7), Some(GlyphId(2)));
+    assert_eq!(table.glyph_index_by_cid(6), None);
+
+    let data = gen_sid_font();
+    let table = cff::Table::parse(&data).unwrap();
+    assert_eq!(table.glyph_index_by_cid(0), None);
+}
+
+#[test]
+fn cid_font_dict_select() {
+    let data = gen_cid_font();
+    let table = cff::Table::parse(&data).unwrap();
+    assert_eq!(table.glyph_font_dict_index(GlyphId(0)), Some(0));
+    assert_eq!(table.glyph_font_dict_index(GlyphId(1)), Some(1));
+    assert_eq!(table.glyph_font_dict_index(GlyphId(2)), Some(1));
+    assert_eq!(table.glyph_font_dict_index(GlyphId(3)), None);
+    assert_eq!(table.font_dict_name(0), Some("Latin"));
+    assert_eq!(table.font_dict_name(1), Some("Symbols"));
+    assert_eq!(table.font_dict_name(2), None);
+
+    let data = gen_sid_font();
+    let table = cff::Table::parse(&data).unwrap();
+    assert_eq!(table.glyph_font_dict_index(GlyphId(0)), None);
+    assert_eq!(table.font_dict_name(0), None);
+}
+
+fn tokens_to_string(tokens: cff::Tokens) -> String {
+    let mut s = String::new();
+    for token in tokens {
+        match token {
+            Ok(cff::Token::Operand(n)) => write!(&mut s, "{} ", n).unwrap(),
+            Ok(cff::Token::Operator(op)) => match op {
+                cff::Operator::HintMask(mask) | cff::Operator::CounterMask(mask) => {
+                    write!(&mut s, "{} {:?} ", op.name(), mask).unwrap()
+                }
+                _ => write!(&mut s, "{} ", op.name()).unwrap(),
+            },
+            Err(e) => write!(&mut s, "{:?} ", e).unwrap(),
+        }
+    }
+
+    s
+}
+
+#[test]
+fn decompile() {
+    let data = gen_cff(&[], &[], &[
+        CFFInt(5), // width
+        CFFInt(10), CFFInt(20), UInt8(operator::HORIZONTAL_STEM_HINT_MASK),
+        CFFInt(50), CFFInt(10), UInt8(operator::HINT_MASK), UInt8(0b1100_0000),
+        UInt8(28), Int16(1000), UInt8(operator::HORIZONTAL_MOVE_TO),
+        UInt8(operator::FIXED_16_16), Int32(0x18000), CFFInt(-200), UInt8(operator::LINE_TO),
+        UInt8(operator::ENDCHAR),
+    ]);
+    let table = cff::Table::parse(&data).unwrap();
+    let tokens = table.char_string_tokens(GlyphId(0), false).unwrap();
+    assert_eq!(
+        tokens_to_string(tokens),
+        "5 10 20 hstemhm 50 10 hintmask [192] 1000 hmoveto 1.5 -200 rlineto endchar "
+    );
+    assert!(table.char_string_tokens(GlyphId(1), false).is_none());
+}
+
+#[test]
+fn decompile_subrs() {
+    let data = gen_cff(
+        &[&[
+            CFFInt(30), CFFInt(40), UInt8(operator::LINE_TO),
+            UInt8(operator::RETURN),
+        ]],
+        &[&[
+            CFFInt(10), CFFInt(20), UInt8(operator::HORIZONTAL_STEM_HINT_MASK),
+            UInt8(operator::RETURN),
+        ]],
+        &[
+            CFFInt(0 - 107), UInt8(operator::CALL_LOCAL_SUBROUTINE),
+            UInt8(operator::HINT_MASK), UInt8(0b1000_0000),
+            CFFInt(10), UInt8(operator::HORIZONTAL_MOVE_TO),
+            CFFInt(0 - 107), UInt8(operator::CALL_GLOBAL_SUBROUTINE),
+            UInt8(operator::ENDCHAR),
+        ],
+    );
+    let table = cff::Table::parse(&data).unwrap();
+
+    // Stems from subroutines are still counted.
+    let tokens = table.char_string_tokens(GlyphId(0), false).unwrap();
+    assert_eq!(
+        tokens_to_string(tokens),
+        "-107 callsubr hintmask [128] 10 hmoveto -107 callgsubr endchar "
+    );
+
+    let tokens = table.char_string_tokens(GlyphId(0), true).unwrap();
+    assert_eq!(
+        tokens_to_string(tokens),
+        "10 20 hstemhm hintmask [128] 10 hmoveto 30 40 rlineto endchar "
+    );
+}
+
+#[test]
+fn decompile_errors() {
+    let data = gen_cff(&[], &[], &[
+        CFFInt(10), UInt8(operator::HORIZONTAL_MOVE_TO),
+        CFFInt(1), UInt8(operator::CALL_LOCAL_SUBROUTINE),
+        UInt8(operator::ENDCHAR),
+    ]);
+    let table = cff::Table::parse(&data).unwrap();
+    let tokens = table.char_string_tokens(GlyphId(0), false).unwrap();
+    assert_eq!(tokens_to_string(tokens), "10 hmoveto 1 InvalidSubroutineIndex ");
+
+    let data = gen_cff(&[], &[], &[
+        CFFInt(10), UInt8(operator::HORIZONTAL_MOVE_TO),
+        UInt8(12), UInt8(0), // dotsection
+        UInt8(operator::ENDCHAR),
+    ]);
+    let table = cff::Table::parse(&data).unwrap();
+    let tokens = table.char_string_tokens(GlyphId(0), false).unwrap();
+    assert_eq!(tokens_to_string(tokens), "10 hmoveto UnsupportedOperator ");
+
+    let data = gen_cff(&[], &[], &[
+        UInt8(operator::ENDCHAR),
+        CFFInt(10),
+    ]);
+    let table = cff::Table::parse(&data).unwrap();
+    let tokens = table.char_string_tokens(GlyphId(0), false).unwrap();
+    assert_eq!(tokens_to_string(tokens), "DataAfterEndChar ");
+}
+
 // TODO: return from main
 // TODO: return without endchar
 // TODO: data after return
 // TODO: recursive subr
-// TODO: HORIZONTAL_STEM
-// TODO: VERTICAL_STEM
-// TODO: HORIZONTAL_STEM_HINT_MASK
-// TODO: HINT_MASK
-// TODO: COUNTER_MASK
-// TODO: VERTICAL_STEM_HINT_MASK
 // TODO: CURVE_LINE
 // TODO: LINE_CURVE
 // TODO: VH_CURVE_TO
@@ -0,0 +1,186 @@
+use ttf_parser::cff2::{Table, Token};
+use ttf_parser::{CFFError, GlyphId, RegionAxisCoordinates};
+use crate::{convert, Unit::*};
+
+mod operator {
+    pub const HORIZONTAL_MOVE_TO: u8 = 22;
+    pub const MOVE_TO: u8            = 21;
+    pub const VS_INDEX: u8           = 15;
+    pub const BLEND: u8              = 16;
+    pub const ENDCHAR: u8            = 14;
+}
+
+// Encodes a small charstring number.
+fn int(n: i16) -> crate::Unit {
+    assert!((-107..=107).contains(&n));
+    UInt8((n + 139) as u8)
+}
+
+fn gen_cff2(chars: &[&[crate::Unit]]) -> Vec<u8> {
+    let chars: Vec<Vec<u8>> = chars.iter().map(|c| convert(c)).collect();
+    let char_strings_len = 4 + 1 + chars.len() + 1 + chars.iter().map(|c| c.len()).sum::<usize>();
+
+    let header_len = 5;
+    let top_dict_len = 8;
+    let global_subrs_len = 4;
+    let char_strings_offset = header_len + top_dict_len + global_subrs_len;
+    let variation_store_offset = char_strings_offset + char_strings_len;
+
+    let mut data = convert(&[
+        UInt8(2), // major version
+        UInt8(0), // minor version
+        UInt8(5), // header size
+        UInt16(top_dict_len as u16),
+
+        // Top DICT
+        UInt8(28), UInt16(char_strings_offset as u16), UInt8(17), // CharStrings
+        UInt8(28), UInt16(variation_store_offset as u16), UInt8(24), // VariationStore
+
+        // Global Subroutines INDEX
+        UInt32(0), // count
+
+        // CharStrings INDEX
+        UInt32(chars.len() as u32), // count
+        UInt8(1), // offset size
+    ]);
+
+    let mut offset = 1;
+    data.push(offset);
+    for c in &chars {
+        offset += c.len() as u8;
+        data.push(offset);
+    }
+
+    for c in &chars {
+        data.extend_from_slice(c);
+    }
+
+    data.extend_from_slice(&convert(&[
+        UInt16(50), // length
+
+        // ItemVariationStore
+        UInt16(1), // format
+        UInt32(16), // offset to the region list
+        UInt16(2), // number of data subtables
+        UInt32(32), // offset to the data subtable [0]
+        UInt32(42), // offset to the data subtable [1]
+
+        // VariationRegionList
+        UInt16(1), // axis count
+        UInt16(2), // region count
+        Int16(0), Int16(16384), Int16(16384), // region [0]: 0..1
+        Int16(-16384), Int16(-16384), Int16(0), // region [1]: -1..0
+
+        // ItemVariationData [0]
+        UInt16(0), // item count
+        UInt16(0), // word delta count
+        UInt16(2), // region index count
+        UInt16(0), UInt16(1), // region indices
+
+        // ItemVariationData [1]
+        UInt16(0), // item count
+        UInt16(0), // word delta count
+        UInt16(1), // region index count
+        UInt16(1), // region indices
+    ]));
+
+    data
+}
+
+fn tokens_to_string(table: &Table, glyph_id: u16) -> String {
+    let tokens = table.char_string_tokens(ttf_parser::GlyphId(glyph_id), false).unwrap();
+    let mut s = Vec::new();
+    for token in tokens {
+        match token {
+            Ok(Token::Operand(n)) => s.push(n.to_string()),
+            Ok(Token::Operator(op)) => s.push(op.name().to_string()),
+            Err(e) => s.push(format!("{:?}", e)),
+        }
+    }
+
+    s.join(" ")
+}
+
+#[test]
+fn decompile_blend() {
+    let data = gen_cff2(&[
+        &[
+            int(10), int(20), int(5), int(-5), int(1), int(2), int(2),
+            UInt8(operator::BLEND), UInt8(operator::MOVE_TO),
+        ],
+        &[
+            int(1), UInt8(operator::VS_INDEX),
+            int(100), int(50), int(1), UInt8(operator::BLEND),
+            UInt8(operator::HORIZONTAL_MOVE_TO),
+        ],
+        &[
+            // Only two operands left after blend.
+            int(10), int(20), int(30), int(5), int(-5), int(1), int(2), int(2),
+            UInt8(operator::BLEND), UInt8(operator::MOVE_TO),
+        ],
+    ]);
+    let table = Table::parse(&data).unwrap();
+    assert_eq!(tokens_to_string(&table, 0), "10 20 5 -5 1 2 2 blend rmoveto");
+    assert_eq!(tokens_to_string(&table, 1), "1 vsindex 100 50 1 blend hmoveto");
+    assert_eq!(tokens_to_string(&table, 2), "10 20 30 5 -5 1 2 2 blend rmoveto");
+}
+
+#[test]
+fn decompile_errors() {
+    let data = gen_cff2(&[
+        &[int(10), UInt8(operator::HORIZONTAL_MOVE_TO), UInt8(operator::ENDCHAR)],
+        &[int(2), UInt8(operator::VS_INDEX)],
+        &[int(1), int(1), UInt8(operator::BLEND)],
+    ]);
+    let table = Table::parse(&data).unwrap();
+    assert_eq!(tokens_to_string(&table, 0), "10 hmoveto InvalidOperator");
+    assert_eq!(tokens_to_string(&table, 1), "2 InvalidItemVariationDataIndex");
+    assert_eq!(tokens_to_string(&table, 2), "1 1 InvalidArgumentsStackLength");
+
+    let mut tokens = table.char_string_tokens(ttf_parser::GlyphId(1), false).unwrap();
+    assert_eq!(tokens.next(), Some(Ok(Token::Operand(2.0))));
+    assert_eq!(tokens.next(), Some(Err(CFFError::InvalidItemVariationDataIndex)));
+    assert_eq!(tokens.next(), None);
+}
+
+#[test]
+fn blend_values() {
+    let data = gen_cff2(&[
+        &[
+            int(10), int(20), int(5), int(-5), int(1), int(2), int(2),
+            UInt8(operator::BLEND), UInt8(operator::MOVE_TO),
+        ],
+        &[
+            int(1), UInt8(operator::VS_INDEX),
+            int(100), int(50), int(1), UInt8(operator::BLEND),
+            UInt8(operator::HORIZONTAL_MOVE_TO),
+        ],
+    ]);
+    let table = Table::parse(&data).unwrap();
+
+    assert_eq!(table.glyph_variation_store_index(GlyphId(0)), Some(0));
+    assert_eq!(table.glyph_variation_store_index(GlyphId(1)), Some(1));
+    assert_eq!(table.glyph_variation_store_index(GlyphId(2)), None);
+
+    let indices: Vec<u16> = table.variation_region_indices(0).unwrap().into_iter().collect();
+    assert_eq!(indices, [0, 1]);
+    let indices: Vec<u16> = table.variation_region_indices(1).unwrap().into_iter().collect();
+    assert_eq!(indices, [1]);
+    assert!(table.variation_region_indices(2).is_none());
+
+    let region: Vec<RegionAxisCoordinates> = table.variation_region(1).unwrap().into_iter().collect();
+    assert_eq!(region, [RegionAxisCoordinates { start_coord: -16384, peak_coord: -16384, end_coord: 0 }]);
+    assert!(table.variation_region(2).is_none());
+
+    let values: Vec<_> = table.glyph_blend_values(GlyphId(0)).unwrap().map(|v| v.unwrap()).collect();
+    assert_eq!(values.len(), 2);
+    assert_eq!(values[0].default, 10.0);
+    assert_eq!(values[0].deltas(), [5.0, -5.0]);
+    assert_eq!(values[1].default, 20.0);
+    assert_eq!(values[1].deltas(), [1.0, 2.0]);
+
+    let values: Vec<_> = table.glyph_blend_values(GlyphId(1)).unwrap().map(|v| v.unwrap()).collect();
+    assert_eq!(values.len(), 1);
+    assert_eq!(values[0].default, 100.0);
+    assert_eq!(values[0].deltas(), [50.0]);
+}
@@ -553,3 +553,147 @@ mod format4 {
         assert_eq!(vec, [27, 28, 29, 30, 31, 32, 33, 34, 65533, 65534, 65535]);
     }
 }
+
+mod format8 {
+    use ttf_parser::{cmap, GlyphId};
+    use crate::{convert, Unit::*};
+
+    fn subtable_data() -> Vec<u8> {
+        let mut data = convert(&[
+            UInt16(8), // format
+            UInt16(0), // reserved
+            UInt32(8244), // length
+            UInt32(0), // language
+        ]);
+
+        // is32: 0xD800 starts a 32-bit character code.
+        let mut is32 = vec![0; 8192];
+        is32[0xD800 / 8] = 0x80;
+        data.extend_from_slice(&is32);
+
+        data.extend_from_slice(&convert(&[
+            UInt32(2), // number of groups
+            // Group [0]
+            UInt32(0x41), // start char code
+            UInt32(0x43), // end char code
+            UInt32(10), // start glyph ID
+            // Group [1]
+            UInt32(0xD800DC00), // start char code
+            UInt32(0xD800DC01), // end char code
+            UInt32(20), // start glyph ID
+        ]));
+
+        data
+    }
+
+    #[test]
+    fn single_group() {
+        let data = subtable_data();
+        let subtable = cmap::Subtable8::parse(&data).unwrap();
+        assert_eq!(subtable.glyph_index(0x40), None);
+        assert_eq!(subtable.glyph_index(0x41), Some(GlyphId(10)));
+        assert_eq!(subtable.glyph_index(0x43), Some(GlyphId(12)));
+        assert_eq!(subtable.glyph_index(0x44), None);
+    }
+
+    #[test]
+    fn surrogate_pairs() {
+        let data = subtable_data();
+        let subtable = cmap::Subtable8::parse(&data).unwrap();
+        assert!(subtable.is_32bit_start(0xD800));
+        assert!(!subtable.is_32bit_start(0xD801));
+        assert!(!subtable.is_32bit_start(0x41));
+        assert_eq!(subtable.glyph_index(0x10000), Some(GlyphId(20)));
+        assert_eq!(subtable.glyph_index(0x10001), Some(GlyphId(21)));
+        assert_eq!(subtable.glyph_index(0xD800DC01), Some(GlyphId(21)));
+        assert_eq!(subtable.glyph_index(0x10002), None);
+    }
+
+    #[test]
+    fn collect_codepoints() {
+        let data = subtable_data();
+        let subtable = cmap::Subtable8::parse(&data).unwrap();
+
+        let mut vec = vec![];
+        subtable.codepoints(|c| vec.push(c));
+        assert_eq!(vec, [0x41, 0x42, 0x43, 0x10000, 0x10001]);
+    }
+
+    #[test]
+    fn truncated() {
+        let data = subtable_data();
+        assert!(cmap::Subtable8::parse(&data[..8000]).is_none());
+    }
+}
+
+mod format14 {
+    use ttf_parser::{cmap, GlyphId};
+    use ttf_parser::cmap::{GlyphVariationResult, VariationSequence};
+    use crate::{convert, Unit::*};
+
+    fn subtable_data() -> Vec<u8> {
+        convert(&[
+            UInt16(14), // format
+            UInt32(62), // length
+            UInt32(2), // number of variation selector records
+            // Record [0]
+            Raw(&[0x00, 0xFE, 0x00]), // variation selector
+            UInt32(32), // offset to default UVS
+            UInt32(44), // offset to non-default UVS
+            // Record [1]
+            Raw(&[0x00, 0xFE, 0x0F]), // variation selector
+            UInt32(0), // offset to default UVS
+            UInt32(53), // offset to non-default UVS
+
+            // Default UVS [0]
+            UInt32(2), // number of ranges
+            Raw(&[0x00, 0x00, 0x30]), UInt8(1), // start value, additional count
+            Raw(&[0x00, 0x00, 0x41]), UInt8(0), // start value, additional count
+
+            // Non-default UVS [0]
+            UInt32(1), // number of mappings
+            Raw(&[0x00, 0x4E, 0x00]), UInt16(7), // unicode value, glyph ID
+
+            // Non-default UVS [1]
+            UInt32(1), // number of mappings
+            Raw(&[0x01, 0xF6, 0x00]), UInt16(9), // unicode value, glyph ID
+        ])
+    }
+
+    #[test]
+    fn glyph_index() {
+        let data = subtable_data();
+        let subtable = cmap::Subtable14::parse(&data).unwrap();
+        assert_eq!(subtable.glyph_index(0x31, 0xFE00), Some(GlyphVariationResult::UseDefault));
+        assert_eq!(subtable.glyph_index(0x4E00, 0xFE00), Some(GlyphVariationResult::Found(GlyphId(7))));
+        assert_eq!(subtable.glyph_index(0x1F600, 0xFE0F), Some(GlyphVariationResult::Found(GlyphId(9))));
+        assert_eq!(subtable.glyph_index(0x1F600, 0xFE00), None);
+        assert_eq!(subtable.glyph_index(0x31, 0xFE01), None);
+    }
+
+    #[test]
+    fn variation_sequences() {
+        let data = subtable_data();
+        let subtable = cmap::Subtable14::parse(&data).unwrap();
+
+        let sequence = |code_point, variation, glyph| VariationSequence { code_point, variation, glyph };
+        let sequences: Vec<_> = subtable.variation_sequences().collect();
+        assert_eq!(sequences, [
+            sequence(0x30, 0xFE00, GlyphVariationResult::UseDefault),
+            sequence(0x31, 0xFE00, GlyphVariationResult::UseDefault),
+            sequence(0x41, 0xFE00, GlyphVariationResult::UseDefault),
+            sequence(0x4E00, 0xFE00, GlyphVariationResult::Found(GlyphId(7))),
+            sequence(0x1F600, 0xFE0F, GlyphVariationResult::Found(GlyphId(9))),
+        ]);
+    }
+
+    #[test]
+    fn variation_sequences_with_invalid_offset() {
+        let mut data = subtable_data();
+        // Point non-default UVS [1] outside of the subtable.
+        data[28..32].copy_from_slice(&1000u32.to_be_bytes());
+
+        let subtable = cmap::Subtable14::parse(&data).unwrap();
+        assert_eq!(subtable.variation_sequences().count(), 4);
+    }
+}
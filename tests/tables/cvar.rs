@@ -0,0 +1,121 @@
+use ttf_parser::cvar::Table;
+use ttf_parser::{Face, NormalizedCoordinate, RawFaceTables, Tag};
+use crate::{convert, demo_face_tables, Unit::*};
+
+fn cvar_data() -> Vec<u8> {
+    convert(&[
+        UInt32(0x00010000), // version
+        UInt16(0x8003), // tuple variation count with shared point numbers
+        UInt16(30), // offset to data
+
+        // Tuple variation header [0].
+        UInt16(5), // data size
+        UInt16(0xA000), // embedded peak, private point numbers
+        Int16(16384), // peak: 1.0
+
+        // Tuple variation header [1].
+        UInt16(5), // data size
+        UInt16(0x8000), // embedded peak
+        Int16(-16384), // peak: -1.0
+
+        // Tuple variation header [2].
+        UInt16(6), // data size
+        UInt16(0xE000), // embedded peak, intermediate region, private point numbers
+        Int16(8192), // peak: 0.5
+        Int16(0), // start: 0.0
+        Int16(16384), // end: 1.0
+
+        // Shared point numbers.
+        UInt8(2), // count
+        UInt8(0x01), UInt8(1), UInt8(2), // control, points 1 and 3
+
+        // Tuple data [0].
+        UInt8(1), // point numbers count
+        UInt8(0x00), UInt8(0), // control, point 0
+        UInt8(0x00), Int8(10), // control, delta
+
+        // Tuple data [1].
+        UInt8(0x41), Int16(-20), Int16(300), // control, word deltas
+
+        // Tuple data [2].
+        UInt8(0), // all points
+        UInt8(0x03), Int8(1), Int8(2), Int8(3), Int8(4), // control, deltas
+    ])
+}
+
+fn apply(table: &Table, coord: f32, len: usize) -> Vec<f32> {
+    let mut values = vec![100.0, 200.0, 300.0, 400.0];
+    values.truncate(len);
+    table.apply(&[NormalizedCoordinate::from(coord)], &mut values).unwrap();
+    values
+}
+
+#[test]
+fn apply_deltas() {
+    let data = cvar_data();
+    let table = Table::parse(&data).unwrap();
+    assert_eq!(apply(&table, 0.0, 4), [100.0, 200.0, 300.0, 400.0]);
+    assert_eq!(apply(&table, 1.0, 4), [110.0, 200.0, 300.0, 400.0]);
+    assert_eq!(apply(&table, 0.5, 4), [106.0, 202.0, 303.0, 404.0]);
+    assert_eq!(apply(&table, 0.25, 4), [103.0, 201.0, 301.5, 402.0]);
+    assert_eq!(apply(&table, -0.5, 4), [100.0, 190.0, 300.0, 550.0]);
+}
+
+#[test]
+fn apply_out_of_bounds() {
+    let data = cvar_data();
+    let table = Table::parse(&data).unwrap();
+    // Point 3 is out of bounds and must be ignored.
+    assert_eq!(apply(&table, -1.0, 2), [100.0, 180.0]);
+    assert_eq!(apply(&table, 0.5, 2), [106.0, 202.0]);
+}
+
+#[test]
+fn invalid_version() {
+    let data = convert(&[
+        UInt16(2), // major version
+        UInt16(0), // minor version
+        UInt16(0), // tuple variation count
+        UInt16(8), // offset to data
+    ]);
+    assert!(Table::parse(&data).is_none());
+}
+
+#[test]
+fn face_cvt_values() {
+    let fvar = convert(&[
+        UInt32(0x00010000), // version
+        UInt16(16), // offset to axes array
+        UInt16(2), // reserved
+        UInt16(1), // axis count
+        UInt16(20), // axis size
+        UInt16(0), // instance count
+        UInt16(8), // instance size
+
+        Raw(b"wght"), Fixed(100.0), Fixed(400.0), Fixed(900.0), UInt16(0), UInt16(256),
+    ]);
+    let cvt = convert(&[Int16(100), Int16(200), Int16(300), Int16(400)]);
+    let cvar = cvar_data();
+
+    let tables = demo_face_tables();
+
+    let face = Face::from_raw_tables(tables.clone()).unwrap();
+    assert_eq!(face.cvt_values(&mut [0.0; 4]), None);
+
+    let tables = RawFaceTables {
+        cvt: Some(&cvt),
+        cvar: Some(&cvar),
+        fvar: Some(&fvar),
+        ..tables
+    };
+    let mut face = Face::from_raw_tables(tables).unwrap();
+
+    let mut values = [0.0; 5];
+    assert_eq!(face.cvt_values(&mut values), Some(4));
+    assert_eq!(values, [100.0, 200.0, 300.0, 400.0, 0.0]);
+
+    face.set_variation(Tag::from_bytes(b"wght"), 650.0);
+    let mut values = [0.0; 3];
+    assert_eq!(face.cvt_values(&mut values), Some(3));
+    assert_eq!(values, [106.0, 202.0, 303.0]);
+}
@@ -0,0 +1,100 @@
+use ttf_parser::fvar::Table;
+use ttf_parser::{Face, NormalizedCoordinate, RawFaceTables};
+use crate::{convert, demo_face_tables, Unit::*};
+
+fn fvar_data(instance_size: u16) -> Vec<u8> {
+    let mut data = convert(&[
+        UInt32(0x00010000), // version
+        UInt16(16), // offset to axes array
+        UInt16(2), // reserved
+        UInt16(2), // axis count
+        UInt16(20), // axis size
+        UInt16(2), // instance count
+        UInt16(instance_size), // instance size
+
+        Raw(b"wght"), Fixed(100.0), Fixed(400.0), Fixed(900.0), UInt16(0), UInt16(256),
+        Raw(b"wdth"), Fixed(50.0), Fixed(100.0), Fixed(100.0), UInt16(0), UInt16(257),
+
+        // Instance [0]
+        UInt16(258), // subfamily name ID
+        UInt16(0), // flags
+        Fixed(700.0), // wght
+        Fixed(75.0), // wdth
+    ]);
+
+    if instance_size == 14 {
+        data.extend_from_slice(&convert(&[UInt16(259)])); // PostScript name ID
+    }
+
+    data.extend_from_slice(&convert(&[
+        // Instance [1]
+        UInt16(260), // subfamily name ID
+        UInt16(0), // flags
+        Fixed(400.0), // wght
+        Fixed(100.0), // wdth
+    ]));
+
+    if instance_size == 14 {
+        data.extend_from_slice(&convert(&[UInt16(0xFFFF)])); // PostScript name ID
+    }
+
+    data
+}
+
+#[test]
+fn instances() {
+    let data = fvar_data(12);
+    let table = Table::parse(&data).unwrap();
+    assert_eq!(table.axes.len(), 2);
+    assert_eq!(table.instances.len(), 2);
+
+    let instance = table.instances.get(0).unwrap();
+    assert_eq!(instance.subfamily_name_id, 258);
+    assert_eq!(instance.post_script_name_id, None);
+    let coords: Vec<f32> = instance.coordinates.into_iter().map(|c| c.0).collect();
+    assert_eq!(coords, [700.0, 75.0]);
+
+    assert_eq!(table.instances.into_iter().last().unwrap().subfamily_name_id, 260);
+    assert!(table.instances.get(2).is_none());
+}
+
+#[test]
+fn instances_with_post_script_names() {
+    let data = fvar_data(14);
+    let table = Table::parse(&data).unwrap();
+    assert_eq!(table.instances.len(), 2);
+    assert_eq!(table.instances.get(0).unwrap().post_script_name_id, Some(259));
+    assert_eq!(table.instances.get(1).unwrap().subfamily_name_id, 260);
+    assert_eq!(table.instances.get(1).unwrap().post_script_name_id, None);
+}
+
+#[test]
+fn instances_with_invalid_size() {
+    let data = fvar_data(8);
+    let table = Table::parse(&data).unwrap();
+    assert_eq!(table.axes.len(), 2);
+    assert!(table.instances.is_empty());
+}
+
+#[test]
+fn set_named_instance() {
+    let data = fvar_data(14);
+    let tables = RawFaceTables {
+        fvar: Some(&data),
+        ..demo_face_tables()
+    };
+    let mut face = Face::from_raw_tables(tables).unwrap();
+
+    assert_eq!(face.named_instances().len(), 2);
+
+    face.set_named_instance(0).unwrap();
+    assert_eq!(
+        face.variation_coordinates(),
+        [NormalizedCoordinate::from(0.6), NormalizedCoordinate::from(-0.5)]
+    );
+
+    face.set_named_instance(1).unwrap();
+    assert!(!face.has_non_default_variation_coordinates());
+
+    assert!(face.set_named_instance(2).is_none());
+}
@@ -0,0 +1,74 @@
+use ttf_parser::gasp::{Behavior, Table};
+use ttf_parser::{Face, RawFaceTables};
+use crate::{convert, demo_face_tables, Unit::*};
+
+fn gasp_data() -> Vec<u8> {
+    convert(&[
+        UInt16(1), // version
+        UInt16(3), // number of ranges
+        UInt16(8), UInt16(0x0002), // max PPEM, behavior
+        UInt16(16), UInt16(0x0005), // max PPEM, behavior
+        UInt16(0xFFFF), UInt16(0x000F), // max PPEM, behavior
+    ])
+}
+
+#[test]
+fn parse() {
+    let data = gasp_data();
+    let table = Table::parse(&data).unwrap();
+    assert_eq!(table.ranges.len(), 3);
+
+    let behavior = table.behavior(0).unwrap();
+    assert!(!behavior.gridfit());
+    assert!(behavior.do_gray());
+    assert!(!behavior.symmetric_gridfit());
+    assert!(!behavior.symmetric_smoothing());
+
+    assert_eq!(table.behavior(8), Some(Behavior(0x0002)));
+
+    let behavior = table.behavior(9).unwrap();
+    assert!(behavior.gridfit());
+    assert!(!behavior.do_gray());
+    assert!(behavior.symmetric_gridfit());
+    assert!(!behavior.symmetric_smoothing());
+
+    let behavior = table.behavior(1000).unwrap();
+    assert!(behavior.gridfit());
+    assert!(behavior.do_gray());
+    assert!(behavior.symmetric_gridfit());
+    assert!(behavior.symmetric_smoothing());
+}
+
+#[test]
+fn uncovered_ppem() {
+    let data = convert(&[
+        UInt16(0), // version
+        UInt16(1), // number of ranges
+        UInt16(8), UInt16(0x0003), // max PPEM, behavior
+    ]);
+    let table = Table::parse(&data).unwrap();
+    assert_eq!(table.behavior(8), Some(Behavior(0x0003)));
+    assert_eq!(table.behavior(9), None);
+}
+
+#[test]
+fn invalid_version() {
+    let data = convert(&[
+        UInt16(2), // version
+        UInt16(0), // number of ranges
+    ]);
+    assert!(Table::parse(&data).is_none());
+}
+
+#[test]
+fn face_behavior() {
+    let tables = demo_face_tables();
+
+    let face = Face::from_raw_tables(tables.clone()).unwrap();
+    assert_eq!(face.gasp_behavior(12), None);
+
+    let data = gasp_data();
+    let tables = RawFaceTables { gasp: Some(&data), ..tables };
+    let face = Face::from_raw_tables(tables).unwrap();
+    assert_eq!(face.gasp_behavior(12), Some(Behavior(0x0005)));
+}
@@ -0,0 +1,115 @@
+use ttf_parser::gdef::Table;
+use ttf_parser::gpos::Device;
+use ttf_parser::{Face, GlyphId, RawFace, RawFaceTables, Tag};
+use crate::{convert, demo_face_tables, DEMO_FONT, Unit::*};
+
+fn gdef_data() -> Vec<u8> {
+    convert(&[
+        UInt32(0x00010000), // version
+        UInt16(0), // offset to glyph class definition
+        UInt16(12), // offset to attach list
+        UInt16(30), // offset to ligature caret list
+        UInt16(0), // offset to mark attach class definition
+
+        // Attach list.
+        UInt16(6), // offset to coverage
+        UInt16(1), // glyph count
+        UInt16(12), // offset to attach point [0]
+        // Coverage.
+        UInt16(1), // format
+        UInt16(1), // glyph count
+        UInt16(5), // glyph [0]
+        // Attach point [0].
+        UInt16(2), // point count
+        UInt16(3), // point index [0]
+        UInt16(7), // point index [1]
+
+        // Ligature caret list.
+        UInt16(6), // offset to coverage
+        UInt16(1), // ligature glyph count
+        UInt16(12), // offset to ligature glyph [0]
+        // Coverage.
+        UInt16(1), // format
+        UInt16(1), // glyph count
+        UInt16(1), // glyph [0]
+        // Ligature glyph [0].
+        UInt16(3), // caret count
+        UInt16(8), // offset to caret value [0]
+        UInt16(12), // offset to caret value [1]
+        UInt16(16), // offset to caret value [2]
+        // Caret value [0].
+        UInt16(1), // format
+        Int16(300), // coordinate
+        // Caret value [1].
+        UInt16(2), // format
+        UInt16(1), // contour point index
+        // Caret value [2].
+        UInt16(3), // format
+        Int16(500), // coordinate
+        UInt16(6), // offset to device
+        // Device.
+        UInt16(0), // outer index
+        UInt16(2), // inner index
+        UInt16(0x8000), // format
+    ])
+}
+
+#[test]
+fn attachment_points() {
+    let data = gdef_data();
+    let table = Table::parse(&data).unwrap();
+    let points: Vec<u16> = table.glyph_attachment_points(GlyphId(5)).unwrap().into_iter().collect();
+    assert_eq!(points, [3, 7]);
+    assert!(table.glyph_attachment_points(GlyphId(1)).is_none());
+}
+
+#[test]
+fn ligature_carets() {
+    let data = gdef_data();
+    let table = Table::parse(&data).unwrap();
+    assert!(table.glyph_ligature_carets(GlyphId(5)).is_none());
+
+    let carets = table.glyph_ligature_carets(GlyphId(1)).unwrap();
+    assert_eq!(carets.len(), 3);
+
+    let caret = carets.get(0).unwrap();
+    assert_eq!(caret.coordinate, 300);
+    assert_eq!(caret.contour_point, None);
+    assert!(caret.device.is_none());
+
+    let caret = carets.get(1).unwrap();
+    assert_eq!(caret.contour_point, Some(1));
+
+    let caret = carets.get(2).unwrap();
+    assert_eq!(caret.coordinate, 500);
+    assert!(matches!(
+        caret.device,
+        Some(Device::Variation(d)) if d.outer_index == 0 && d.inner_index == 2
+    ));
+
+    assert!(carets.get(3).is_none());
+    assert_eq!(carets.into_iter().count(), 3);
+}
+
+#[test]
+fn resolve_ligature_carets() {
+    let data = gdef_data();
+    let raw_face = RawFace::parse(DEMO_FONT, 0).unwrap();
+    let tables = RawFaceTables {
+        loca: raw_face.table(Tag::from_bytes(b"loca")),
+        glyf: raw_face.table(Tag::from_bytes(b"glyf")),
+        gdef: Some(&data),
+        ..demo_face_tables()
+    };
+    let face = Face::from_raw_tables(tables).unwrap();
+
+    let carets = face.tables().gdef.unwrap().glyph_ligature_carets(GlyphId(1)).unwrap();
+    let positions: Vec<f32> = carets
+        .into_iter()
+        .map(|caret| face.glyph_ligature_caret(GlyphId(1), &caret, false))
+        .collect();
+    assert_eq!(positions, [300.0, 369.0, 500.0]);
+
+    let caret = carets.get(1).unwrap();
+    assert_eq!(face.glyph_ligature_caret(GlyphId(1), &caret, true), 267.0);
+}
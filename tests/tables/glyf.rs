@@ -45,3 +45,343 @@ fn endless_loop() {
     let face = ttf_parser::Face::parse(data, 0).unwrap();
     let _ = face.outline_glyph(ttf_parser::GlyphId(0), &mut Builder(String::new()));
 }
+
+mod contour_points {
+    use ttf_parser::{gpos, Face, GlyphId, RawFaceTables, Tag};
+    use crate::{convert, demo_face_tables, DEMO_FONT, Unit::*};
+
+    fn glyf_data() -> Vec<u8> {
+        convert(&[
+            // Glyph [0]: a triangle.
+            Int16(1), // number of contours
+            Int16(0), Int16(0), Int16(100), Int16(100), // bbox
+            UInt16(2), // end point [0]
+            UInt16(0), // instructions length
+            UInt8(1), UInt8(1), UInt8(1), // flags: on curve
+            Int16(0), Int16(100), Int16(-50), // x coordinates
+            Int16(0), Int16(0), Int16(100), // y coordinates
+            UInt8(0), // padding
+
+            // Glyph [1]: two triangles.
+            Int16(-1), // number of contours
+            Int16(0), Int16(0), Int16(300), Int16(400), // bbox
+            UInt16(0x0023), // flags: words, xy values, more components
+            UInt16(0), // glyph ID
+            Int16(200), Int16(0), // x, y offset
+            UInt16(0x0003), // flags: words, xy values
+            UInt16(0), // glyph ID
+            Int16(0), Int16(300), // x, y offset
+        ])
+    }
+
+    fn loca_data() -> Vec<u8> {
+        convert(&[UInt16(0), UInt16(15), UInt16(28)])
+    }
+
+    fn maxp_data() -> Vec<u8> {
+        convert(&[
+            UInt32(0x00005000), // version
+            UInt16(2), // number of glyphs
+        ])
+    }
+
+    fn fvar_data() -> Vec<u8> {
+        convert(&[
+            UInt32(0x00010000), // version
+            UInt16(16), // offset to axes array
+            UInt16(2), // reserved
+            UInt16(1), // axis count
+            UInt16(20), // axis size
+            UInt16(0), // instance count
+            UInt16(8), // instance size
+            Raw(b"wght"), Fixed(100.0), Fixed(400.0), Fixed(900.0), UInt16(0), UInt16(256),
+        ])
+    }
+
+    fn gvar_data() -> Vec<u8> {
+        convert(&[
+            UInt32(0x00010000), // version
+            UInt16(1), // axis count
+            UInt16(0), // shared tuple count
+            UInt32(26), // offset to shared tuples
+            UInt16(2), // glyph count
+            UInt16(0), // flags
+            UInt32(26), // offset to glyph variation data array
+            UInt16(0), UInt16(13), UInt16(13), // glyph variation data offsets / 2
+
+            // Glyph [0] variation data.
+            UInt16(1), // tuple variation count
+            UInt16(10), // offset to serialized data
+            UInt16(16), // variation data size
+            UInt16(0x8000), // tuple index: embedded peak tuple
+            Int16(0x4000), // peak: 1.0
+            // X deltas for 3 points and 4 phantom points.
+            UInt8(6), Int8(10), Int8(0), Int8(0), Int8(0), Int8(0), Int8(0), Int8(0),
+            // Y deltas for 3 points and 4 phantom points.
+            UInt8(6), Int8(0), Int8(20), Int8(0), Int8(0), Int8(0), Int8(0), Int8(0),
+        ])
+    }
+
+    struct Data {
+        glyf: Vec<u8>,
+        loca: Vec<u8>,
+        maxp: Vec<u8>,
+        fvar: Vec<u8>,
+        gvar: Vec<u8>,
+    }
+
+    impl Data {
+        fn new() -> Self {
+            Data {
+                glyf: glyf_data(),
+                loca: loca_data(),
+                maxp: maxp_data(),
+                fvar: fvar_data(),
+                gvar: gvar_data(),
+            }
+        }
+
+        fn face(&self, variable: bool) -> Face<'_> {
+            let tables = RawFaceTables {
+                maxp: &self.maxp,
+                glyf: Some(&self.glyf),
+                loca: Some(&self.loca),
+                fvar: if variable { Some(&self.fvar) } else { None },
+                gvar: if variable { Some(&self.gvar) } else { None },
+                ..demo_face_tables()
+            };
+            Face::from_raw_tables(tables).unwrap()
+        }
+    }
+
+    fn point(face: &Face, glyph_id: u16, index: u16) -> Option<(f32, f32)> {
+        face.glyph_contour_point(GlyphId(glyph_id), index).map(|p| (p.x, p.y))
+    }
+
+    #[test]
+    fn demo_font() {
+        let face = Face::parse(DEMO_FONT, 0).unwrap();
+        assert_eq!(point(&face, 1, 0), Some((173.0, 267.0)));
+        assert_eq!(point(&face, 1, 2), Some((270.0, 587.0)));
+        assert_eq!(point(&face, 1, 3), Some((6.0, 0.0)));
+        assert_eq!(point(&face, 1, 10), Some((85.0, 0.0)));
+        assert_eq!(point(&face, 1, 11), None);
+    }
+
+    #[test]
+    fn composite_glyph() {
+        let data = Data::new();
+        let face = data.face(false);
+        assert_eq!(point(&face, 0, 2), Some((50.0, 100.0)));
+        assert_eq!(point(&face, 1, 1), Some((300.0, 0.0)));
+        assert_eq!(point(&face, 1, 3), Some((0.0, 300.0)));
+        assert_eq!(point(&face, 1, 5), Some((50.0, 400.0)));
+        assert_eq!(point(&face, 1, 6), None);
+    }
+
+    #[test]
+    fn variable_glyph() {
+        let data = Data::new();
+        let mut face = data.face(true);
+        assert_eq!(point(&face, 0, 0), Some((0.0, 0.0)));
+        assert_eq!(point(&face, 1, 4), Some((100.0, 300.0)));
+
+        face.set_variation(Tag::from_bytes(b"wght"), 900.0).unwrap();
+        assert_eq!(point(&face, 0, 0), Some((10.0, 0.0)));
+        assert_eq!(point(&face, 0, 1), Some((100.0, 20.0)));
+        assert_eq!(point(&face, 0, 2), Some((50.0, 100.0)));
+        assert_eq!(point(&face, 1, 0), Some((210.0, 0.0)));
+        assert_eq!(point(&face, 1, 4), Some((100.0, 320.0)));
+
+        face.set_variation(Tag::from_bytes(b"wght"), 650.0).unwrap();
+        assert_eq!(point(&face, 0, 0), Some((5.0, 0.0)));
+    }
+
+    #[test]
+    fn anchor() {
+        let data = Data::new();
+        let mut face = data.face(true);
+        face.set_variation(Tag::from_bytes(b"wght"), 900.0).unwrap();
+
+        let anchor = gpos::Anchor {
+            x: 50,
+            y: 60,
+            contour_point: None,
+            x_device: None,
+            y_device: None,
+        };
+        let p = face.glyph_anchor(GlyphId(1), &anchor);
+        assert_eq!((p.x, p.y), (50.0, 60.0));
+
+        let anchor = gpos::Anchor { contour_point: Some(4), ..anchor };
+        let p = face.glyph_anchor(GlyphId(1), &anchor);
+        assert_eq!((p.x, p.y), (100.0, 320.0));
+
+        // Fallback to coordinates when a point is missing.
+        let anchor = gpos::Anchor { contour_point: Some(100), ..anchor };
+        let p = face.glyph_anchor(GlyphId(1), &anchor);
+        assert_eq!((p.x, p.y), (50.0, 60.0));
+    }
+}
+
+mod instructions {
+    use ttf_parser::{Face, GlyphId, RawFaceTables};
+    use crate::{convert, demo_face_tables, Unit::*};
+
+    fn glyf_data() -> Vec<u8> {
+        convert(&[
+            // Glyph [0]: a triangle with instructions.
+            Int16(1), // number of contours
+            Int16(0), Int16(0), Int16(100), Int16(100), // bbox
+            UInt16(2), // end point [0]
+            UInt16(3), // instructions length
+            UInt8(0xB0), UInt8(5), UInt8(0x2F), // PUSHB[0] 5, MDAP[r]
+            UInt8(1), UInt8(1), UInt8(1), // flags: on curve
+            Int16(0), Int16(100), Int16(-50), // x coordinates
+            Int16(0), Int16(0), Int16(100), // y coordinates
+
+            // Glyph [1]: a composite glyph with instructions.
+            Int16(-1), // number of contours
+            Int16(0), Int16(0), Int16(100), Int16(100), // bbox
+            UInt16(0x0103), // flags: words, xy values, have instructions
+            UInt16(0), // glyph ID
+            Int16(0), Int16(0), // x, y offset
+            UInt16(1), // instructions length
+            UInt8(0x01), // SVTCA[x]
+            UInt8(0), // padding
+
+            // Glyph [2]: a triangle without instructions.
+            Int16(1), // number of contours
+            Int16(0), Int16(0), Int16(100), Int16(100), // bbox
+            UInt16(2), // end point [0]
+            UInt16(0), // instructions length
+            UInt8(1), UInt8(1), UInt8(1), // flags: on curve
+            Int16(0), Int16(100), Int16(-50), // x coordinates
+            Int16(0), Int16(0), Int16(100), // y coordinates
+            UInt8(0), // padding
+
+            // Glyph [3] is empty.
+        ])
+    }
+
+    #[test]
+    fn glyph_instructions() {
+        let glyf = glyf_data();
+        let loca = convert(&[UInt16(0), UInt16(16), UInt16(27), UInt16(42), UInt16(42)]);
+        let maxp = convert(&[
+            UInt32(0x00005000), // version
+            UInt16(4), // number of glyphs
+        ]);
+
+        let tables = RawFaceTables {
+            maxp: &maxp,
+            glyf: Some(&glyf),
+            loca: Some(&loca),
+            ..demo_face_tables()
+        };
+        let face = Face::from_raw_tables(tables).unwrap();
+
+        let table = face.tables().glyf.unwrap();
+        assert_eq!(table.glyph_instructions(GlyphId(0)), Some(&[0xB0, 5, 0x2F][..]));
+        assert_eq!(table.glyph_instructions(GlyphId(1)), Some(&[0x01][..]));
+        assert_eq!(table.glyph_instructions(GlyphId(2)), None);
+        assert_eq!(table.glyph_instructions(GlyphId(3)), None);
+        assert_eq!(table.glyph_instructions(GlyphId(4)), None);
+    }
+}
+
+mod raw_structure {
+    use ttf_parser::glyf::GlyphPoint;
+    use ttf_parser::{Face, GlyphId, RawFaceTables, Transform};
+    use crate::{convert, demo_face_tables, Unit::*};
+
+    fn glyf_data() -> Vec<u8> {
+        convert(&[
+            // Glyph [0]: two contours.
+            Int16(2), // number of contours
+            Int16(0), Int16(0), Int16(100), Int16(100), // bbox
+            UInt16(1), UInt16(3), // end points
+            UInt16(0), // instructions length
+            UInt8(1), UInt8(0), UInt8(1), UInt8(1), // flags: on, off, on, on curve
+            Int16(0), Int16(100), Int16(-100), Int16(50), // x coordinates
+            Int16(0), Int16(100), Int16(-50), Int16(0), // y coordinates
+
+            // Glyph [1]: a single point.
+            Int16(1), // number of contours
+            Int16(10), Int16(20), Int16(10), Int16(20), // bbox
+            UInt16(0), // end point [0]
+            UInt16(0), // instructions length
+            UInt8(1), // flags: on curve
+            Int16(10), // x coordinates
+            Int16(20), // y coordinates
+            UInt8(0), // padding
+
+            // Glyph [2]: a composite glyph.
+            Int16(-1), // number of contours
+            Int16(0), Int16(0), Int16(100), Int16(100), // bbox
+            UInt16(0x022B), // flags: words, xy values, scale, more components, use my metrics
+            UInt16(0), // glyph ID
+            Int16(10), Int16(-20), // x, y offset
+            Int16(0x2000), // scale: 0.5
+            UInt16(0x0400), // flags: overlap compound
+            UInt16(1), // glyph ID
+            UInt8(3), UInt8(0), // parent and child points
+
+            // Glyph [3] is empty.
+        ])
+    }
+
+    #[test]
+    fn points_and_components() {
+        let glyf = glyf_data();
+        let loca = convert(&[UInt16(0), UInt16(18), UInt16(28), UInt16(41), UInt16(41)]);
+        let maxp = convert(&[
+            UInt32(0x00005000), // version
+            UInt16(4), // number of glyphs
+        ]);
+
+        let tables = RawFaceTables {
+            maxp: &maxp,
+            glyf: Some(&glyf),
+            loca: Some(&loca),
+            ..demo_face_tables()
+        };
+        let face = Face::from_raw_tables(tables).unwrap();
+        let table = face.tables().glyf.unwrap();
+
+        let point = |x, y, on_curve_point, last_point| GlyphPoint { x, y, on_curve_point, last_point };
+
+        let end_points: Vec<u16> = table.glyph_contour_end_points(GlyphId(0)).unwrap().into_iter().collect();
+        assert_eq!(end_points, [1, 3]);
+        let points: Vec<GlyphPoint> = table.glyph_points(GlyphId(0)).unwrap().collect();
+        assert_eq!(points, [
+            point(0, 0, true, false),
+            point(100, 100, false, true),
+            point(0, 50, true, false),
+            point(50, 50, true, true),
+        ]);
+        assert!(table.glyph_components(GlyphId(0)).is_none());
+
+        // A single point contour is not skipped.
+        let points: Vec<GlyphPoint> = table.glyph_points(GlyphId(1)).unwrap().collect();
+        assert_eq!(points, [point(10, 20, true, true)]);
+
+        assert!(table.glyph_points(GlyphId(2)).is_none());
+        assert!(table.glyph_contour_end_points(GlyphId(2)).is_none());
+        let components: Vec<_> = table.glyph_components(GlyphId(2)).unwrap().collect();
+        assert_eq!(components.len(), 2);
+        assert_eq!(components[0].glyph_id, GlyphId(0));
+        assert!(components[0].transform == Transform::new(0.5, 0.0, 0.0, 0.5, 10.0, -20.0));
+        assert_eq!(components[0].matching_points, None);
+        assert!(components[0].flags.use_my_metrics());
+        assert!(!components[0].flags.overlap_compound());
+        assert_eq!(components[1].glyph_id, GlyphId(1));
+        assert!(components[1].transform.is_default());
+        assert_eq!(components[1].matching_points, Some((3, 0)));
+        assert!(!components[1].flags.use_my_metrics());
+        assert!(components[1].flags.overlap_compound());
+
+        assert!(table.glyph_points(GlyphId(3)).is_none());
+        assert!(table.glyph_components(GlyphId(3)).is_none());
+    }
+}
@@ -0,0 +1,171 @@
+use ttf_parser::gvar::PointDelta;
+use ttf_parser::{Face, GlyphId, NormalizedCoordinate, RawFaceTables, RegionAxisCoordinates, Tag};
+use crate::{convert, demo_face_tables, Unit::*};
+
+fn glyf_data() -> Vec<u8> {
+    convert(&[
+        // Glyph [0]: a square.
+        Int16(1), // number of contours
+        Int16(0), Int16(0), Int16(100), Int16(100), // bbox
+        UInt16(3), // end point [0]
+        UInt16(0), // instructions length
+        UInt8(1), UInt8(1), UInt8(1), UInt8(1), // flags: on curve
+        Int16(0), Int16(100), Int16(0), Int16(-100), // x coordinates
+        Int16(0), Int16(0), Int16(100), Int16(0), // y coordinates
+    ])
+}
+
+fn fvar_data() -> Vec<u8> {
+    convert(&[
+        UInt32(0x00010000), // version
+        UInt16(16), // offset to axes array
+        UInt16(2), // reserved
+        UInt16(1), // axis count
+        UInt16(20), // axis size
+        UInt16(0), // instance count
+        UInt16(8), // instance size
+        Raw(b"wght"), Fixed(100.0), Fixed(400.0), Fixed(900.0), UInt16(0), UInt16(256),
+    ])
+}
+
+fn gvar_data() -> Vec<u8> {
+    convert(&[
+        UInt32(0x00010000), // version
+        UInt16(1), // axis count
+        UInt16(0), // shared tuple count
+        UInt32(24), // offset to shared tuples
+        UInt16(1), // glyph count
+        UInt16(0), // flags
+        UInt32(24), // offset to glyph variation data array
+        UInt16(0), UInt16(18), // glyph variation data offsets / 2
+
+        // Glyph [0] variation data.
+        UInt16(2), // tuple variation count
+        UInt16(20), // offset to serialized data
+
+        // Tuple variation header [0].
+        UInt16(8), // variation data size
+        UInt16(0xA000), // tuple index: embedded peak tuple, private point numbers
+        Int16(16384), // peak: 1.0
+
+        // Tuple variation header [1].
+        UInt16(8), // variation data size
+        UInt16(0xE000), // tuple index: embedded peak tuple, intermediate region, private point numbers
+        Int16(8192), // peak: 0.5
+        Int16(0), // start: 0.0
+        Int16(16384), // end: 1.0
+
+        // Tuple data [0].
+        UInt8(2), // point numbers count
+        UInt8(0x01), UInt8(0), UInt8(2), // control, points 0 and 2
+        UInt8(0x01), Int8(10), Int8(30), // X deltas
+        UInt8(0x81), // Y deltas: zeros
+
+        // Tuple data [1].
+        UInt8(0), // all points
+        UInt8(0x87), // X deltas for 4 points and 4 phantom points: zeros
+        UInt8(0x03), Int8(4), Int8(4), Int8(4), Int8(4), // Y deltas for 4 points
+        UInt8(0x83), // Y deltas for 4 phantom points: zeros
+    ])
+}
+
+fn deltas(face: &Face, coord: f32) -> Vec<(f32, f32)> {
+    let gvar = face.tables().gvar.unwrap();
+    let glyf = face.tables().glyf.unwrap();
+    let mut deltas = Vec::new();
+    gvar.glyph_deltas(glyf, &[NormalizedCoordinate::from(coord)], GlyphId(0), |p| {
+        deltas.push((p.x, p.y))
+    }).unwrap();
+    deltas
+}
+
+fn maxp_data() -> Vec<u8> {
+    convert(&[
+        UInt32(0x00005000), // version
+        UInt16(1), // number of glyphs
+    ])
+}
+
+fn face<'a>(glyf: &'a [u8], loca: &'a [u8], maxp: &'a [u8], fvar: &'a [u8], gvar: &'a [u8]) -> Face<'a> {
+    let tables = RawFaceTables {
+        maxp,
+        glyf: Some(glyf),
+        loca: Some(loca),
+        fvar: Some(fvar),
+        gvar: Some(gvar),
+        ..demo_face_tables()
+    };
+    Face::from_raw_tables(tables).unwrap()
+}
+
+#[test]
+fn tuple_variations() {
+    let glyf = glyf_data();
+    let loca = convert(&[UInt16(0), UInt16(17)]);
+    let maxp = maxp_data();
+    let fvar = fvar_data();
+    let gvar = gvar_data();
+    let mut face = face(&glyf, &loca, &maxp, &fvar, &gvar);
+
+    let glyf_table = face.tables().glyf.unwrap();
+    let gvar_table = face.tables().gvar.unwrap();
+    let tuples: Vec<_> = gvar_table.glyph_tuple_variations(glyf_table, GlyphId(0)).unwrap().collect();
+    assert_eq!(tuples.len(), 2);
+
+    let delta = |point_index, x, y| PointDelta { point_index, x, y };
+
+    assert!(!tuples[0].has_intermediate_region());
+    assert_eq!(tuples[0].axis_region(0), Some(RegionAxisCoordinates { start_coord: 0, peak_coord: 16384, end_coord: 16384 }));
+    assert_eq!(tuples[0].axis_region(1), None);
+    assert_eq!(tuples[0].scalar(&[NormalizedCoordinate::from(0.5)]), Some(0.5));
+    assert!(!tuples[0].has_all_points());
+    let points: Vec<PointDelta> = tuples[0].deltas().collect();
+    assert_eq!(points, [delta(0, 10.0, 0.0), delta(2, 30.0, 0.0)]);
+
+    assert!(tuples[1].has_intermediate_region());
+    assert_eq!(tuples[1].axis_region(0), Some(RegionAxisCoordinates { start_coord: 0, peak_coord: 8192, end_coord: 16384 }));
+    assert_eq!(tuples[1].scalar(&[NormalizedCoordinate::from(0.5)]), Some(1.0));
+    assert_eq!(tuples[1].scalar(&[NormalizedCoordinate::from(1.0)]), Some(0.0));
+    assert!(tuples[1].has_all_points());
+    let points: Vec<PointDelta> = tuples[1].deltas().collect();
+    assert_eq!(points, [
+        delta(0, 0.0, 4.0), delta(1, 0.0, 4.0), delta(2, 0.0, 4.0), delta(3, 0.0, 4.0),
+        delta(4, 0.0, 0.0), delta(5, 0.0, 0.0), delta(6, 0.0, 0.0), delta(7, 0.0, 0.0),
+    ]);
+
+    // Deltas of points 1 and 3 are inferred.
+    assert_eq!(deltas(&face, 0.0), [(0.0, 0.0); 8]);
+    assert_eq!(deltas(&face, 1.0), [
+        (10.0, 0.0), (30.0, 0.0), (30.0, 0.0), (10.0, 0.0),
+        (0.0, 0.0), (0.0, 0.0), (0.0, 0.0), (0.0, 0.0),
+    ]);
+    assert_eq!(deltas(&face, 0.5), [
+        (5.0, 4.0), (15.0, 4.0), (15.0, 4.0), (5.0, 4.0),
+        (0.0, 0.0), (0.0, 0.0), (0.0, 0.0), (0.0, 0.0),
+    ]);
+
+    // Matches the outline.
+    face.set_variation(Tag::from_bytes(b"wght"), 650.0).unwrap();
+    let p = face.glyph_contour_point(GlyphId(0), 1).unwrap();
+    assert_eq!((p.x, p.y), (115.0, 4.0));
+}
+
+#[test]
+fn wrong_coordinates_count() {
+    let glyf = glyf_data();
+    let loca = convert(&[UInt16(0), UInt16(17)]);
+    let maxp = maxp_data();
+    let fvar = fvar_data();
+    let gvar = gvar_data();
+    let face = face(&glyf, &loca, &maxp, &fvar, &gvar);
+
+    let glyf_table = face.tables().glyf.unwrap();
+    let gvar_table = face.tables().gvar.unwrap();
+    let tuple = gvar_table.glyph_tuple_variations(glyf_table, GlyphId(0)).unwrap().next().unwrap();
+    let coords = [NormalizedCoordinate::from(0.5); 2];
+    assert_eq!(tuple.scalar(&[]), None);
+    assert_eq!(tuple.scalar(&coords), None);
+
+    assert!(gvar_table.glyph_deltas(glyf_table, &[], GlyphId(0), |_| {}).is_none());
+    assert!(gvar_table.glyph_deltas(glyf_table, &coords, GlyphId(0), |_| {}).is_none());
+}
@@ -0,0 +1,78 @@
+use std::num::NonZeroU16;
+use ttf_parser::hdmx::Table;
+use ttf_parser::{Face, GlyphId, RawFaceTables};
+use crate::{convert, demo_face_tables, Unit::*};
+
+#[test]
+fn parse() {
+    let data = convert(&[
+        UInt16(0), // version
+        Int16(2), // number of records
+        Int32(8), // size of a record
+        // Record [0].
+        UInt8(10), // pixel size
+        UInt8(7), // max width
+        UInt8(5), UInt8(7), UInt8(6), // widths
+        UInt8(0), UInt8(0), UInt8(0), // padding
+        // Record [1].
+        UInt8(12), // pixel size
+        UInt8(9), // max width
+        UInt8(6), UInt8(9), UInt8(7), // widths
+        UInt8(0), UInt8(0), UInt8(0), // padding
+    ]);
+
+    let table = Table::parse(NonZeroU16::new(3).unwrap(), &data).unwrap();
+    assert_eq!(table.records.len(), 2);
+
+    let record = table.records.get(1).unwrap();
+    assert_eq!(record.pixel_size, 12);
+    assert_eq!(record.max_width, 9);
+    assert_eq!(record.widths.len(), 3);
+    assert!(table.records.get(2).is_none());
+
+    assert_eq!(table.glyph_advance(GlyphId(1), 10), Some(7));
+    assert_eq!(table.glyph_advance(GlyphId(2), 12), Some(7));
+    assert_eq!(table.glyph_advance(GlyphId(3), 12), None);
+    assert_eq!(table.glyph_advance(GlyphId(0), 11), None);
+}
+
+#[test]
+fn record_too_small() {
+    let data = convert(&[
+        UInt16(0), // version
+        Int16(1), // number of records
+        Int32(4), // size of a record
+        UInt8(10), // pixel size
+        UInt8(7), // max width
+        UInt8(5), UInt8(7), // widths
+    ]);
+
+    assert!(Table::parse(NonZeroU16::new(3).unwrap(), &data).is_none());
+}
+
+#[test]
+fn face_advance() {
+    let tables = demo_face_tables();
+
+    let face = Face::from_raw_tables(tables.clone()).unwrap();
+    assert_eq!(face.glyph_hor_device_advance(GlyphId(1), 16), None);
+
+    let number_of_glyphs = face.number_of_glyphs();
+    let mut units = vec![
+        UInt16(0), // version
+        Int16(1), // number of records
+        Int32(i32::from(number_of_glyphs) + 2), // size of a record
+        UInt8(16), // pixel size
+        UInt8(9), // max width
+    ];
+    for i in 0..number_of_glyphs {
+        units.push(UInt8(i as u8));
+    }
+
+    let data = convert(&units);
+    let tables = RawFaceTables { hdmx: Some(&data), ..tables };
+    let face = Face::from_raw_tables(tables).unwrap();
+    assert_eq!(face.glyph_hor_device_advance(GlyphId(1), 16), Some(1));
+    assert_eq!(face.glyph_hor_device_advance(GlyphId(1), 17), None);
+    assert_eq!(face.glyph_hor_device_advance(GlyphId(1), 272), None);
+}
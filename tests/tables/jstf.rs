@@ -0,0 +1,114 @@
+use ttf_parser::gpos::{PositioningSubtable, SingleAdjustment};
+use ttf_parser::jstf::Table;
+use ttf_parser::{GlyphId, Tag};
+use crate::{convert, Unit::*};
+
+fn jstf_data() -> Vec<u8> {
+    convert(&[
+        UInt16(1), // major version
+        UInt16(0), // minor version
+        UInt16(1), // script count
+        Raw(b"arab"), UInt16(12), // tag, offset
+
+        // Script.
+        UInt16(12), // offset to extender glyphs
+        UInt16(16), // offset to default language system
+        UInt16(1), // language system count
+        Raw(b"URD "), UInt16(76), // tag, offset
+        // Extender glyphs.
+        UInt16(1), // count
+        UInt16(7), // glyph [0]
+
+        // Default language system.
+        UInt16(1), // priority count
+        UInt16(4), // offset to priority [0]
+        // Priority [0].
+        UInt16(20), // offset to GSUB shrinkage enable
+        UInt16(0), // offset to GSUB shrinkage disable
+        UInt16(0), // offset to GPOS shrinkage enable
+        UInt16(0), // offset to GPOS shrinkage disable
+        UInt16(26), // offset to shrinkage max
+        UInt16(0), // offset to GSUB extension enable
+        UInt16(0), // offset to GSUB extension disable
+        UInt16(52), // offset to GPOS extension enable
+        UInt16(0), // offset to GPOS extension disable
+        UInt16(0), // offset to extension max
+        // GSUB shrinkage enable.
+        UInt16(2), // lookup count
+        UInt16(1), // lookup index [0]
+        UInt16(2), // lookup index [1]
+        // Shrinkage max.
+        UInt16(1), // lookup count
+        UInt16(4), // offset to lookup [0]
+        // Lookup [0].
+        UInt16(1), // type: single adjustment
+        UInt16(0), // flags
+        UInt16(1), // subtable count
+        UInt16(8), // offset to subtable [0]
+        // Subtable [0].
+        UInt16(1), // format
+        UInt16(8), // offset to coverage
+        UInt16(0x0004), // value format: x advance
+        Int16(-50), // x advance
+        // Coverage.
+        UInt16(1), // format
+        UInt16(1), // glyph count
+        UInt16(3), // glyph [0]
+        // GPOS extension enable.
+        UInt16(1), // lookup count
+        UInt16(5), // lookup index [0]
+
+        // Language system.
+        UInt16(0), // priority count
+    ])
+}
+
+#[test]
+fn parse() {
+    let data = jstf_data();
+    let table = Table::parse(&data).unwrap();
+    assert_eq!(table.scripts.len(), 1);
+
+    let script = table.scripts.find(Tag::from_bytes(b"arab")).unwrap();
+    let glyphs: Vec<GlyphId> = script.extender_glyphs.into_iter().collect();
+    assert_eq!(glyphs, [GlyphId(7)]);
+
+    let language = script.languages.find(Tag::from_bytes(b"URD ")).unwrap();
+    assert!(language.priorities.is_empty());
+
+    let language = script.default_language.unwrap();
+    assert_eq!(language.tag, Tag::from_bytes(b"dflt"));
+    assert_eq!(language.priorities.len(), 1);
+
+    let priority = language.priorities.get(0).unwrap();
+    let lookups: Vec<u16> = priority.shrinkage.gsub_enable.into_iter().collect();
+    assert_eq!(lookups, [1, 2]);
+    assert!(priority.shrinkage.gsub_disable.is_empty());
+    assert!(priority.shrinkage.gpos_enable.is_empty());
+    assert!(priority.shrinkage.gpos_disable.is_empty());
+    assert_eq!(priority.shrinkage.max.len(), 1);
+
+    let lookup = priority.shrinkage.max.get(0).unwrap();
+    match lookup.subtables.get::<PositioningSubtable>(0) {
+        Some(PositioningSubtable::Single(SingleAdjustment::Format1 { coverage, value })) => {
+            assert!(coverage.contains(GlyphId(3)));
+            assert_eq!(value.x_advance, -50);
+        }
+        _ => panic!("invalid subtable"),
+    }
+
+    let lookups: Vec<u16> = priority.extension.gpos_enable.into_iter().collect();
+    assert_eq!(lookups, [5]);
+    assert!(priority.extension.gsub_enable.is_empty());
+    assert!(priority.extension.max.is_empty());
+}
+
+#[test]
+fn invalid_version() {
+    let data = convert(&[
+        UInt16(2), // major version
+        UInt16(0), // minor version
+        UInt16(0), // script count
+    ]);
+    assert!(Table::parse(&data).is_none());
+}
@@ -0,0 +1,28 @@
+use ttf_parser::ltsh::Table;
+use ttf_parser::GlyphId;
+use crate::{convert, Unit::*};
+
+#[test]
+fn parse() {
+    let data = convert(&[
+        UInt16(0), // version
+        UInt16(3), // number of glyphs
+        UInt8(1), UInt8(24), UInt8(255), // thresholds
+    ]);
+
+    let table = Table::parse(&data).unwrap();
+    assert_eq!(table.y_pels.len(), 3);
+    assert_eq!(table.glyph_threshold(GlyphId(0)), Some(1));
+    assert_eq!(table.glyph_threshold(GlyphId(1)), Some(24));
+    assert_eq!(table.glyph_threshold(GlyphId(2)), Some(255));
+    assert_eq!(table.glyph_threshold(GlyphId(3)), None);
+}
+
+#[test]
+fn invalid_version() {
+    let data = convert(&[
+        UInt16(1), // version
+        UInt16(0), // number of glyphs
+    ]);
+    assert!(Table::parse(&data).is_none());
+}
@@ -1,16 +1,45 @@
 #[rustfmt::skip] mod aat;
 #[rustfmt::skip] mod ankr;
+#[rustfmt::skip] mod avar;
+#[rustfmt::skip] mod base;
 #[rustfmt::skip] mod cff1;
+#[rustfmt::skip] mod cff2;
 #[rustfmt::skip] mod cmap;
 #[rustfmt::skip] mod colr;
+#[rustfmt::skip] mod cvar;
 #[rustfmt::skip] mod feat;
+#[rustfmt::skip] mod fvar;
+#[rustfmt::skip] mod gasp;
+#[rustfmt::skip] mod gdef;
 #[rustfmt::skip] mod glyf;
+#[rustfmt::skip] mod gvar;
+#[rustfmt::skip] mod hdmx;
 #[rustfmt::skip] mod hmtx;
+#[rustfmt::skip] mod jstf;
+#[rustfmt::skip] mod ltsh;
 #[rustfmt::skip] mod maxp;
+#[rustfmt::skip] mod meta;
+#[rustfmt::skip] mod opentype_layout;
 #[rustfmt::skip] mod sbix;
+#[rustfmt::skip] mod stat;
 #[rustfmt::skip] mod trak;
+#[rustfmt::skip] mod vdmx;
 
-use ttf_parser::{fonts_in_collection, Face, FaceParsingError};
+use ttf_parser::{fonts_in_collection, Face, FaceParsingError, RawFace, RawFaceTables, Tag};
+
+pub static DEMO_FONT: &[u8] = include_bytes!("../fonts/demo.ttf");
+
+/// Returns the `head`, `hhea` and `maxp` tables of the demo font,
+/// so tests can build a `Face` from custom tables.
+pub fn demo_face_tables() -> RawFaceTables<'static> {
+    let raw_face = RawFace::parse(DEMO_FONT, 0).unwrap();
+    RawFaceTables {
+        head: raw_face.table(Tag::from_bytes(b"head")).unwrap(),
+        hhea: raw_face.table(Tag::from_bytes(b"hhea")).unwrap(),
+        maxp: raw_face.table(Tag::from_bytes(b"maxp")).unwrap(),
+        ..RawFaceTables::default()
+    }
+}
 
 #[allow(dead_code)]
 #[derive(Clone, Copy)]
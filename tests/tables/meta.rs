@@ -0,0 +1,65 @@
+use ttf_parser::meta::Table;
+use ttf_parser::Tag;
+use crate::{convert, Unit::*};
+
+#[test]
+fn parse() {
+    let data = convert(&[
+        UInt32(1), // version
+        UInt32(0), // flags
+        UInt32(0), // reserved
+        UInt32(3), // data maps count
+        Raw(b"dlng"), UInt32(52), UInt32(7), // tag, offset, length
+        Raw(b"slng"), UInt32(59), UInt32(22), // tag, offset, length
+        Raw(b"APPL"), UInt32(81), UInt32(2), // tag, offset, length
+        Raw(b"en-La"), Raw(b"tn"),
+        Raw(b"en-Latn, ru ,,Cyrl, ja"),
+        UInt8(1), UInt8(2),
+    ]);
+
+    let table = Table::parse(&data).unwrap();
+    assert_eq!(table.data_maps.len(), 3);
+
+    let map = table.data_maps.get(2).unwrap();
+    assert_eq!(map.tag, Tag::from_bytes(b"APPL"));
+    assert_eq!(map.data, &[1, 2]);
+
+    let map = table.data_maps.find(Tag::from_bytes(b"slng")).unwrap();
+    assert_eq!(map.data, b"en-Latn, ru ,,Cyrl, ja");
+
+    let tags: Vec<&str> = table.design_languages().unwrap().collect();
+    assert_eq!(tags, ["en-Latn"]);
+
+    let tags: Vec<&str> = table.supported_languages().unwrap().collect();
+    assert_eq!(tags, ["en-Latn", "ru", "Cyrl", "ja"]);
+}
+
+#[test]
+fn missing_entries() {
+    let data = convert(&[
+        UInt32(1), // version
+        UInt32(0), // flags
+        UInt32(0), // reserved
+        UInt32(1), // data maps count
+        Raw(b"dlng"), UInt32(28), UInt32(10), // tag, offset, length
+        Raw(b"Latn"),
+    ]);
+
+    let table = Table::parse(&data).unwrap();
+    // Out of bounds data is ignored.
+    assert!(table.data_maps.get(0).is_none());
+    assert!(table.design_languages().is_none());
+    assert!(table.supported_languages().is_none());
+    assert_eq!(table.data_maps.into_iter().count(), 0);
+}
+
+#[test]
+fn invalid_version() {
+    let data = convert(&[
+        UInt32(0), // version
+        UInt32(0), // flags
+        UInt32(0), // reserved
+        UInt32(0), // data maps count
+    ]);
+    assert!(Table::parse(&data).is_none());
+}
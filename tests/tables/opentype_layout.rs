@@ -0,0 +1,164 @@
+use ttf_parser::opentype_layout::{Feature, FeatureParams, RecordListItem, SizeParams};
+use ttf_parser::Tag;
+use crate::{convert, Unit::*};
+
+fn feature_data(params: &[crate::Unit]) -> Vec<u8> {
+    let mut data = convert(&[
+        UInt16(if params.is_empty() { 0 } else { 6 }), // offset to feature params
+        UInt16(1), // lookup index count
+        UInt16(3), // lookup index [0]
+    ]);
+    data.extend_from_slice(&convert(params));
+    data
+}
+
+#[test]
+fn no_params() {
+    let data = feature_data(&[]);
+    let feature = Feature::parse(Tag::from_bytes(b"ss01"), &data).unwrap();
+    assert!(feature.params.is_none());
+    assert_eq!(feature.lookup_indices.get(0), Some(3));
+}
+
+#[test]
+fn size() {
+    let data = feature_data(&[
+        UInt16(100), // design size
+        UInt16(1), // subfamily ID
+        UInt16(256), // subfamily name ID
+        UInt16(80), // range start
+        UInt16(140), // range end
+    ]);
+    let feature = Feature::parse(Tag::from_bytes(b"size"), &data).unwrap();
+    match feature.params {
+        Some(FeatureParams::Size(params)) => assert_eq!(params, SizeParams {
+            design_size: 100,
+            subfamily_id: 1,
+            subfamily_name_id: Some(256),
+            range_start: 80,
+            range_end: 140,
+        }),
+        _ => panic!("invalid params"),
+    }
+}
+
+#[test]
+fn size_without_range() {
+    let data = feature_data(&[
+        UInt16(100), // design size
+        UInt16(0), // subfamily ID
+        UInt16(0), // subfamily name ID
+        UInt16(0), // range start
+        UInt16(0), // range end
+    ]);
+    let feature = Feature::parse(Tag::from_bytes(b"size"), &data).unwrap();
+    match feature.params {
+        Some(FeatureParams::Size(params)) => {
+            assert_eq!(params.design_size, 100);
+            assert_eq!(params.subfamily_name_id, None);
+        }
+        _ => panic!("invalid params"),
+    }
+}
+
+#[test]
+fn size_invalid_range() {
+    let data = feature_data(&[
+        UInt16(100), // design size
+        UInt16(1), // subfamily ID
+        UInt16(256), // subfamily name ID
+        UInt16(120), // range start
+        UInt16(140), // range end
+    ]);
+    let feature = Feature::parse(Tag::from_bytes(b"size"), &data).unwrap();
+    assert!(feature.params.is_none());
+}
+
+#[test]
+fn stylistic_set() {
+    let data = feature_data(&[
+        UInt16(0), // version
+        UInt16(256), // UI name ID
+    ]);
+    let feature = Feature::parse(Tag::from_bytes(b"ss07"), &data).unwrap();
+    match feature.params {
+        Some(FeatureParams::StylisticSet(params)) => assert_eq!(params.ui_name_id, 256),
+        _ => panic!("invalid params"),
+    }
+
+    // Parameters of unknown features are ignored.
+    let feature = Feature::parse(Tag::from_bytes(b"liga"), &data).unwrap();
+    assert!(feature.params.is_none());
+}
+
+#[test]
+fn character_variant() {
+    let data = feature_data(&[
+        UInt16(0), // format
+        UInt16(256), // label name ID
+        UInt16(257), // tooltip name ID
+        UInt16(0), // sample text name ID
+        UInt16(2), // number of named parameters
+        UInt16(258), // first parameter label name ID
+        UInt16(2), // number of characters
+        Raw(&[0x00, 0x00, 0x61]), // character [0]
+        Raw(&[0x01, 0xF6, 0x00]), // character [1]
+    ]);
+    let feature = Feature::parse(Tag::from_bytes(b"cv12"), &data).unwrap();
+    match feature.params {
+        Some(FeatureParams::CharacterVariant(params)) => {
+            assert_eq!(params.label_name_id, Some(256));
+            assert_eq!(params.tooltip_name_id, Some(257));
+            assert_eq!(params.sample_text_name_id, None);
+            assert_eq!(params.param_label_name_id(0), Some(258));
+            assert_eq!(params.param_label_name_id(1), Some(259));
+            assert_eq!(params.param_label_name_id(2), None);
+            let characters: Vec<u32> = params.characters.into_iter().map(|c| c.0).collect();
+            assert_eq!(characters, [0x61, 0x1F600]);
+        }
+        _ => panic!("invalid params"),
+    }
+}
+
+#[test]
+fn out_of_range_tags() {
+    let data = feature_data(&[
+        UInt16(0), // version
+        UInt16(256), // UI name ID
+    ]);
+    for tag in &[b"ss00", b"ss21", b"cv00"] {
+        let feature = Feature::parse(Tag::from_bytes(tag), &data).unwrap();
+        assert!(feature.params.is_none());
+    }
+
+    let feature = Feature::parse(Tag::from_bytes(b"ss20"), &data).unwrap();
+    assert!(feature.params.is_some());
+}
+
+#[test]
+fn size_relative_to_feature_list() {
+    let list_data = convert(&[
+        UInt16(1), // feature count
+        Raw(b"size"), UInt16(8), // feature record [0]
+
+        // Feature [0].
+        UInt16(12), // offset to feature params from the beginning of the FeatureList
+        UInt16(0), // lookup index count
+
+        // Feature params.
+        UInt16(100), // design size
+        UInt16(1), // subfamily ID
+        UInt16(256), // subfamily name ID
+        UInt16(80), // range start
+        UInt16(140), // range end
+    ]);
+    let data = &list_data[8..];
+    let tag = Tag::from_bytes(b"size");
+    assert!(Feature::parse(tag, data).unwrap().params.is_none());
+
+    let feature = Feature::parse_in_list(tag, data, &list_data).unwrap();
+    match feature.params {
+        Some(FeatureParams::Size(params)) => assert_eq!(params.design_size, 100),
+        _ => panic!("invalid params"),
+    }
+}
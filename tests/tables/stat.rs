@@ -0,0 +1,297 @@
+use ttf_parser::stat::{AxisValueKind, Table};
+use ttf_parser::{Face, RawFaceTables, Tag};
+use crate::{convert, demo_face_tables, Unit::*};
+
+fn stat_data() -> Vec<u8> {
+    convert(&[
+        UInt16(1), // major version
+        UInt16(1), // minor version
+        UInt16(8), // design axis size
+        UInt16(3), // design axis count
+        UInt32(20), // offset to design axes
+        UInt16(7), // axis value count
+        UInt32(44), // offset to axis value offsets
+        UInt16(307), // elided fallback name ID
+
+        // Design axes.
+        Raw(b"wght"), UInt16(256), UInt16(1),
+        Raw(b"wdth"), UInt16(257), UInt16(0),
+        Raw(b"ital"), UInt16(258), UInt16(2),
+
+        // Axis value offsets.
+        UInt16(14),
+        UInt16(26),
+        UInt16(46),
+        UInt16(62),
+        UInt16(74),
+        UInt16(86),
+        UInt16(98),
+
+        // Regular
+        UInt16(1), // format
+        UInt16(0), // axis index
+        UInt16(2), // flags: elidable
+        UInt16(300), // value name ID
+        Fixed(400.0), // value
+
+        // SemiBold
+        UInt16(2), // format
+        UInt16(0), // axis index
+        UInt16(0), // flags
+        UInt16(301), // value name ID
+        Fixed(600.0), // nominal value
+        Fixed(550.0), // range min value
+        Fixed(650.0), // range max value
+
+        // Bold
+        UInt16(3), // format
+        UInt16(0), // axis index
+        UInt16(0), // flags
+        UInt16(302), // value name ID
+        Fixed(700.0), // value
+        Fixed(400.0), // linked value
+
+        // Normal
+        UInt16(1), // format
+        UInt16(1), // axis index
+        UInt16(2), // flags: elidable
+        UInt16(303), // value name ID
+        Fixed(100.0), // value
+
+        // Condensed
+        UInt16(1), // format
+        UInt16(1), // axis index
+        UInt16(0), // flags
+        UInt16(304), // value name ID
+        Fixed(75.0), // value
+
+        // Roman
+        UInt16(1), // format
+        UInt16(2), // axis index
+        UInt16(2), // flags: elidable
+        UInt16(305), // value name ID
+        Fixed(0.0), // value
+
+        // Black Condensed
+        UInt16(4), // format
+        UInt16(2), // axis count
+        UInt16(0), // flags
+        UInt16(306), // value name ID
+        UInt16(0), Fixed(900.0), // axis index, value
+        UInt16(1), Fixed(75.0), // axis index, value
+    ])
+}
+
+fn fvar_data() -> Vec<u8> {
+    convert(&[
+        UInt32(0x00010000), // version
+        UInt16(16), // offset to axes array
+        UInt16(2), // reserved
+        UInt16(2), // axis count
+        UInt16(20), // axis size
+        UInt16(0), // instance count
+        UInt16(12), // instance size
+
+        Raw(b"wght"), Fixed(100.0), Fixed(400.0), Fixed(900.0), UInt16(0), UInt16(256),
+        Raw(b"wdth"), Fixed(75.0), Fixed(100.0), Fixed(100.0), UInt16(0), UInt16(257),
+    ])
+}
+
+// Creates a `name` table with Windows English names.
+fn name_data(names: &[(u16, &str)]) -> Vec<u8> {
+    let mut records = Vec::new();
+    let mut storage = Vec::new();
+    for (name_id, name) in names {
+        let encoded: Vec<u8> = name.encode_utf16().flat_map(|c| c.to_be_bytes()).collect();
+        records.extend_from_slice(&convert(&[
+            UInt16(3), // platform ID
+            UInt16(1), // encoding ID
+            UInt16(0x0409), // language ID
+            UInt16(*name_id),
+            UInt16(encoded.len() as u16),
+            UInt16(storage.len() as u16),
+        ]));
+        storage.extend_from_slice(&encoded);
+    }
+
+    let mut data = convert(&[
+        UInt16(0), // format
+        UInt16(names.len() as u16), // count
+        UInt16(6 + records.len() as u16), // storage offset
+    ]);
+    data.extend_from_slice(&records);
+    data.extend_from_slice(&storage);
+    data
+}
+
+fn names() -> Vec<u8> {
+    name_data(&[
+        (2, "Regular"),
+        (300, "Regular"),
+        (301, "SemiBold"),
+        (302, "Bold"),
+        (303, "Normal"),
+        (304, "Condensed"),
+        (305, "Roman"),
+        (306, "Black Condensed"),
+        (307, "Fallback"),
+    ])
+}
+
+fn style_names(face: &Face) -> Vec<String> {
+    face.style_names()
+        .map(|n| n.name.unwrap().to_string().unwrap())
+        .collect()
+}
+
+fn face<'a>(stat: Option<&'a [u8]>, fvar: &'a [u8], name: &'a [u8]) -> Face<'a> {
+    let tables = RawFaceTables {
+        stat,
+        fvar: Some(fvar),
+        name: Some(name),
+        ..demo_face_tables()
+    };
+    Face::from_raw_tables(tables).unwrap()
+}
+
+#[test]
+fn parse() {
+    let data = stat_data();
+    let table = Table::parse(&data).unwrap();
+    assert_eq!(table.axes.len(), 3);
+    assert_eq!(table.axes.get(1).unwrap().tag, Tag::from_bytes(b"wdth"));
+    assert_eq!(table.axes.get(1).unwrap().ordering, 0);
+    assert_eq!(table.values.len(), 7);
+    assert_eq!(table.elided_fallback_name_id, Some(307));
+
+    let value = table.values.get(0).unwrap();
+    assert!(value.flags.elidable());
+    assert_eq!(value.value_name_id, 300);
+    assert!(matches!(value.kind, AxisValueKind::Value { axis_index: 0, value } if value == 400.0));
+
+    let value = table.values.get(1).unwrap();
+    assert!(!value.flags.elidable());
+    assert!(matches!(
+        value.kind,
+        AxisValueKind::Range { axis_index: 0, nominal_value, range_min_value, range_max_value }
+            if nominal_value == 600.0 && range_min_value == 550.0 && range_max_value == 650.0
+    ));
+
+    let value = table.values.get(2).unwrap();
+    assert!(matches!(
+        value.kind,
+        AxisValueKind::Linked { axis_index: 0, value, linked_value }
+            if value == 700.0 && linked_value == 400.0
+    ));
+
+    let value = table.values.get(6).unwrap();
+    match value.kind {
+        AxisValueKind::Multiple(records) => {
+            assert_eq!(records.len(), 2);
+            assert_eq!(records.get(1).unwrap().axis_index, 1);
+            assert_eq!(records.get(1).unwrap().value, 75.0);
+        }
+        _ => panic!("invalid format"),
+    }
+    assert!(value.has_axis(0));
+    assert!(value.has_axis(1));
+    assert!(!value.has_axis(2));
+}
+
+#[test]
+fn parse_larger_design_axis_size() {
+    let data = convert(&[
+        UInt16(1), // major version
+        UInt16(0), // minor version
+        UInt16(10), // design axis size
+        UInt16(2), // design axis count
+        UInt32(18), // offset to design axes
+        UInt16(0), // axis value count
+        UInt32(0), // offset to axis value offsets
+
+        // Design axes.
+        Raw(b"wght"), UInt16(256), UInt16(0), UInt16(0),
+        Raw(b"wdth"), UInt16(257), UInt16(1), UInt16(0),
+    ]);
+
+    let table = Table::parse(&data).unwrap();
+    let tags: Vec<_> = table.axes.into_iter().map(|a| a.tag).collect();
+    assert_eq!(tags, [Tag::from_bytes(b"wght"), Tag::from_bytes(b"wdth")]);
+    assert!(table.values.is_empty());
+    assert_eq!(table.elided_fallback_name_id, None);
+}
+
+#[test]
+fn parse_invalid_design_axis_size() {
+    let data = convert(&[
+        UInt16(1), // major version
+        UInt16(0), // minor version
+        UInt16(6), // design axis size
+        UInt16(0), // design axis count
+        UInt32(0), // offset to design axes
+        UInt16(0), // axis value count
+        UInt32(0), // offset to axis value offsets
+    ]);
+
+    assert!(Table::parse(&data).is_none());
+}
+
+#[test]
+fn style_names_default() {
+    let (stat, fvar, name) = (stat_data(), fvar_data(), names());
+    let face = face(Some(&stat), &fvar, &name);
+    assert_eq!(style_names(&face), ["Normal", "Regular", "Roman"]);
+    assert!(face.style_names().all(|n| n.value.flags.elidable()));
+    assert_eq!(
+        face.style_elided_fallback_name().unwrap().to_string().unwrap(),
+        "Fallback"
+    );
+}
+
+#[test]
+fn style_names_for_coordinates() {
+    let (stat, fvar, name) = (stat_data(), fvar_data(), names());
+    let mut face = face(Some(&stat), &fvar, &name);
+
+    face.set_variation(Tag::from_bytes(b"wght"), 600.0);
+    assert_eq!(style_names(&face), ["Normal", "SemiBold", "Roman"]);
+
+    face.set_variation(Tag::from_bytes(b"wght"), 620.0);
+    assert_eq!(style_names(&face), ["Normal", "SemiBold", "Roman"]);
+
+    face.set_variation(Tag::from_bytes(b"wght"), 700.0);
+    assert_eq!(style_names(&face), ["Normal", "Bold", "Roman"]);
+
+    face.set_variation(Tag::from_bytes(b"wdth"), 75.0);
+    assert_eq!(style_names(&face), ["Condensed", "Bold", "Roman"]);
+
+    // No name for this weight.
+    face.set_variation(Tag::from_bytes(b"wght"), 500.0);
+    assert_eq!(style_names(&face), ["Condensed", "Roman"]);
+}
+
+#[test]
+fn style_names_multiple_axes() {
+    let (stat, fvar, name) = (stat_data(), fvar_data(), names());
+    let mut face = face(Some(&stat), &fvar, &name);
+    face.set_variation(Tag::from_bytes(b"wght"), 900.0);
+    face.set_variation(Tag::from_bytes(b"wdth"), 75.0);
+
+    let names: Vec<_> = face.style_names().collect();
+    assert_eq!(names.len(), 2);
+    assert_eq!(names[0].axis.tag, Tag::from_bytes(b"wdth"));
+    assert_eq!(names[0].name.unwrap().to_string().unwrap(), "Black Condensed");
+    assert_eq!(names[1].name.unwrap().to_string().unwrap(), "Roman");
+}
+
+#[test]
+fn elided_fallback_name_without_stat() {
+    let (fvar, name) = (fvar_data(), names());
+    let face = face(None, &fvar, &name);
+
+    assert_eq!(face.style_names().count(), 0);
+    assert_eq!(
+        face.style_elided_fallback_name().unwrap().to_string().unwrap(),
+        "Regular"
+    );
+}
@@ -0,0 +1,96 @@
+use ttf_parser::vdmx::{Record, Table};
+use ttf_parser::{Face, RawFaceTables};
+use crate::{convert, demo_face_tables, Unit::*};
+
+fn vdmx_data() -> Vec<u8> {
+    convert(&[
+        UInt16(1), // version
+        UInt16(2), // number of groups
+        UInt16(3), // number of ratio ranges
+        // Ratio ranges.
+        UInt8(1), UInt8(1), UInt8(1), UInt8(1), // char set, x ratio, y start ratio, y end ratio
+        UInt8(1), UInt8(2), UInt8(1), UInt8(2), // char set, x ratio, y start ratio, y end ratio
+        UInt8(1), UInt8(0), UInt8(0), UInt8(0), // char set, x ratio, y start ratio, y end ratio
+        // Offsets.
+        UInt16(24), // offset to group [0]
+        UInt16(46), // offset to group [1]
+        UInt16(24), // offset to group [0]
+        // Group [0].
+        UInt16(3), // number of records
+        UInt8(8), // start size
+        UInt8(10), // end size
+        UInt16(8), Int16(7), Int16(-2), // y pel height, y max, y min
+        UInt16(9), Int16(8), Int16(-2), // y pel height, y max, y min
+        UInt16(10), Int16(9), Int16(-3), // y pel height, y max, y min
+        // Group [1].
+        UInt16(1), // number of records
+        UInt8(8), // start size
+        UInt8(8), // end size
+        UInt16(8), Int16(4), Int16(-1), // y pel height, y max, y min
+    ])
+}
+
+#[test]
+fn parse() {
+    let data = vdmx_data();
+    let table = Table::parse(&data).unwrap();
+    assert_eq!(table.ratio_ranges.len(), 3);
+
+    let range = table.ratio_ranges.get(1).unwrap();
+    assert_eq!(range.x_ratio, 2);
+    assert_eq!(range.y_start_ratio, 1);
+    assert_eq!(range.y_end_ratio, 2);
+    assert!(range.contains(2, 1));
+    assert!(range.contains(4, 3));
+    assert!(!range.contains(1, 2));
+
+    let group = table.group(0).unwrap();
+    assert_eq!(group.start_size, 8);
+    assert_eq!(group.end_size, 10);
+    assert_eq!(group.records.len(), 3);
+    assert!(table.group(3).is_none());
+
+    // 1:1
+    assert_eq!(
+        table.extents(9, 1, 1),
+        Some(Record { y_pel_height: 9, y_max: 8, y_min: -2 })
+    );
+    // 2:1
+    assert_eq!(
+        table.extents(8, 2, 1),
+        Some(Record { y_pel_height: 8, y_max: 4, y_min: -1 })
+    );
+    assert_eq!(table.extents(9, 2, 1), None);
+    // 1:2, default range.
+    assert_eq!(
+        table.extents(10, 1, 2),
+        Some(Record { y_pel_height: 10, y_max: 9, y_min: -3 })
+    );
+    assert_eq!(table.extents(11, 1, 2), None);
+}
+
+#[test]
+fn invalid_version() {
+    let data = convert(&[
+        UInt16(2), // version
+        UInt16(0), // number of groups
+        UInt16(0), // number of ratio ranges
+    ]);
+    assert!(Table::parse(&data).is_none());
+}
+
+#[test]
+fn face_extents() {
+    let tables = demo_face_tables();
+
+    let face = Face::from_raw_tables(tables.clone()).unwrap();
+    assert_eq!(face.device_vertical_extents(8, 1, 1), None);
+
+    let data = vdmx_data();
+    let tables = RawFaceTables { vdmx: Some(&data), ..tables };
+    let face = Face::from_raw_tables(tables).unwrap();
+    assert_eq!(
+        face.device_vertical_extents(8, 1, 1),
+        Some(Record { y_pel_height: 8, y_max: 7, y_min: -2 })
+    );
+}
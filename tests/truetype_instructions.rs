@@ -0,0 +1,92 @@
+use ttf_parser::truetype_instructions::{Instructions, Operands};
+use ttf_parser::{fpgm, prep};
+
+fn disassemble(data: &[u8]) -> Vec<String> {
+    Instructions::new(data)
+        .map(|i| {
+            let name = i.name().unwrap_or("?");
+            match i.operands {
+                Operands::None => format!("{}:{}[{:#04X}]", i.offset, name, i.opcode),
+                Operands::Bytes(values) => {
+                    let values: Vec<_> = values.into_iter().map(|v| v.to_string()).collect();
+                    format!("{}:{} {}", i.offset, name, values.join(" "))
+                }
+                Operands::Words(values) => {
+                    let values: Vec<_> = values.into_iter().map(|v| v.to_string()).collect();
+                    format!("{}:{} {}", i.offset, name, values.join(" "))
+                }
+            }
+        })
+        .collect()
+}
+
+#[test]
+fn push_instructions() {
+    #[rustfmt::skip]
+    let data = &[
+        0x40, 2, 1, 2, // NPUSHB 1 2
+        0x41, 1, 0xFF, 0xFE, // NPUSHW -2
+        0xB2, 3, 4, 5, // PUSHB[2] 3 4 5
+        0xB9, 0x01, 0x00, 0x80, 0x00, // PUSHW[1] 256 -32768
+    ];
+
+    assert_eq!(
+        disassemble(data),
+        [
+            "0:NPUSHB 1 2",
+            "4:NPUSHW -2",
+            "8:PUSHB 3 4 5",
+            "12:PUSHW 256 -32768"
+        ]
+    );
+}
+
+#[test]
+fn flags() {
+    #[rustfmt::skip]
+    let data = &[
+        0x00, // SVTCA[y]
+        0x01, // SVTCA[x]
+        0x2F, // MDAP[r]
+        0xE9, // MIRP[01001]
+        0x28, // undefined
+    ];
+
+    assert_eq!(
+        disassemble(data),
+        [
+            "0:SVTCA[0x00]",
+            "1:SVTCA[0x01]",
+            "2:MDAP[0x2F]",
+            "3:MIRP[0xE9]",
+            "4:?[0x28]"
+        ]
+    );
+}
+
+#[test]
+fn truncated() {
+    let data = &[0x20, 0xB1, 1];
+    let mut iter = Instructions::new(data);
+    assert_eq!(iter.next().map(|i| i.opcode), Some(0x20));
+    assert!(iter.next().is_none());
+    assert!(iter.next().is_none());
+    assert_eq!(iter.offset(), 1);
+
+    let data = &[0x40];
+    assert!(Instructions::new(data).next().is_none());
+}
+
+#[test]
+fn programs() {
+    let data = &[0xB0, 0, 0x2C, 0x2D];
+    let table = fpgm::Table::parse(data).unwrap();
+    assert_eq!(
+        disassemble(table.instructions),
+        ["0:PUSHB 0", "2:FDEF[0x2C]", "3:ENDF[0x2D]"]
+    );
+    assert_eq!(table.decode().count(), 3);
+
+    let table = prep::Table::parse(&[0x4B]).unwrap();
+    assert_eq!(table.decode().next().and_then(|i| i.name()), Some("MPPEM"));
+}
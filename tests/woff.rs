@@ -0,0 +1,174 @@
+#![cfg(feature = "woff")]
+
+use ttf_parser::woff::{Error, Woff};
+use ttf_parser::{Face, GlyphId, RawFace, Tag};
+
+static FONT_DATA: &[u8] = include_bytes!("fonts/demo.ttf");
+
+// Converts a TrueType font into WOFF.
+fn to_woff(data: &[u8]) -> Vec<u8> {
+    let raw_face = RawFace::parse(data, 0).unwrap();
+    let num_tables = raw_face.table_records.len();
+
+    let mut tables = Vec::new();
+    for record in raw_face.table_records {
+        let table = raw_face.table(record.tag).unwrap();
+        let compressed = miniz_oxide::deflate::compress_to_vec_zlib(table, 6);
+        // Store a table uncompressed when compression doesn't help.
+        let stored = if compressed.len() < table.len() {
+            compressed
+        } else {
+            table.to_vec()
+        };
+        tables.push((record.tag, table.len() as u32, stored));
+    }
+
+    let mut directory = Vec::new();
+    let mut body = Vec::new();
+    for (tag, orig_len, stored) in &tables {
+        let offset = 44 + 20 * num_tables as usize + body.len();
+        directory.extend_from_slice(&tag.0.to_be_bytes());
+        directory.extend_from_slice(&(offset as u32).to_be_bytes());
+        directory.extend_from_slice(&(stored.len() as u32).to_be_bytes());
+        directory.extend_from_slice(&orig_len.to_be_bytes());
+        directory.extend_from_slice(&0u32.to_be_bytes());
+        body.extend_from_slice(stored);
+        while body.len() % 4 != 0 {
+            body.push(0);
+        }
+    }
+
+    let total_len = 44 + directory.len() + body.len();
+    let mut woff = Vec::new();
+    woff.extend_from_slice(b"wOFF");
+    woff.extend_from_slice(&0x00010000u32.to_be_bytes()); // flavor
+    woff.extend_from_slice(&(total_len as u32).to_be_bytes()); // length
+    woff.extend_from_slice(&num_tables.to_be_bytes()); // numTables
+    woff.extend_from_slice(&0u16.to_be_bytes()); // reserved
+    woff.extend_from_slice(&(data.len() as u32).to_be_bytes()); // totalSfntSize
+    woff.extend_from_slice(&1u16.to_be_bytes()); // majorVersion
+    woff.extend_from_slice(&0u16.to_be_bytes()); // minorVersion
+    woff.extend_from_slice(&[0; 20]); // metadata and private data
+    woff.extend_from_slice(&directory);
+    woff.extend_from_slice(&body);
+    woff
+}
+
+#[test]
+fn decompress_tables() {
+    let data = to_woff(FONT_DATA);
+    let woff = Woff::parse(&data).unwrap();
+    assert_eq!(woff.flavor, 0x00010000);
+    assert_eq!(woff.major_version, 1);
+
+    let mut buffer = vec![0; woff.tables_len().unwrap()];
+    let tables = woff.decompress_tables(&mut buffer).unwrap();
+    let face = Face::from_raw_tables(tables).unwrap();
+
+    let orig_face = Face::parse(FONT_DATA, 0).unwrap();
+    assert_eq!(face.number_of_glyphs(), orig_face.number_of_glyphs());
+    assert_eq!(face.glyph_index('A'), orig_face.glyph_index('A'));
+    assert_eq!(
+        face.glyph_bounding_box(GlyphId(1)),
+        orig_face.glyph_bounding_box(GlyphId(1))
+    );
+}
+
+#[test]
+fn decompress_single_table() {
+    let data = to_woff(FONT_DATA);
+    let woff = Woff::parse(&data).unwrap();
+    let record = woff.table(Tag::from_bytes(b"glyf")).unwrap();
+
+    let mut buffer = vec![0; record.original_length as usize];
+    let table = woff.decompress_table(record, &mut buffer).unwrap();
+    let orig_table = RawFace::parse(FONT_DATA, 0)
+        .unwrap()
+        .table(Tag::from_bytes(b"glyf"))
+        .unwrap();
+    assert_eq!(table, orig_table);
+}
+
+#[test]
+fn to_sfnt() {
+    let data = to_woff(FONT_DATA);
+    let woff = Woff::parse(&data).unwrap();
+    let sfnt = woff.to_sfnt().unwrap();
+    assert_eq!(sfnt.len(), woff.sfnt_len().unwrap());
+
+    let face = Face::parse(&sfnt, 0).unwrap();
+    let orig_face = Face::parse(FONT_DATA, 0).unwrap();
+    assert_eq!(face.number_of_glyphs(), orig_face.number_of_glyphs());
+    assert_eq!(face.ascender(), orig_face.ascender());
+    assert_eq!(
+        face.raw_face().table(Tag::from_bytes(b"cmap")),
+        orig_face.raw_face().table(Tag::from_bytes(b"cmap"))
+    );
+}
+
+#[test]
+fn buffer_too_small() {
+    let data = to_woff(FONT_DATA);
+    let woff = Woff::parse(&data).unwrap();
+    let mut buffer = vec![0; woff.tables_len().unwrap() - 1];
+    assert_eq!(
+        woff.decompress_tables(&mut buffer).err(),
+        Some(Error::BufferTooSmall)
+    );
+}
+
+#[test]
+fn impossible_compression_ratio() {
+    let mut data = to_woff(FONT_DATA);
+    let woff = Woff::parse(&data).unwrap();
+    let (index, record) = woff
+        .tables
+        .into_iter()
+        .enumerate()
+        .min_by_key(|(_, r)| r.compressed_length)
+        .unwrap();
+
+    // Declare an original length that cannot be produced by deflate.
+    let orig_len_offset = 44 + 20 * index + 12;
+    let orig_len = record.compressed_length * 2000;
+    data[orig_len_offset..orig_len_offset + 4].copy_from_slice(&orig_len.to_be_bytes());
+    let woff = Woff::parse(&data).unwrap();
+
+    let mut buffer = vec![0; woff.tables_len().unwrap()];
+    assert_eq!(
+        woff.decompress_tables(&mut buffer).err(),
+        Some(Error::MalformedFont)
+    );
+    let mut buffer = vec![0; woff.sfnt_len().unwrap()];
+    assert_eq!(
+        woff.decompress_sfnt(&mut buffer).err(),
+        Some(Error::MalformedFont)
+    );
+    assert_eq!(woff.to_sfnt().err(), Some(Error::MalformedFont));
+}
+
+#[test]
+fn corrupted_stream() {
+    let mut data = to_woff(FONT_DATA);
+    let woff = Woff::parse(&data).unwrap();
+    let record = woff
+        .tables
+        .into_iter()
+        .find(|r| r.compressed_length < r.original_length)
+        .unwrap();
+
+    // Break the zlib header.
+    data[record.offset as usize] = 0xFF;
+    let woff = Woff::parse(&data).unwrap();
+    let mut buffer = vec![0; woff.tables_len().unwrap()];
+    assert_eq!(
+        woff.decompress_tables(&mut buffer).err(),
+        Some(Error::DecompressionFailed)
+    );
+}
+
+#[test]
+fn not_woff() {
+    assert_eq!(Woff::parse(FONT_DATA).unwrap_err(), Error::UnknownSignature);
+    assert_eq!(Woff::parse(b"wOFF").unwrap_err(), Error::MalformedFont);
+}
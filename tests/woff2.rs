@@ -0,0 +1,328 @@
+#![cfg(feature = "woff2")]
+
+use std::fmt::Write;
+
+use ttf_parser::woff2::{Error, Woff2};
+use ttf_parser::{Face, GlyphId, RawFace, Rect, Tag};
+
+static FONT_DATA: &[u8] = include_bytes!("fonts/demo.ttf");
+
+const FLAVOR: u32 = 0x00010000;
+const COLLECTION_FLAVOR: u32 = 0x74746366;
+
+// A transformed glyf table with 3 glyphs: an empty one, a triangle
+// and a composite one, which references the triangle with an offset.
+#[rustfmt::skip]
+fn transformed_glyf() -> Vec<u8> {
+    let n_contour_stream: &[u8] = &[0x00, 0x00, 0x00, 0x01, 0xFF, 0xFF];
+    let n_points_stream: &[u8] = &[3];
+    let flag_stream: &[u8] = &[
+        11, // dx: +10, dy: 0, on-curve
+        11, // dx: +100, dy: 0, on-curve
+        0x80 | 90, // dx: -50, dy: +300, off-curve
+    ];
+    let glyph_stream: &[u8] = &[
+        10,
+        100,
+        49, 43,
+        0, // instructions length
+    ];
+    let composite_stream: &[u8] = &[
+        0x00, 0x03, // flags: ARG_1_AND_2_ARE_WORDS | ARGS_ARE_XY_VALUES
+        0x00, 0x01, // glyph index
+        0x00, 0xC8, // dx: 200
+        0x00, 0x00, // dy: 0
+    ];
+    let bbox_stream: &[u8] = &[
+        0x20, 0x00, 0x00, 0x00, // bbox bitmap, set only for the composite glyph
+        0x00, 0xD2, 0x00, 0x00, 0x01, 0x36, 0x01, 0x2C, // 210, 0, 310, 300
+    ];
+
+    let mut data = Vec::new();
+    data.extend_from_slice(&0u16.to_be_bytes()); // reserved
+    data.extend_from_slice(&0u16.to_be_bytes()); // optionFlags
+    data.extend_from_slice(&3u16.to_be_bytes()); // numGlyphs
+    data.extend_from_slice(&0u16.to_be_bytes()); // indexFormat
+    let streams = [
+        n_contour_stream, n_points_stream, flag_stream, glyph_stream,
+        composite_stream, bbox_stream, &[], // instructions
+    ];
+    for stream in &streams {
+        data.extend_from_slice(&(stream.len() as u32).to_be_bytes());
+    }
+    for stream in &streams {
+        data.extend_from_slice(stream);
+    }
+    data
+}
+
+#[rustfmt::skip]
+fn transformed_hmtx() -> Vec<u8> {
+    vec![
+        0x03, // both lsb arrays are absent
+        0x01, 0xF4, // advance: 500
+        0x01, 0x90, // advance: 400
+    ]
+}
+
+struct Table {
+    tag: Tag,
+    transform_version: u8,
+    data: Vec<u8>,
+    transformed: Option<Vec<u8>>,
+}
+
+// Returns a list of tables with their transform version, original and transformed data.
+fn tables() -> Vec<Table> {
+    let raw_face = RawFace::parse(FONT_DATA, 0).unwrap();
+    let mut tables = Vec::new();
+    for record in raw_face.table_records {
+        let tag = record.tag;
+        let mut data = raw_face.table(tag).unwrap().to_vec();
+        let (transform_version, data, transformed) = match &tag.to_bytes() {
+            b"glyf" => (0, vec![0; 48], Some(transformed_glyf())),
+            b"loca" => (0, vec![0; 8], Some(Vec::new())),
+            b"hmtx" => (1, vec![0; 10], Some(transformed_hmtx())),
+            b"hhea" => {
+                data[34..36].copy_from_slice(&2u16.to_be_bytes()); // numberOfHMetrics
+                (0, data, None)
+            }
+            b"maxp" => {
+                data[4..6].copy_from_slice(&3u16.to_be_bytes()); // numGlyphs
+                (0, data, None)
+            }
+            _ => (0, data, None),
+        };
+        tables.push(Table {
+            tag,
+            transform_version,
+            data,
+            transformed,
+        });
+    }
+    tables
+}
+
+fn write_base128(data: &mut Vec<u8>, n: u32) {
+    let mut bytes = vec![(n & 0x7F) as u8];
+    let mut n = n >> 7;
+    while n != 0 {
+        bytes.push((n & 0x7F) as u8 | 0x80);
+        n >>= 7;
+    }
+    bytes.reverse();
+    data.extend_from_slice(&bytes);
+}
+
+// Wraps data into a Brotli stream of uncompressed meta-blocks.
+fn brotli_stored(data: &[u8]) -> Vec<u8> {
+    struct BitWriter {
+        data: Vec<u8>,
+        bit: u32,
+    }
+
+    impl BitWriter {
+        fn write(&mut self, value: u32, bits: u32) {
+            for i in 0..bits {
+                if self.bit == 0 {
+                    self.data.push(0);
+                }
+                *self.data.last_mut().unwrap() |= (((value >> i) & 1) as u8) << self.bit;
+                self.bit = (self.bit + 1) % 8;
+            }
+        }
+    }
+
+    let mut w = BitWriter {
+        data: Vec::new(),
+        bit: 0,
+    };
+    w.write(0, 1); // WBITS: 16
+    for chunk in data.chunks(65536) {
+        w.write(0, 1); // ISLAST
+        w.write(0, 2); // MNIBBLES: 4
+        w.write(chunk.len() as u32 - 1, 16); // MLEN - 1
+        w.write(1, 1); // ISUNCOMPRESSED
+        w.bit = 0;
+        w.data.extend_from_slice(chunk);
+    }
+    w.write(1, 1); // ISLAST
+    w.write(1, 1); // ISLASTEMPTY
+    w.data
+}
+
+fn to_woff2(flavor: u32, collection_directory: &[u8]) -> Vec<u8> {
+    let tables = tables();
+
+    let mut directory = Vec::new();
+    let mut stream = Vec::new();
+    for table in &tables {
+        let version = table.transform_version;
+        match &table.tag.to_bytes() {
+            b"glyf" => directory.push(10 | (version << 6)),
+            b"loca" => directory.push(11 | (version << 6)),
+            b"hmtx" => directory.push(3 | (version << 6)),
+            _ => {
+                directory.push(63 | (version << 6));
+                directory.extend_from_slice(&table.tag.0.to_be_bytes());
+            }
+        }
+        write_base128(&mut directory, table.data.len() as u32);
+        if let Some(ref transformed) = table.transformed {
+            write_base128(&mut directory, transformed.len() as u32);
+            stream.extend_from_slice(transformed);
+        } else {
+            stream.extend_from_slice(&table.data);
+        }
+    }
+    directory.extend_from_slice(collection_directory);
+
+    let compressed = brotli_stored(&stream);
+    let total_len = 48 + directory.len() + compressed.len();
+    let mut woff2 = Vec::new();
+    woff2.extend_from_slice(b"wOF2");
+    woff2.extend_from_slice(&flavor.to_be_bytes());
+    woff2.extend_from_slice(&(total_len as u32).to_be_bytes()); // length
+    woff2.extend_from_slice(&(tables.len() as u16).to_be_bytes()); // numTables
+    woff2.extend_from_slice(&0u16.to_be_bytes()); // reserved
+    woff2.extend_from_slice(&0u32.to_be_bytes()); // totalSfntSize
+    woff2.extend_from_slice(&(compressed.len() as u32).to_be_bytes()); // totalCompressedSize
+    woff2.extend_from_slice(&1u16.to_be_bytes()); // majorVersion
+    woff2.extend_from_slice(&0u16.to_be_bytes()); // minorVersion
+    woff2.extend_from_slice(&[0; 20]); // metadata and private data
+    woff2.extend_from_slice(&directory);
+    woff2.extend_from_slice(&compressed);
+    woff2
+}
+
+struct Builder(String);
+
+impl ttf_parser::OutlineBuilder for Builder {
+    fn move_to(&mut self, x: f32, y: f32) {
+        write!(&mut self.0, "M {} {} ", x, y).unwrap();
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        write!(&mut self.0, "L {} {} ", x, y).unwrap();
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        write!(&mut self.0, "Q {} {} {} {} ", x1, y1, x, y).unwrap();
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        write!(&mut self.0, "C {} {} {} {} {} {} ", x1, y1, x2, y2, x, y).unwrap();
+    }
+
+    fn close(&mut self) {
+        write!(&mut self.0, "Z ").unwrap();
+    }
+}
+
+fn outline(face: &Face, glyph_id: GlyphId) -> String {
+    let mut builder = Builder(String::new());
+    face.outline_glyph(glyph_id, &mut builder);
+    builder.0
+}
+
+#[test]
+fn reconstruct_transformed_tables() {
+    let data = to_woff2(FLAVOR, &[]);
+    let woff2 = Woff2::parse(&data).unwrap();
+    assert_eq!(woff2.flavor, FLAVOR);
+    assert!(!woff2.is_collection());
+
+    let glyf = woff2
+        .tables
+        .iter()
+        .find(|t| t.tag == Tag::from_bytes(b"glyf"))
+        .unwrap();
+    assert!(glyf.is_transformed());
+
+    let sfnt = woff2.to_sfnt().unwrap();
+    let face = Face::parse(&sfnt, 0).unwrap();
+    assert_eq!(face.number_of_glyphs(), 3);
+    assert_eq!(face.glyph_index('A'), Some(GlyphId(1)));
+
+    assert_eq!(outline(&face, GlyphId(0)), "");
+    assert_eq!(
+        outline(&face, GlyphId(1)),
+        "M 10 0 L 110 0 Q 60 300 10 0 Z "
+    );
+    assert_eq!(
+        outline(&face, GlyphId(2)),
+        "M 210 0 L 310 0 Q 260 300 210 0 Z "
+    );
+    assert_eq!(
+        face.glyph_bounding_box(GlyphId(2)),
+        Some(Rect {
+            x_min: 210,
+            y_min: 0,
+            x_max: 310,
+            y_max: 300
+        })
+    );
+
+    // Left side bearings are restored from glyphs' bounding boxes.
+    assert_eq!(face.glyph_hor_advance(GlyphId(1)), Some(400));
+    assert_eq!(face.glyph_hor_advance(GlyphId(2)), Some(400));
+    assert_eq!(face.glyph_hor_side_bearing(GlyphId(0)), Some(0));
+    assert_eq!(face.glyph_hor_side_bearing(GlyphId(1)), Some(10));
+    assert_eq!(face.glyph_hor_side_bearing(GlyphId(2)), Some(210));
+}
+
+#[test]
+fn reconstruct_collection() {
+    let num_tables = tables().len() as u8;
+    let mut directory = Vec::new();
+    directory.extend_from_slice(&0x00010000u32.to_be_bytes()); // ttcVersion
+    directory.push(2); // numFonts
+    for _ in 0..2 {
+        directory.push(num_tables);
+        directory.extend_from_slice(&FLAVOR.to_be_bytes());
+        directory.extend(0..num_tables);
+    }
+
+    let data = to_woff2(COLLECTION_FLAVOR, &directory);
+    let woff2 = Woff2::parse(&data).unwrap();
+    assert!(woff2.is_collection());
+    assert_eq!(woff2.collection_fonts.len(), 2);
+
+    let sfnt = woff2.to_sfnt().unwrap();
+    assert_eq!(ttf_parser::fonts_in_collection(&sfnt), Some(2));
+
+    let face1 = Face::parse(&sfnt, 0).unwrap();
+    let face2 = Face::parse(&sfnt, 1).unwrap();
+    assert_eq!(outline(&face2, GlyphId(2)), outline(&face1, GlyphId(2)));
+
+    // Shared tables are stored only once.
+    let glyf = Tag::from_bytes(b"glyf");
+    assert_eq!(
+        face1.raw_face().table(glyf).unwrap().as_ptr(),
+        face2.raw_face().table(glyf).unwrap().as_ptr()
+    );
+}
+
+#[test]
+fn corrupted_stream() {
+    let mut data = to_woff2(FLAVOR, &[]);
+    // Remove the last meta-block header.
+    let len = data.len();
+    data.truncate(len - 1);
+    data[8..12].copy_from_slice(&(len as u32 - 1).to_be_bytes());
+    let compressed_len = u32::from_be_bytes([data[20], data[21], data[22], data[23]]);
+    data[20..24].copy_from_slice(&(compressed_len - 1).to_be_bytes());
+
+    let woff2 = Woff2::parse(&data).unwrap();
+    assert_eq!(woff2.to_sfnt().unwrap_err(), Error::DecompressionFailed);
+}
+
+#[test]
+fn not_woff2() {
+    assert_eq!(
+        Woff2::parse(FONT_DATA).unwrap_err(),
+        Error::UnknownSignature
+    );
+    assert_eq!(Woff2::parse(b"wOF2").unwrap_err(), Error::MalformedFont);
+    assert!(!ttf_parser::woff2::is_woff2(FONT_DATA));
+}